@@ -0,0 +1,121 @@
+//! Benchmarks for the comparison of matrices with different storage formats.
+//!
+//! Run with `cargo bench`. The benchmarks compare matrices that are equal, so that every element
+//! is visited, which is the common case in tests that pass.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{compare_matrices, SparseAccess};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, dense_matrix_strategy_normal_f64, sparse_matrix_strategy_i64,
+    MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::strategy::{Just, Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+use std::hint::black_box;
+
+/// The number of rows and columns of the square matrices that are compared.
+const SIZES: [usize; 4] = [10, 50, 100, 300];
+
+/// Generates a single value from the given strategy.
+///
+/// The runner is deterministic, so that the same matrices are compared in every run.
+fn generate<S: Strategy>(strategy: S) -> S::Value {
+    let mut runner = TestRunner::deterministic();
+    strategy
+        .new_tree(&mut runner)
+        .expect("Strategy must be able to generate a value")
+        .current()
+}
+
+fn dense_i64(size: usize) -> MockDenseMatrix<i64> {
+    generate(dense_matrix_strategy_i64(Just(size), Just(size)))
+}
+
+fn sparse_i64(size: usize) -> MockSparseMatrix<i64> {
+    generate(sparse_matrix_strategy_i64(Just(size), Just(size)))
+}
+
+fn dense_dense(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dense_dense");
+    for &size in &SIZES {
+        group.throughput(Throughput::Elements((size * size) as u64));
+
+        let a = dense_i64(size);
+        let b = a.clone();
+        group.bench_with_input(BenchmarkId::new("exact_i64", size), &size, |bencher, _| {
+            bencher.iter(|| {
+                compare_matrices(black_box(&a), black_box(&b), &ExactElementwiseComparator)
+            })
+        });
+
+        let x = generate(dense_matrix_strategy_normal_f64(Just(size), Just(size)));
+        let y = x.clone();
+        let comparator = AbsoluteElementwiseComparator { tol: 1e-12 };
+        group.bench_with_input(BenchmarkId::new("abs_f64", size), &size, |bencher, _| {
+            bencher.iter(|| compare_matrices(black_box(&x), black_box(&y), &comparator))
+        });
+    }
+    group.finish();
+}
+
+fn sparse_sparse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sparse_sparse");
+    for &size in &SIZES {
+        let a = sparse_i64(size);
+        let b = a.clone();
+        group.throughput(Throughput::Elements(a.nnz() as u64));
+        group.bench_with_input(BenchmarkId::new("exact_i64", size), &size, |bencher, _| {
+            bencher.iter(|| {
+                compare_matrices(black_box(&a), black_box(&b), &ExactElementwiseComparator)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn dense_sparse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dense_sparse");
+    for &size in &SIZES {
+        group.throughput(Throughput::Elements((size * size) as u64));
+
+        let sparse = sparse_i64(size);
+        let dense = sparse
+            .to_dense()
+            .expect("Generated sparse matrices are always valid");
+        group.bench_with_input(BenchmarkId::new("exact_i64", size), &size, |bencher, _| {
+            bencher.iter(|| {
+                compare_matrices(
+                    black_box(&dense),
+                    black_box(&sparse),
+                    &ExactElementwiseComparator,
+                )
+            })
+        });
+
+        // With an exact non-zero count hint, the comparison may skip the zeros of the dense matrix
+        let nonzeros = sparse
+            .fetch_triplets()
+            .iter()
+            .filter(|&&(_, _, v)| v != 0)
+            .count();
+        let hinted = dense.clone().with_nonzero_count_hint(nonzeros);
+        group.bench_with_input(
+            BenchmarkId::new("hinted_exact_i64", size),
+            &size,
+            |bencher, _| {
+                bencher.iter(|| {
+                    compare_matrices(
+                        black_box(&hinted),
+                        black_box(&sparse),
+                        &ExactElementwiseComparator,
+                    )
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dense_dense, sparse_sparse, dense_sparse);
+criterion_main!(benches);
@@ -1,7 +1,22 @@
+//! Core traits for accessing the elements of matrices.
+//!
+//! This crate only depends on `core` and `alloc`, and can be used in `no_std` environments.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
 /// Defines how the elements of a matrix may be accessed.
 pub enum Access<'a, T> {
     Dense(&'a dyn DenseAccess<T>),
     Sparse(&'a dyn SparseAccess<T>),
+    Csr(&'a dyn CsrAccess<T>),
+    Csc(&'a dyn CscAccess<T>),
 }
 
 /// Main interface for access to the elements of a matrix.
@@ -10,12 +25,41 @@ pub trait Matrix<T> {
     fn cols(&self) -> usize;
 
     /// Expose dense or sparse access to the matrix.
-    fn access(&self) -> Access<T>;
+    fn access(&self) -> Access<'_, T>;
 }
 
 /// Access to a dense matrix.
 pub trait DenseAccess<T>: Matrix<T> {
     fn fetch_single(&self, row: usize, col: usize) -> T;
+
+    /// Optional borrowing access to a single element.
+    ///
+    /// Matrices that store their elements directly may return a reference to the element,
+    /// which lets comparisons avoid cloning elements that are expensive to clone, such as
+    /// arbitrary-precision numbers. The default implementation returns `None`, in which case
+    /// [fetch_single](DenseAccess::fetch_single) is used instead.
+    fn fetch_ref(&self, _row: usize, _col: usize) -> Option<&T> {
+        None
+    }
+
+    /// Optional hint for the number of non-zero elements in the matrix.
+    ///
+    /// If the hint is available, comparison with a sparse matrix may avoid visiting every
+    /// element of the dense matrix. The hint must be exact when provided, otherwise the result
+    /// of the comparison is unspecified. The default implementation returns `None`.
+    fn nonzero_count_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Optional access to all elements as a contiguous slice in row-major order.
+    ///
+    /// Matrices that store their elements contiguously in row-major order may return the
+    /// elements as a slice of length `rows * cols`, which lets comparisons iterate over the
+    /// elements directly instead of accessing them one at a time. Slices of any other length
+    /// are ignored. The default implementation returns `None`.
+    fn as_slice(&self) -> Option<&[T]> {
+        None
+    }
 }
 
 /// Access to a sparse matrix.
@@ -25,11 +69,135 @@ pub trait SparseAccess<T>: Matrix<T> {
 
     /// Retrieve the triplets that identify the coefficients of the sparse matrix.
     fn fetch_triplets(&self) -> Vec<(usize, usize, T)>;
+
+    /// Optional borrowing access to the triplets of the sparse matrix.
+    ///
+    /// Matrices that store their triplets contiguously may return a borrowed slice, which
+    /// lets comparisons avoid allocating and filling a new vector of triplets. The default
+    /// implementation returns the owned triplets obtained from
+    /// [fetch_triplets](SparseAccess::fetch_triplets).
+    fn fetch_triplets_cow(&self) -> Cow<'_, [(usize, usize, T)]>
+    where
+        T: Clone,
+    {
+        Cow::Owned(self.fetch_triplets())
+    }
+}
+
+/// Access to a sparse matrix stored in Compressed Sparse Row (CSR) format.
+///
+/// The column indices and values of row `i` are stored in
+/// `column_indices()[row_offsets()[i] .. row_offsets()[i + 1]]` and
+/// `values()[row_offsets()[i] .. row_offsets()[i + 1]]`, respectively.
+pub trait CsrAccess<T>: Matrix<T> {
+    /// Offsets into the column indices and values for each row. Must have length `rows + 1`.
+    fn row_offsets(&self) -> &[usize];
+
+    /// Column indices of the explicitly stored entries, ordered by row.
+    fn column_indices(&self) -> &[usize];
+
+    /// Values of the explicitly stored entries, ordered by row.
+    fn values(&self) -> &[T];
+}
+
+/// Access to a sparse matrix stored in Compressed Sparse Column (CSC) format.
+///
+/// The row indices and values of column `j` are stored in
+/// `row_indices()[col_offsets()[j] .. col_offsets()[j + 1]]` and
+/// `values()[col_offsets()[j] .. col_offsets()[j + 1]]`, respectively.
+pub trait CscAccess<T>: Matrix<T> {
+    /// Offsets into the row indices and values for each column. Must have length `cols + 1`.
+    fn col_offsets(&self) -> &[usize];
+
+    /// Row indices of the explicitly stored entries, ordered by column.
+    fn row_indices(&self) -> &[usize];
+
+    /// Values of the explicitly stored entries, ordered by column.
+    fn values(&self) -> &[T];
+}
+
+/// An error encountered when fetching an element through [TryDenseAccess].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessError {
+    /// A description of the error, such as the underlying I/O error.
+    pub message: String,
+}
+
+impl AccessError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Fallible access to a dense matrix.
+///
+/// This is an alternative to [DenseAccess] for matrices whose elements may fail to load,
+/// for example matrices backed by memory-mapped files or computed on demand. Every dense
+/// matrix also provides fallible access that never fails.
+///
+/// Fallible matrices do not need to implement [Matrix], since they may not be able to provide
+/// infallible access to their elements. Consequently, a fallible matrix should implement
+/// only this trait, and not [DenseAccess].
+pub trait TryDenseAccess<T> {
+    /// The number of rows and columns of the matrix, in that order.
+    ///
+    /// This is not split into `rows` and `cols`, so that calls to [Matrix::rows] and
+    /// [Matrix::cols] remain unambiguous for dense matrices, which implement both traits.
+    fn dimensions(&self) -> (usize, usize);
+
+    fn try_fetch_single(&self, row: usize, col: usize) -> Result<T, AccessError>;
+}
+
+impl<T, X> TryDenseAccess<T> for X
+where
+    X: ?Sized + DenseAccess<T>,
+{
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows(), self.cols())
+    }
+
+    fn try_fetch_single(&self, row: usize, col: usize) -> Result<T, AccessError> {
+        Ok(self.fetch_single(row, col))
+    }
+}
+
+/// Two-dimensional arrays are interpreted as dense matrices with `R` rows and `C` columns,
+/// where each inner array is a row of the matrix.
+impl<T: Clone, const R: usize, const C: usize> Matrix<T> for [[T; C]; R] {
+    fn rows(&self) -> usize {
+        R
+    }
+
+    fn cols(&self) -> usize {
+        C
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        Access::Dense(self)
+    }
+}
+
+impl<T: Clone, const R: usize, const C: usize> DenseAccess<T> for [[T; C]; R] {
+    fn fetch_single(&self, row: usize, col: usize) -> T {
+        self[row][col].clone()
+    }
+
+    fn fetch_ref(&self, row: usize, col: usize) -> Option<&T> {
+        Some(&self[row][col])
+    }
 }
 
 impl<T, X> Matrix<T> for &X
-    where
-        X: Matrix<T>,
+where
+    X: ?Sized + Matrix<T>,
 {
     fn rows(&self) -> usize {
         X::rows(*self)
@@ -39,23 +207,35 @@ impl<T, X> Matrix<T> for &X
         X::cols(*self)
     }
 
-    fn access(&self) -> Access<T> {
+    fn access(&self) -> Access<'_, T> {
         X::access(*self)
     }
 }
 
 impl<T, X> DenseAccess<T> for &X
-    where
-        X: DenseAccess<T>,
+where
+    X: ?Sized + DenseAccess<T>,
 {
     fn fetch_single(&self, row: usize, col: usize) -> T {
         X::fetch_single(*self, row, col)
     }
+
+    fn fetch_ref(&self, row: usize, col: usize) -> Option<&T> {
+        X::fetch_ref(*self, row, col)
+    }
+
+    fn nonzero_count_hint(&self) -> Option<usize> {
+        X::nonzero_count_hint(*self)
+    }
+
+    fn as_slice(&self) -> Option<&[T]> {
+        X::as_slice(*self)
+    }
 }
 
 impl<T, X> SparseAccess<T> for &X
-    where
-        X: SparseAccess<T>,
+where
+    X: ?Sized + SparseAccess<T>,
 {
     fn nnz(&self) -> usize {
         X::nnz(*self)
@@ -64,4 +244,191 @@ impl<T, X> SparseAccess<T> for &X
     fn fetch_triplets(&self) -> Vec<(usize, usize, T)> {
         X::fetch_triplets(&self)
     }
-}
\ No newline at end of file
+
+    fn fetch_triplets_cow(&self) -> Cow<'_, [(usize, usize, T)]>
+    where
+        T: Clone,
+    {
+        X::fetch_triplets_cow(*self)
+    }
+}
+
+impl<T, X> CsrAccess<T> for &X
+where
+    X: ?Sized + CsrAccess<T>,
+{
+    fn row_offsets(&self) -> &[usize] {
+        X::row_offsets(*self)
+    }
+
+    fn column_indices(&self) -> &[usize] {
+        X::column_indices(*self)
+    }
+
+    fn values(&self) -> &[T] {
+        X::values(*self)
+    }
+}
+
+impl<T, X> CscAccess<T> for &X
+where
+    X: ?Sized + CscAccess<T>,
+{
+    fn col_offsets(&self) -> &[usize] {
+        X::col_offsets(*self)
+    }
+
+    fn row_indices(&self) -> &[usize] {
+        X::row_indices(*self)
+    }
+
+    fn values(&self) -> &[T] {
+        X::values(*self)
+    }
+}
+
+/// Exposes the transpose of a matrix, without storing the transposed matrix.
+///
+/// Element `(i, j)` of the transpose is element `(j, i)` of the wrapped matrix. The transpose
+/// exposes the same kind of access as the wrapped matrix, except that the transpose of a
+/// CSR matrix is exposed as a CSC matrix with the same data, and vice versa.
+///
+/// ```
+/// use matrixcompare_core::{Access, Matrix, Transpose};
+///
+/// let a = [[1, 2, 3],
+///          [4, 5, 6]];
+/// let transpose = Transpose(&a);
+/// assert_eq!((transpose.rows(), transpose.cols()), (3, 2));
+/// match transpose.access() {
+///     Access::Dense(access) => assert_eq!(access.fetch_single(2, 0), 3),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Transpose<'a, M: ?Sized>(pub &'a M);
+
+impl<'a, T, M> Matrix<T> for Transpose<'a, M>
+where
+    M: ?Sized + Matrix<T>,
+{
+    fn rows(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        match self.0.access() {
+            Access::Dense(_) => Access::Dense(self),
+            Access::Sparse(_) => Access::Sparse(self),
+            Access::Csr(_) => Access::Csc(self),
+            Access::Csc(_) => Access::Csr(self),
+        }
+    }
+}
+
+impl<'a, T, M> DenseAccess<T> for Transpose<'a, M>
+where
+    M: ?Sized + Matrix<T>,
+{
+    fn fetch_single(&self, row: usize, col: usize) -> T {
+        match self.0.access() {
+            Access::Dense(access) => access.fetch_single(col, row),
+            _ => unreachable!("Transpose only exposes dense access for dense matrices"),
+        }
+    }
+
+    fn fetch_ref(&self, row: usize, col: usize) -> Option<&T> {
+        match self.0.access() {
+            Access::Dense(access) => access.fetch_ref(col, row),
+            _ => unreachable!("Transpose only exposes dense access for dense matrices"),
+        }
+    }
+
+    fn nonzero_count_hint(&self) -> Option<usize> {
+        match self.0.access() {
+            Access::Dense(access) => access.nonzero_count_hint(),
+            _ => unreachable!("Transpose only exposes dense access for dense matrices"),
+        }
+    }
+}
+
+impl<'a, T, M> SparseAccess<T> for Transpose<'a, M>
+where
+    M: ?Sized + Matrix<T>,
+{
+    fn nnz(&self) -> usize {
+        match self.0.access() {
+            Access::Sparse(access) => access.nnz(),
+            _ => unreachable!("Transpose only exposes sparse access for sparse matrices"),
+        }
+    }
+
+    fn fetch_triplets(&self) -> Vec<(usize, usize, T)> {
+        match self.0.access() {
+            Access::Sparse(access) => access
+                .fetch_triplets()
+                .into_iter()
+                .map(|(i, j, v)| (j, i, v))
+                .collect(),
+            _ => unreachable!("Transpose only exposes sparse access for sparse matrices"),
+        }
+    }
+}
+
+impl<'a, T, M> CsrAccess<T> for Transpose<'a, M>
+where
+    M: ?Sized + Matrix<T>,
+    T: 'a,
+{
+    fn row_offsets(&self) -> &[usize] {
+        match self.0.access() {
+            Access::Csc(access) => access.col_offsets(),
+            _ => unreachable!("Transpose only exposes CSR access for CSC matrices"),
+        }
+    }
+
+    fn column_indices(&self) -> &[usize] {
+        match self.0.access() {
+            Access::Csc(access) => access.row_indices(),
+            _ => unreachable!("Transpose only exposes CSR access for CSC matrices"),
+        }
+    }
+
+    fn values(&self) -> &[T] {
+        match self.0.access() {
+            Access::Csc(access) => access.values(),
+            _ => unreachable!("Transpose only exposes CSR access for CSC matrices"),
+        }
+    }
+}
+
+impl<'a, T, M> CscAccess<T> for Transpose<'a, M>
+where
+    M: ?Sized + Matrix<T>,
+    T: 'a,
+{
+    fn col_offsets(&self) -> &[usize] {
+        match self.0.access() {
+            Access::Csr(access) => access.row_offsets(),
+            _ => unreachable!("Transpose only exposes CSC access for CSR matrices"),
+        }
+    }
+
+    fn row_indices(&self) -> &[usize] {
+        match self.0.access() {
+            Access::Csr(access) => access.column_indices(),
+            _ => unreachable!("Transpose only exposes CSC access for CSR matrices"),
+        }
+    }
+
+    fn values(&self) -> &[T] {
+        match self.0.access() {
+            Access::Csr(access) => access.values(),
+            _ => unreachable!("Transpose only exposes CSC access for CSR matrices"),
+        }
+    }
+}
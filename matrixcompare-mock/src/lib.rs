@@ -2,9 +2,14 @@
 //! `matrixcompare` crate. Not intended for usage outside of
 //! `matrixcompare` tests.
 
-use matrixcompare_core::{Access, DenseAccess, Matrix, SparseAccess};
+use matrixcompare_core::{Access, CscAccess, CsrAccess, DenseAccess, Matrix, SparseAccess};
 use proptest::prelude::*;
-use std::fmt::Debug;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::FromIterator;
 
 use num::Zero;
 use std::ops::Range;
@@ -14,6 +19,7 @@ pub struct MockDenseMatrix<T> {
     data: Vec<T>,
     rows: usize,
     cols: usize,
+    nonzero_count_hint: Option<usize>,
 }
 
 #[derive(Clone, Debug)]
@@ -22,6 +28,81 @@ pub struct MockSparseMatrix<T> {
     triplets: Vec<(usize, usize, T)>,
 }
 
+/// A mock sparse matrix stored in Compressed Sparse Row (CSR) format.
+#[derive(Clone, Debug)]
+pub struct MockCsrMatrix<T> {
+    shape: (usize, usize),
+    row_offsets: Vec<usize>,
+    column_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// A mock sparse matrix stored in Compressed Sparse Column (CSC) format.
+#[derive(Clone, Debug)]
+pub struct MockCscMatrix<T> {
+    shape: (usize, usize),
+    col_offsets: Vec<usize>,
+    row_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// Compresses (major, minor, value) triplets into offsets, minor indices and values.
+///
+/// Duplicate entries and out-of-bounds minor indices are preserved as-is, so that
+/// they can be used to test error handling.
+fn compress_triplets<T>(
+    major_dim: usize,
+    mut triplets: Vec<(usize, usize, T)>,
+) -> (Vec<usize>, Vec<usize>, Vec<T>) {
+    assert!(
+        triplets.iter().all(|(major, _, _)| *major < major_dim),
+        "Compressed formats cannot represent out-of-bounds rows (CSR) or columns (CSC)."
+    );
+    triplets.sort_by_key(|(major, minor, _)| (*major, *minor));
+
+    let mut offsets = vec![0; major_dim + 1];
+    for (major, _, _) in &triplets {
+        offsets[*major + 1] += 1;
+    }
+    for k in 0..major_dim {
+        offsets[k + 1] += offsets[k];
+    }
+
+    let (minor_indices, values) = triplets.into_iter().map(|(_, minor, v)| (minor, v)).unzip();
+    (offsets, minor_indices, values)
+}
+
+impl<T> MockCsrMatrix<T> {
+    /// Constructs a CSR matrix from (row, col, value) triplets.
+    ///
+    /// Panics if any row index is out of bounds.
+    pub fn from_triplets(rows: usize, cols: usize, triplets: Vec<(usize, usize, T)>) -> Self {
+        let (row_offsets, column_indices, values) = compress_triplets(rows, triplets);
+        Self {
+            shape: (rows, cols),
+            row_offsets,
+            column_indices,
+            values,
+        }
+    }
+}
+
+impl<T> MockCscMatrix<T> {
+    /// Constructs a CSC matrix from (row, col, value) triplets.
+    ///
+    /// Panics if any column index is out of bounds.
+    pub fn from_triplets(rows: usize, cols: usize, triplets: Vec<(usize, usize, T)>) -> Self {
+        let transposed = triplets.into_iter().map(|(i, j, v)| (j, i, v)).collect();
+        let (col_offsets, row_indices, values) = compress_triplets(cols, transposed);
+        Self {
+            shape: (rows, cols),
+            col_offsets,
+            row_indices,
+            values,
+        }
+    }
+}
+
 impl<T> MockSparseMatrix<T> {
     pub fn from_triplets(rows: usize, cols: usize, triplets: Vec<(usize, usize, T)>) -> Self {
         Self {
@@ -30,40 +111,107 @@ impl<T> MockSparseMatrix<T> {
         }
     }
 
+    /// Constructs a sparse matrix from an iterator over (row, col, value) triplets.
+    ///
+    /// As with [MockSparseMatrix::from_triplets], the triplets are not validated.
+    pub fn from_iter(
+        rows: usize,
+        cols: usize,
+        triplets: impl IntoIterator<Item = (usize, usize, T)>,
+    ) -> Self {
+        Self::from_triplets(rows, cols, triplets.into_iter().collect())
+    }
+
     pub fn take_triplets(self) -> Vec<(usize, usize, T)> {
         self.triplets
     }
 }
 
+/// Error returned by [MockSparseMatrix::to_dense] when the triplets do not describe a valid matrix.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToDenseError {
+    /// A triplet refers to a coordinate outside of the shape of the matrix.
+    OutOfBounds {
+        coord: (usize, usize),
+        shape: (usize, usize),
+    },
+}
+
+impl Display for ToDenseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { coord, shape } => write!(
+                f,
+                "Triplet at ({}, {}) is out of bounds for matrix of shape {} x {}.",
+                coord.0, coord.1, shape.0, shape.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ToDenseError {}
+
 impl<T> MockSparseMatrix<T>
 where
     T: Zero + Clone,
 {
-    pub fn to_dense(&self) -> Result<MockDenseMatrix<T>, ()> {
+    pub fn to_dense(&self) -> Result<MockDenseMatrix<T>, ToDenseError> {
         let (r, c) = (self.rows(), self.cols());
-        let mut result =
-            MockDenseMatrix::from_row_major(self.rows(), self.cols(), vec![T::zero(); r * c]);
+        let mut result = MockDenseMatrix::from_row_major(r, c, vec![T::zero(); checked_len(r, c)]);
         for (i, j, v) in &self.triplets {
-            *result.get_mut(*i, *j).ok_or(())? = v.clone();
+            let entry = result.get_mut(*i, *j).ok_or(ToDenseError::OutOfBounds {
+                coord: (*i, *j),
+                shape: self.shape,
+            })?;
+            *entry = v.clone();
         }
 
         Ok(result)
     }
 }
 
+/// Computes `rows * cols`, panicking if the product overflows.
+fn checked_len(rows: usize, cols: usize) -> usize {
+    rows.checked_mul(cols)
+        .expect("Dimension product overflows usize.")
+}
+
+/// Computes the row-major index `row * cols + col`, panicking if the index overflows.
+fn checked_linear_index(row: usize, col: usize, cols: usize) -> usize {
+    row.checked_mul(cols)
+        .and_then(|offset| offset.checked_add(col))
+        .expect("Linear index overflows usize.")
+}
+
 impl<T> MockDenseMatrix<T> {
     pub fn from_row_major(rows: usize, cols: usize, data: Vec<T>) -> Self {
         assert_eq!(
-            rows * cols,
+            checked_len(rows, cols),
             data.len(),
             "Data must have rows*cols number of elements."
         );
-        Self { data, rows, cols }
+        Self {
+            data,
+            rows,
+            cols,
+            nonzero_count_hint: None,
+        }
+    }
+
+    /// Sets the hint for the number of non-zero elements exposed through `DenseAccess`.
+    ///
+    /// The hint is not validated, which allows testing the behavior for incorrect hints.
+    /// Note that the hint is not updated if the matrix is subsequently modified.
+    pub fn with_nonzero_count_hint(self, hint: usize) -> Self {
+        Self {
+            nonzero_count_hint: Some(hint),
+            ..self
+        }
     }
 
     fn get_linear_index(&self, i: usize, j: usize) -> Option<usize> {
         if i < self.rows && j < self.cols {
-            Some(i * self.cols + j)
+            Some(checked_linear_index(i, j, self.cols))
         } else {
             None
         }
@@ -88,16 +236,29 @@ impl<T: Clone> Matrix<T> for MockDenseMatrix<T> {
         self.cols
     }
 
-    fn access(&self) -> Access<T> {
+    fn access(&self) -> Access<'_, T> {
         Access::Dense(self)
     }
 }
 
 impl<T: Clone> DenseAccess<T> for MockDenseMatrix<T> {
     fn fetch_single(&self, row: usize, col: usize) -> T {
-        let idx = row * self.cols + col;
+        let idx = checked_linear_index(row, col, self.cols);
         self.data[idx].clone()
     }
+
+    fn fetch_ref(&self, row: usize, col: usize) -> Option<&T> {
+        let idx = checked_linear_index(row, col, self.cols);
+        Some(&self.data[idx])
+    }
+
+    fn nonzero_count_hint(&self) -> Option<usize> {
+        self.nonzero_count_hint
+    }
+
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
 }
 
 impl<T: Clone> Matrix<T> for MockSparseMatrix<T> {
@@ -109,7 +270,7 @@ impl<T: Clone> Matrix<T> for MockSparseMatrix<T> {
         self.shape.1
     }
 
-    fn access(&self) -> Access<T> {
+    fn access(&self) -> Access<'_, T> {
         Access::Sparse(self)
     }
 }
@@ -122,10 +283,322 @@ impl<T: Clone> SparseAccess<T> for MockSparseMatrix<T> {
     fn fetch_triplets(&self) -> Vec<(usize, usize, T)> {
         self.triplets.clone()
     }
+
+    fn fetch_triplets_cow(&self) -> Cow<'_, [(usize, usize, T)]> {
+        Cow::Borrowed(&self.triplets)
+    }
+}
+
+impl<T> Matrix<T> for MockCsrMatrix<T> {
+    fn rows(&self) -> usize {
+        self.shape.0
+    }
+
+    fn cols(&self) -> usize {
+        self.shape.1
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        Access::Csr(self)
+    }
+}
+
+impl<T> CsrAccess<T> for MockCsrMatrix<T> {
+    fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    fn column_indices(&self) -> &[usize] {
+        &self.column_indices
+    }
+
+    fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T> Matrix<T> for MockCscMatrix<T> {
+    fn rows(&self) -> usize {
+        self.shape.0
+    }
+
+    fn cols(&self) -> usize {
+        self.shape.1
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        Access::Csc(self)
+    }
+}
+
+impl<T> CscAccess<T> for MockCscMatrix<T> {
+    fn col_offsets(&self) -> &[usize] {
+        &self.col_offsets
+    }
+
+    fn row_indices(&self) -> &[usize] {
+        &self.row_indices
+    }
+
+    fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+/// The number of calls made to each accessor method of a matrix wrapped in [AccessRecorder].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessCounts {
+    pub rows: usize,
+    pub cols: usize,
+    pub fetch_single: usize,
+    pub fetch_ref: usize,
+    pub as_slice: usize,
+    pub fetch_triplets: usize,
+    pub fetch_triplets_cow: usize,
+}
+
+/// A wrapper around a matrix that records how many times its accessor methods are called.
+///
+/// The recorder forwards all calls to the wrapped matrix, and exposes the same kind of access
+/// (dense or sparse) as the wrapped matrix. This makes it possible to verify that a
+/// comparison accesses a matrix in the expected manner.
+#[derive(Debug)]
+pub struct AccessRecorder<'a, M> {
+    inner: &'a M,
+    counts: Cell<AccessCounts>,
+}
+
+impl<'a, M> AccessRecorder<'a, M> {
+    pub fn new(inner: &'a M) -> Self {
+        Self {
+            inner,
+            counts: Cell::new(AccessCounts::default()),
+        }
+    }
+
+    /// The number of calls recorded so far.
+    pub fn counts(&self) -> AccessCounts {
+        self.counts.get()
+    }
+
+    fn record(&self, update: impl FnOnce(&mut AccessCounts)) {
+        let mut counts = self.counts.get();
+        update(&mut counts);
+        self.counts.set(counts);
+    }
+}
+
+impl<'a, T, M> Matrix<T> for AccessRecorder<'a, M>
+where
+    M: Matrix<T>,
+{
+    fn rows(&self) -> usize {
+        self.record(|counts| counts.rows += 1);
+        self.inner.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.record(|counts| counts.cols += 1);
+        self.inner.cols()
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        match self.inner.access() {
+            Access::Dense(_) => Access::Dense(self),
+            Access::Sparse(_) => Access::Sparse(self),
+            Access::Csr(_) => Access::Csr(self),
+            Access::Csc(_) => Access::Csc(self),
+        }
+    }
+}
+
+impl<'a, T, M> DenseAccess<T> for AccessRecorder<'a, M>
+where
+    M: Matrix<T>,
+{
+    fn fetch_single(&self, row: usize, col: usize) -> T {
+        self.record(|counts| counts.fetch_single += 1);
+        match self.inner.access() {
+            Access::Dense(access) => access.fetch_single(row, col),
+            _ => panic!("Wrapped matrix does not provide dense access."),
+        }
+    }
+
+    fn fetch_ref(&self, row: usize, col: usize) -> Option<&T> {
+        self.record(|counts| counts.fetch_ref += 1);
+        match self.inner.access() {
+            Access::Dense(access) => access.fetch_ref(row, col),
+            _ => panic!("Wrapped matrix does not provide dense access."),
+        }
+    }
+
+    fn nonzero_count_hint(&self) -> Option<usize> {
+        match self.inner.access() {
+            Access::Dense(access) => access.nonzero_count_hint(),
+            _ => panic!("Wrapped matrix does not provide dense access."),
+        }
+    }
+
+    fn as_slice(&self) -> Option<&[T]> {
+        self.record(|counts| counts.as_slice += 1);
+        match self.inner.access() {
+            Access::Dense(access) => access.as_slice(),
+            _ => panic!("Wrapped matrix does not provide dense access."),
+        }
+    }
+}
+
+impl<'a, T, M> SparseAccess<T> for AccessRecorder<'a, M>
+where
+    M: Matrix<T>,
+{
+    fn nnz(&self) -> usize {
+        match self.inner.access() {
+            Access::Sparse(access) => access.nnz(),
+            _ => panic!("Wrapped matrix does not provide sparse access."),
+        }
+    }
+
+    fn fetch_triplets(&self) -> Vec<(usize, usize, T)> {
+        self.record(|counts| counts.fetch_triplets += 1);
+        match self.inner.access() {
+            Access::Sparse(access) => access.fetch_triplets(),
+            _ => panic!("Wrapped matrix does not provide sparse access."),
+        }
+    }
+
+    fn fetch_triplets_cow(&self) -> Cow<'_, [(usize, usize, T)]>
+    where
+        T: Clone,
+    {
+        self.record(|counts| counts.fetch_triplets_cow += 1);
+        match self.inner.access() {
+            Access::Sparse(access) => access.fetch_triplets_cow(),
+            _ => panic!("Wrapped matrix does not provide sparse access."),
+        }
+    }
+}
+
+impl<'a, T, M> CsrAccess<T> for AccessRecorder<'a, M>
+where
+    T: 'a,
+    M: Matrix<T>,
+{
+    fn row_offsets(&self) -> &[usize] {
+        match self.inner.access() {
+            Access::Csr(access) => access.row_offsets(),
+            _ => panic!("Wrapped matrix does not provide CSR access."),
+        }
+    }
+
+    fn column_indices(&self) -> &[usize] {
+        match self.inner.access() {
+            Access::Csr(access) => access.column_indices(),
+            _ => panic!("Wrapped matrix does not provide CSR access."),
+        }
+    }
+
+    fn values(&self) -> &[T] {
+        match self.inner.access() {
+            Access::Csr(access) => access.values(),
+            _ => panic!("Wrapped matrix does not provide CSR access."),
+        }
+    }
+}
+
+impl<'a, T, M> CscAccess<T> for AccessRecorder<'a, M>
+where
+    T: 'a,
+    M: Matrix<T>,
+{
+    fn col_offsets(&self) -> &[usize] {
+        match self.inner.access() {
+            Access::Csc(access) => access.col_offsets(),
+            _ => panic!("Wrapped matrix does not provide CSC access."),
+        }
+    }
+
+    fn row_indices(&self) -> &[usize] {
+        match self.inner.access() {
+            Access::Csc(access) => access.row_indices(),
+            _ => panic!("Wrapped matrix does not provide CSC access."),
+        }
+    }
+
+    fn values(&self) -> &[T] {
+        match self.inner.access() {
+            Access::Csc(access) => access.values(),
+            _ => panic!("Wrapped matrix does not provide CSC access."),
+        }
+    }
+}
+
+/// Constructs a dense matrix from `(rows, cols, data)`, with `data` in row-major order.
+///
+/// Panics if `data` does not have `rows * cols` elements, like [MockDenseMatrix::from_row_major].
+impl<T> From<(usize, usize, Vec<T>)> for MockDenseMatrix<T> {
+    fn from((rows, cols, data): (usize, usize, Vec<T>)) -> Self {
+        Self::from_row_major(rows, cols, data)
+    }
+}
+
+/// Error returned when constructing a [MockDenseMatrix] from rows of different lengths.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RaggedRowsError {
+    pub row: usize,
+    pub len: usize,
+    pub expected_len: usize,
+}
+
+impl Display for RaggedRowsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Row {} has {} elements, but expected {} elements (the length of row 0).",
+            self.row, self.len, self.expected_len
+        )
+    }
+}
+
+impl std::error::Error for RaggedRowsError {}
+
+/// Constructs a dense matrix from a list of rows, which must all have the same length.
+///
+/// An empty list of rows gives a 0 x 0 matrix.
+impl<T> TryFrom<Vec<Vec<T>>> for MockDenseMatrix<T> {
+    type Error = RaggedRowsError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let num_rows = rows.len();
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some((row, data)) = rows.iter().enumerate().find(|(_, row)| row.len() != cols) {
+            return Err(RaggedRowsError {
+                row,
+                len: data.len(),
+                expected_len: cols,
+            });
+        }
+        let data = rows.into_iter().flatten().collect();
+        Ok(Self::from_row_major(num_rows, cols, data))
+    }
+}
+
+/// Collects a dense matrix from an iterator over its rows.
+///
+/// Panics if the rows do not all have the same length.
+impl<T> FromIterator<Vec<T>> for MockDenseMatrix<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(rows: I) -> Self {
+        let rows: Vec<_> = rows.into_iter().collect();
+        Self::try_from(rows).unwrap_or_else(|err| panic!("{}", err))
+    }
 }
 
 /// Macro that helps with the construction of small dense (mock) matrices for testing.
 ///
+/// Rows are separated by `;`, and a trailing `;` after the last row is permitted.
+/// All rows must have the same number of elements, otherwise the macro panics with
+/// a message naming the first offending row.
+///
 /// Originally lifted from the `rulinalg` crate (author being the same as for this crate).
 #[macro_export]
 macro_rules! mock_matrix {
@@ -136,23 +609,18 @@ macro_rules! mock_matrix {
             MockDenseMatrix::from_row_major(0, 0, vec![])
         }
     };
-    ($( $( $x: expr ),*);*) => {
+    ($( $( $x: expr ),+ );+ $(;)?) => {
         {
             use $crate::MockDenseMatrix;
-            let data_as_nested_array = [ $( [ $($x),* ] ),* ];
-            let rows = data_as_nested_array.len();
-            let cols = data_as_nested_array[0].len();
-            let data_as_flat_array: Vec<_> = data_as_nested_array.iter()
-                .flat_map(|row| row.into_iter())
-                .cloned()
-                .collect();
-            MockDenseMatrix::from_row_major(rows, cols, data_as_flat_array)
+            use ::std::convert::TryFrom;
+            let data_as_nested_vec = vec![ $( vec![ $($x),+ ] ),+ ];
+            MockDenseMatrix::try_from(data_as_nested_vec).unwrap_or_else(|err| panic!("{}", err))
         }
     }
 }
 
 pub fn i64_range() -> Range<i64> {
-    -100i64 .. 100
+    -100i64..100
 }
 
 pub fn dense_matrix_strategy<T, S>(
@@ -185,6 +653,94 @@ pub fn dense_matrix_strategy_normal_f64(
     dense_matrix_strategy(rows, cols, proptest::num::f64::NORMAL)
 }
 
+/// A strategy for f64 numbers with random signs, whose magnitudes are log-uniformly distributed.
+///
+/// The base-10 logarithm of the magnitude is drawn uniformly from `exponent_range`, so that
+/// e.g. `-10.0..10.0` produces magnitudes between `1e-10` and `1e10`, with each order of
+/// magnitude being equally likely.
+pub fn log_uniform_f64(exponent_range: Range<f64>) -> impl Strategy<Value = f64> + Clone {
+    (any::<bool>(), exponent_range).prop_map(|(negative, exponent)| {
+        let magnitude = 10.0f64.powf(exponent);
+        if negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    })
+}
+
+/// A strategy for dense f64 matrices whose entries span a large dynamic range.
+///
+/// The entries are drawn from [log_uniform_f64] with the given exponent range. Such matrices
+/// are typically compared successfully by relative comparators, but not by absolute ones.
+pub fn dense_matrix_strategy_log_uniform(
+    rows: impl Strategy<Value = usize>,
+    cols: impl Strategy<Value = usize>,
+    exponent_range: Range<f64>,
+) -> impl Strategy<Value = MockDenseMatrix<f64>> {
+    dense_matrix_strategy(rows, cols, log_uniform_f64(exponent_range))
+}
+
+/// A strategy for square dense matrices, with the dimension drawn from `dim`.
+pub fn square_dense_matrix_strategy<T, S>(
+    dim: impl Strategy<Value = usize>,
+    strategy: S,
+) -> impl Strategy<Value = MockDenseMatrix<T>>
+where
+    T: Debug,
+    S: Clone + Strategy<Value = T>,
+{
+    dim.prop_flat_map(move |n| dense_matrix_strategy(Just(n), Just(n), strategy.clone()))
+}
+
+/// A strategy for square dense matrices that are symmetric by construction.
+///
+/// Only the upper triangle (including the diagonal) is generated, and then mirrored
+/// into the lower triangle.
+pub fn symmetric_dense_matrix_strategy<T, S>(
+    dim: impl Strategy<Value = usize>,
+    strategy: S,
+) -> impl Strategy<Value = MockDenseMatrix<T>>
+where
+    T: Clone + Debug,
+    S: Clone + Strategy<Value = T>,
+{
+    dim.prop_flat_map(move |n| {
+        proptest::collection::vec(strategy.clone(), n * (n + 1) / 2).prop_map(move |upper| {
+            // The upper triangle is stored row by row, so that row i starts at the offset
+            // given by the number of elements in the preceding rows of the upper triangle
+            let upper_index = |i: usize, j: usize| i * n - i * (i + 1) / 2 + j;
+            let data = (0..n)
+                .flat_map(|i| (0..n).map(move |j| (i, j)))
+                .map(|(i, j)| upper[upper_index(i.min(j), i.max(j))].clone())
+                .collect();
+            MockDenseMatrix::from_row_major(n, n, data)
+        })
+    })
+}
+
+/// A strategy for square sparse matrices that explicitly store every diagonal entry,
+/// and no off-diagonal entries.
+pub fn diagonal_sparse_matrix_strategy<T, S>(
+    dim: impl Strategy<Value = usize>,
+    strategy: S,
+) -> impl Strategy<Value = MockSparseMatrix<T>>
+where
+    T: Debug,
+    S: Clone + Strategy<Value = T>,
+{
+    dim.prop_flat_map(move |n| {
+        proptest::collection::vec(strategy.clone(), n).prop_map(move |diagonal| {
+            let triplets = diagonal
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (i, i, v))
+                .collect();
+            MockSparseMatrix::from_triplets(n, n, triplets)
+        })
+    })
+}
+
 pub fn sparse_matrix_strategy<T, S>(
     rows: impl Strategy<Value = usize>,
     cols: impl Strategy<Value = usize>,
@@ -202,10 +758,12 @@ where
         let values_strategy = strategy.clone();
         // Use BTreeMap to avoid potential randomness in hash map iteration order
         proptest::collection::btree_map(ij_strategy, values_strategy, 0..=max_nnz)
-            .prop_map(|map_matrix| map_matrix
-                .into_iter()
-                .map(|((i, j), v)| (i, j, v))
-                .collect())
+            .prop_map(|map_matrix| {
+                map_matrix
+                    .into_iter()
+                    .map(|((i, j), v)| (i, j, v))
+                    .collect()
+            })
             .prop_map(move |triplets| MockSparseMatrix::from_triplets(r, c, triplets))
     })
 }
@@ -222,4 +780,82 @@ pub fn sparse_matrix_strategy_normal_f64(
     cols: impl Strategy<Value = usize>,
 ) -> impl Strategy<Value = MockSparseMatrix<f64>> {
     sparse_matrix_strategy(rows, cols, proptest::num::f64::NORMAL)
-}
\ No newline at end of file
+}
+
+/// A comparison that the pairs generated by [failing_dense_pair_strategy] are guaranteed to fail.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FailingComparison {
+    /// Exact equality.
+    Exact,
+    /// Absolute difference with the given (finite, non-negative) tolerance.
+    Absolute { tol: f64 },
+    /// ULP difference with any tolerance.
+    Ulp,
+}
+
+impl FailingComparison {
+    /// Returns a value that fails the comparison against `x`, for `x` in `-100.0 .. 100.0`
+    /// and `delta` in `0.0 .. 10.0`.
+    fn perturb(&self, x: f64, delta: f64) -> f64 {
+        match *self {
+            FailingComparison::Exact => x + 1.0 + delta,
+            FailingComparison::Absolute { tol } => x + 2.0 * tol + 1.0 + delta,
+            // Non-zero numbers of opposite signs fail ULP comparison for any tolerance
+            FailingComparison::Ulp if x.is_sign_negative() => -x + 1.0 + delta,
+            FailingComparison::Ulp => -x - 1.0 - delta,
+        }
+    }
+}
+
+/// A pair of dense matrices that differ in exactly the given elements.
+#[derive(Clone, Debug)]
+pub struct FailingPair {
+    pub left: MockDenseMatrix<f64>,
+    pub right: MockDenseMatrix<f64>,
+    /// The coordinates of the mismatched elements, in row-major order. Never empty.
+    pub mismatched: Vec<(usize, usize)>,
+}
+
+/// A strategy for pairs of dense matrices that are guaranteed to fail the given comparison.
+///
+/// The matrices are identical except for a non-empty set of elements, which are perturbed so
+/// that they fail the comparison. Shrinking reduces the dimensions, the number of mismatched
+/// elements and the magnitudes of the elements. Dimensions without any elements are rejected.
+/// Panics if an absolute tolerance is negative or not finite.
+pub fn failing_dense_pair_strategy(
+    comparison: FailingComparison,
+    rows: impl Strategy<Value = usize>,
+    cols: impl Strategy<Value = usize>,
+) -> impl Strategy<Value = FailingPair> {
+    if let FailingComparison::Absolute { tol } = comparison {
+        assert!(
+            tol.is_finite() && tol >= 0.0,
+            "Tolerance must be finite and non-negative."
+        );
+    }
+
+    (rows, cols)
+        .prop_filter("Matrices must have at least one element.", |(r, c)| {
+            r * c > 0
+        })
+        .prop_flat_map(move |(r, c)| {
+            let n = r * c;
+            let indices = proptest::sample::subsequence((0..n).collect::<Vec<_>>(), 1..=n);
+            (
+                proptest::collection::vec(-100.0..100.0, n),
+                indices,
+                proptest::collection::vec(0.0..10.0, n),
+            )
+                .prop_map(move |(data, indices, deltas)| {
+                    let mut perturbed = data.clone();
+                    for &k in &indices {
+                        perturbed[k] = comparison.perturb(data[k], deltas[k]);
+                    }
+                    FailingPair {
+                        left: MockDenseMatrix::from_row_major(r, c, data),
+                        right: MockDenseMatrix::from_row_major(r, c, perturbed),
+                        mismatched: indices.into_iter().map(|k| (k / c, k % c)).collect(),
+                    }
+                })
+        })
+}
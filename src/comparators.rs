@@ -1,11 +1,19 @@
 //! Comparators used for element-wise comparison of matrix entries.
 
+use crate::interval::Interval;
+use crate::optional::Optional;
 use crate::ulp::{Ulp, UlpComparisonResult};
 
-use num_traits::{float::FloatCore, Num};
+use num_traits::{float::FloatCore, Num, Zero};
 
-use std::fmt;
-use std::fmt::{Display, Formatter};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::num::FpCategory;
+use core::ops::Sub;
 
 /// Trait that describes elementwise comparators for [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
 ///
@@ -21,12 +29,134 @@ pub trait ElementwiseComparator<T> {
 
     /// A description of the comparator.
     fn description(&self) -> String;
+
+    /// A structured specification of the comparison criterion.
+    ///
+    /// Unlike the description, the specification lets tooling reason about the criterion
+    /// without parsing prose. The default implementation wraps the description in
+    /// [ComparatorSpec::Custom].
+    fn spec(&self) -> ComparatorSpec<T> {
+        ComparatorSpec::Custom(self.description())
+    }
+
+    /// Whether the element is NaN (not a number).
+    ///
+    /// Used to reject NaN elements outright, see
+    /// [ComparisonOptions::reject_nan](crate::ComparisonOptions::reject_nan). Comparators that
+    /// accept floating-point elements detect NaN, whereas the default implementation considers
+    /// no element to be NaN.
+    fn is_nan(&self, _x: &T) -> bool {
+        false
+    }
+}
+
+impl<T, C> ElementwiseComparator<T> for &C
+where
+    C: ElementwiseComparator<T> + ?Sized,
+{
+    type Error = C::Error;
+
+    fn compare(&self, x: &T, y: &T) -> Result<(), Self::Error> {
+        C::compare(self, x, y)
+    }
+
+    fn description(&self) -> String {
+        C::description(self)
+    }
+
+    fn spec(&self) -> ComparatorSpec<T> {
+        C::spec(self)
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        C::is_nan(self, x)
+    }
+}
+
+/// Whether the element is not equal to itself, which for floating-point numbers holds
+/// exactly for NaN.
+#[allow(clippy::eq_op)]
+fn is_unequal_to_itself<T: PartialEq>(x: &T) -> bool {
+    x != x
+}
+
+/// A structured specification of the criterion used by a comparator.
+///
+/// See [ElementwiseComparator::spec]. The `Display` output is the description of the
+/// corresponding comparator.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComparatorSpec<T> {
+    /// The `exact` comparator.
+    Exact,
+    /// The `abs` comparator with the given tolerance.
+    Absolute { tol: T },
+    /// The `ulp` comparator with the given tolerance in ULP.
+    Ulp { tol: u64 },
+    /// The `float` comparator with the given epsilon and ULP tolerances, and whether subnormal
+    /// values are flushed to zero before comparison.
+    Float {
+        eps: T,
+        ulp: u64,
+        flush_subnormals: bool,
+    },
+    /// The `decimal` comparator with the given number of decimal places.
+    DecimalPlaces { decimals: u32 },
+    /// A comparator without a structured specification, represented by its description.
+    Custom(String),
+}
+
+impl<T> Display for ComparatorSpec<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ComparatorSpec::Exact => write!(f, "exact equality x == y."),
+            ComparatorSpec::Absolute { tol } => {
+                write!(f, "absolute difference, |x - y| <= {tol}.", tol = tol)
+            }
+            ComparatorSpec::Ulp { tol } => write!(
+                f,
+                "ULP difference less than or equal to {tol}. See documentation for details.",
+                tol = tol
+            ),
+            ComparatorSpec::Float {
+                eps,
+                ulp,
+                flush_subnormals,
+            } => {
+                write!(
+                    f,
+                    "Epsilon-sized absolute comparison, followed by an ULP-based comparison.
+Please see the documentation for details.
+Epsilon:       {eps}
+ULP tolerance: {ulp}",
+                    eps = eps,
+                    ulp = ulp
+                )?;
+                if *flush_subnormals {
+                    write!(f, "\nSubnormal values are flushed to zero.")?;
+                }
+                Ok(())
+            }
+            ComparatorSpec::DecimalPlaces { decimals } => write!(
+                f,
+                "equality to {decimals} decimal places, |x - y| < {threshold}.",
+                decimals = decimals,
+                threshold = decimal_places_threshold(*decimals)
+            ),
+            ComparatorSpec::Custom(description) => write!(f, "{}", description),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct AbsoluteError<T>(pub T);
 
 /// The `abs` comparator used with [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// Only subtraction and ordering are required of the elements, so the comparator also
+/// accepts e.g. arbitrary-precision integers and rationals.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct AbsoluteElementwiseComparator<T> {
     /// The maximum absolute difference tolerated (inclusive).
@@ -38,13 +168,19 @@ where
     T: Display,
 {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Absolute error: {error}.", error = self.0)
+        write!(f, "Absolute error: ")?;
+        // Forward the formatter so that a requested precision applies to the error
+        self.0.fmt(f)?;
+        write!(f, ".")
     }
 }
 
+#[cfg(feature = "std")]
+impl<T> std::error::Error for AbsoluteError<T> where T: fmt::Debug + Display {}
+
 impl<T> ElementwiseComparator<T> for AbsoluteElementwiseComparator<T>
 where
-    T: Clone + Display + Num + PartialOrd<T>,
+    T: Clone + Display + Zero + Sub<Output = T> + PartialOrd<T>,
 {
     type Error = AbsoluteError<T>;
 
@@ -55,24 +191,34 @@ where
         // ourselves to Signed types (i.e. we still want to be able to
         // handle unsigned types).
 
-        if a == b {
+        // Determine the order with a single comparison. The subtraction consumes its operands,
+        // which are therefore only cloned if the elements are not equal.
+        let (larger, smaller) = match a.partial_cmp(b) {
+            Some(Ordering::Equal) => return Ok(()),
+            Some(Ordering::Greater) => (a, b),
+            // Incomparable elements (e.g. NaN) yield an incomparable distance, which fails below
+            _ => (b, a),
+        };
+        let distance = larger.clone() - smaller.clone();
+        if distance <= self.tol {
             Ok(())
         } else {
-            let distance = if a > b {
-                a.clone() - b.clone()
-            } else {
-                b.clone() - a.clone()
-            };
-            if distance <= self.tol {
-                Ok(())
-            } else {
-                Err(AbsoluteError(distance))
-            }
+            Err(AbsoluteError(distance))
         }
     }
 
     fn description(&self) -> String {
-        format!("absolute difference, |x - y| <= {tol}.", tol = self.tol)
+        ElementwiseComparator::<T>::spec(self).to_string()
+    }
+
+    fn spec(&self) -> ComparatorSpec<T> {
+        ComparatorSpec::Absolute {
+            tol: self.tol.clone(),
+        }
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
     }
 }
 
@@ -89,6 +235,9 @@ impl Display for ExactError {
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for ExactError {}
+
 impl<T> ElementwiseComparator<T> for ExactElementwiseComparator
 where
     T: Display + PartialEq<T>,
@@ -104,67 +253,290 @@ where
     }
 
     fn description(&self) -> String {
-        "exact equality x == y.".to_string()
+        ElementwiseComparator::<T>::spec(self).to_string()
+    }
+
+    fn spec(&self) -> ComparatorSpec<T> {
+        ComparatorSpec::Exact
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
+    }
+}
+
+/// Comparator that considers two elements equal if neither is less than the other.
+///
+/// Equality is defined as `!(x < y) && !(y < x)`, using only the ordering of the elements.
+/// This is useful for types whose `PartialEq` implementation does not reflect numerical
+/// equality, such as types that compare representations rather than values. Note that Rust
+/// requires every `PartialOrd` type to also implement `PartialEq`, but it is never used here.
+///
+/// **NaN behavior**: Since NaN is not ordered with respect to any value, NaN is considered
+/// equal to *every* value, including other NaNs and all numbers. This is the opposite of the
+/// `exact` comparator, for which NaN is not equal to anything. To guard against NaN, combine
+/// this comparator with
+/// [ComparisonOptions::reject_nan](crate::ComparisonOptions::reject_nan), which detects
+/// elements that are not ordered with respect to themselves.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OrderExactComparator;
+
+/// The error associated with [OrderExactComparator], holding the ordering of `x` relative to `y`.
+///
+/// The ordering is always either `Ordering::Less` or `Ordering::Greater`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OrderError(pub Ordering);
+
+impl Display for OrderError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0 {
+            Ordering::Less => write!(f, "x < y."),
+            Ordering::Greater => write!(f, "x > y."),
+            Ordering::Equal => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OrderError {}
+
+impl<T> ElementwiseComparator<T> for OrderExactComparator
+where
+    T: PartialOrd<T>,
+{
+    type Error = OrderError;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), OrderError> {
+        if a < b {
+            Err(OrderError(Ordering::Less))
+        } else if b < a {
+            Err(OrderError(Ordering::Greater))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn description(&self) -> String {
+        "order-based equality, neither x < y nor y < x.".to_string()
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        x.partial_cmp(x).is_none()
     }
 }
 
 /// The `ulp` comparator used with [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// ULP-based comparison is not useful very close to zero, where e.g. `1e-300` and `1e-310` are
+/// far apart in ULP. See [near_zero_abs](Self::near_zero_abs) for an opt-in absolute threshold.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct UlpElementwiseComparator {
     /// The maximum difference in ULP units tolerated (inclusive).
     pub tol: u64,
 }
 
+impl UlpElementwiseComparator {
+    /// Considers values equal if both are within the given absolute threshold of zero.
+    pub fn near_zero_abs<T>(self, near_zero_abs: T) -> NearZeroUlpComparator<T> {
+        NearZeroUlpComparator {
+            tol: self.tol,
+            near_zero_abs,
+        }
+    }
+}
+
+/// The `ulp` comparator with an absolute threshold near zero.
+///
+/// Two values that are both within `near_zero_abs` of zero (inclusive) are considered equal
+/// regardless of their ULP difference. Otherwise, the values are compared as with
+/// [UlpElementwiseComparator]. Usually constructed with
+/// [UlpElementwiseComparator::near_zero_abs].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NearZeroUlpComparator<T> {
+    /// The maximum difference in ULP units tolerated (inclusive).
+    pub tol: u64,
+    /// The absolute threshold below which values are considered equal.
+    pub near_zero_abs: T,
+}
+
+/// The error associated with the `ulp` comparator.
+///
+/// When displayed, ULP differences above [max_displayed_difference](Self::max_displayed_difference)
+/// are shown as e.g. `> 1000000000 ULP` for readability. The alternate format `{:#}` shows the
+/// exact difference, which is also available through [UlpError::ulp_difference].
 #[derive(Copy, Clone, Debug, PartialEq)]
 // TODO: Use same pattern for UlpComparisonResult, i.e. use Result<(), UlpComparisonError>?
-pub struct UlpError(pub UlpComparisonResult);
+pub struct UlpError<T> {
+    /// The result of the ULP comparison.
+    pub result: UlpComparisonResult,
+    /// The absolute difference |x - y| between the two compared values.
+    pub abs_diff: T,
+    /// The largest ULP difference that is displayed exactly, or `None` to always display the
+    /// exact difference. Defaults to
+    /// [DEFAULT_MAX_DISPLAYED_DIFFERENCE](Self::DEFAULT_MAX_DISPLAYED_DIFFERENCE).
+    pub max_displayed_difference: Option<u64>,
+}
+
+impl<T> UlpError<T> {
+    /// The default for [max_displayed_difference](Self::max_displayed_difference). Larger
+    /// differences carry little information beyond their magnitude.
+    pub const DEFAULT_MAX_DISPLAYED_DIFFERENCE: u64 = 1_000_000_000;
+
+    /// Creates an error that caps the displayed difference at the default.
+    pub fn new(result: UlpComparisonResult, abs_diff: T) -> Self {
+        Self {
+            result,
+            abs_diff,
+            max_displayed_difference: Some(Self::DEFAULT_MAX_DISPLAYED_DIFFERENCE),
+        }
+    }
+
+    /// Sets the largest ULP difference that is displayed exactly, or `None` to always display
+    /// the exact difference.
+    pub fn with_max_displayed_difference(self, max_displayed_difference: Option<u64>) -> Self {
+        Self {
+            max_displayed_difference,
+            ..self
+        }
+    }
+
+    /// The exact difference in ULP, if it is defined.
+    pub fn ulp_difference(&self) -> Option<u64> {
+        match self.result {
+            UlpComparisonResult::Difference(diff) => Some(diff),
+            _ => None,
+        }
+    }
+}
 
-impl Display for UlpError {
+impl<T> Display for UlpError<T>
+where
+    T: Display,
+{
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self.0 {
-            UlpComparisonResult::Difference(diff) => {
-                write!(f, "Difference: {diff} ULP.", diff = diff)
+        match (self.result, self.max_displayed_difference) {
+            (UlpComparisonResult::Difference(diff), Some(max)) if diff > max && !f.alternate() => {
+                write!(f, "Difference: > {} ULP. ", max)?;
+            }
+            (UlpComparisonResult::Difference(diff), _) => {
+                write!(f, "Difference: {} ULP. ", diff)?;
             }
-            UlpComparisonResult::IncompatibleSigns => write!(f, "Numbers have incompatible signs."),
-            _ => Ok(()),
+            (UlpComparisonResult::IncompatibleSigns, _) => {
+                write!(f, "Numbers have incompatible signs. ")?;
+            }
+            _ => return Ok(()),
         }
+        write!(f, "Absolute difference: ")?;
+        self.abs_diff.fmt(f)?;
+        write!(f, ".")
     }
 }
 
+#[cfg(feature = "std")]
+impl<T> std::error::Error for UlpError<T> where T: fmt::Debug + Display {}
+
 impl<T> ElementwiseComparator<T> for UlpElementwiseComparator
 where
-    T: Ulp,
+    T: Ulp + Clone + Display + Num + PartialOrd<T>,
 {
-    type Error = UlpError;
+    type Error = UlpError<T>;
 
-    fn compare(&self, a: &T, b: &T) -> Result<(), UlpError> {
+    fn compare(&self, a: &T, b: &T) -> Result<(), UlpError<T>> {
         let diff = Ulp::ulp_diff(a, b);
         match diff {
             UlpComparisonResult::ExactMatch => Ok(()),
             UlpComparisonResult::Difference(diff) if diff <= self.tol => Ok(()),
-            _ => Err(UlpError(diff)),
+            _ => {
+                // As with the absolute comparator, we avoid requiring a Signed type
+                let abs_diff = if a > b {
+                    a.clone() - b.clone()
+                } else {
+                    b.clone() - a.clone()
+                };
+                Err(UlpError::new(diff, abs_diff))
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        ElementwiseComparator::<T>::spec(self).to_string()
+    }
+
+    fn spec(&self) -> ComparatorSpec<T> {
+        ComparatorSpec::Ulp { tol: self.tol }
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
+    }
+}
+
+impl<T> ElementwiseComparator<T> for NearZeroUlpComparator<T>
+where
+    T: Ulp + Clone + Display + Num + PartialOrd<T>,
+{
+    type Error = UlpError<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), UlpError<T>> {
+        // As with the absolute comparator, we avoid requiring a Signed type
+        let is_near_zero = |x: &T| {
+            if *x < T::zero() {
+                T::zero() - x.clone() <= self.near_zero_abs
+            } else {
+                *x <= self.near_zero_abs
+            }
+        };
+        if is_near_zero(a) && is_near_zero(b) {
+            return Ok(());
         }
+
+        UlpElementwiseComparator { tol: self.tol }.compare(a, b)
     }
 
     fn description(&self) -> String {
         format!(
-            "ULP difference less than or equal to {tol}. See documentation for details.",
-            tol = self.tol
+            "ULP difference less than or equal to {tol}, unless |x| <= {abs} and |y| <= {abs}. \
+             See documentation for details.",
+            tol = self.tol,
+            abs = self.near_zero_abs
         )
     }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
+    }
 }
 
 /// The `float` comparator used with [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// The default tolerances are an absolute tolerance of `4 * T::epsilon()` and a ULP tolerance
+/// of 4. Both are relative to the precision of `T`: the machine epsilon of `f32` is about
+/// `1.2e-7`, compared to `2.2e-16` for `f64`, and one ULP of an `f32` is correspondingly larger
+/// than one ULP of an `f64` of the same magnitude. The defaults therefore allow a few rounding
+/// errors in either precision, and do not need to be adjusted for `f32` as such.
+///
+/// They are intended for results that are expected to agree to nearly full precision. Results
+/// that accumulate rounding errors, such as sums or products of many `f32` values, typically
+/// need larger tolerances, for example `ulp = 64`, or an `eps` proportional to the magnitude
+/// of the values if they are much larger than one.
+///
+/// By default, subnormal values are compared as they are. With
+/// [flush_subnormals](FloatElementwiseComparator::flush_subnormals), subnormal values are
+/// replaced by zero of the same sign before comparison, which matches the flush-to-zero mode
+/// of some hardware. Reported errors then refer to the flushed values.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct FloatElementwiseComparator<T> {
     abs: AbsoluteElementwiseComparator<T>,
     ulp: UlpElementwiseComparator,
+    flush_subnormals: bool,
 }
 
 impl<T> FloatElementwiseComparator<T>
 where
     T: FloatCore + Ulp,
 {
+    /// Creates a comparator with the default tolerances, `eps = 4 * T::epsilon()` and `ulp = 4`.
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         let four = T::one() + T::one() + T::one() + T::one();
         FloatElementwiseComparator {
@@ -172,21 +544,45 @@ where
                 tol: four * T::epsilon(),
             },
             ulp: UlpElementwiseComparator { tol: 4 },
+            flush_subnormals: false,
         }
     }
 
     pub fn eps(self, eps: T) -> Self {
         FloatElementwiseComparator {
             abs: AbsoluteElementwiseComparator { tol: eps },
-            ulp: self.ulp,
+            ..self
         }
     }
 
     pub fn ulp(self, max_ulp: u64) -> Self {
         FloatElementwiseComparator {
-            abs: self.abs,
             ulp: UlpElementwiseComparator { tol: max_ulp },
+            ..self
+        }
+    }
+
+    /// Whether to replace subnormal values by zero of the same sign before comparison.
+    ///
+    /// Disabled by default.
+    pub fn flush_subnormals(self, flush_subnormals: bool) -> Self {
+        FloatElementwiseComparator {
+            flush_subnormals,
+            ..self
+        }
+    }
+}
+
+/// Replaces a subnormal value by zero of the same sign.
+fn flush_subnormal<T: FloatCore>(x: T) -> T {
+    if x.classify() == FpCategory::Subnormal {
+        if x.is_sign_negative() {
+            -T::zero()
+        } else {
+            T::zero()
         }
+    } else {
+        x
     }
 }
 
@@ -194,9 +590,15 @@ impl<T> ElementwiseComparator<T> for FloatElementwiseComparator<T>
 where
     T: Ulp + FloatCore + Display,
 {
-    type Error = UlpError;
+    type Error = UlpError<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), UlpError<T>> {
+        let (a, b) = if self.flush_subnormals {
+            (&flush_subnormal(*a), &flush_subnormal(*b))
+        } else {
+            (a, b)
+        };
 
-    fn compare(&self, a: &T, b: &T) -> Result<(), UlpError> {
         // First perform an absolute comparison with a presumably very small epsilon tolerance
         if self.abs.compare(a, b).is_err() {
             // Then fall back to an ULP-based comparison
@@ -208,119 +610,1363 @@ where
     }
 
     fn description(&self) -> String {
-        format!(
-            "Epsilon-sized absolute comparison, followed by an ULP-based comparison.
-Please see the documentation for details.
-Epsilon:       {eps}
-ULP tolerance: {ulp}",
-            eps = self.abs.tol,
-            ulp = self.ulp.tol
-        )
+        ElementwiseComparator::<T>::spec(self).to_string()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::comparators::{
-        AbsoluteElementwiseComparator, AbsoluteError, ElementwiseComparator,
-        ExactElementwiseComparator, ExactError, FloatElementwiseComparator,
-        UlpElementwiseComparator, UlpError,
-    };
-    use crate::ulp::{Ulp, UlpComparisonResult};
-    use quickcheck::TestResult;
-    use std::f64;
 
-    /// Returns the next adjacent floating point number (in the direction of positive infinity)
-    fn next_f64(x: f64) -> f64 {
-        use std::mem;
-        let as_int = unsafe { mem::transmute::<f64, i64>(x) };
-        unsafe { mem::transmute::<i64, f64>(as_int + 1) }
+    fn spec(&self) -> ComparatorSpec<T> {
+        ComparatorSpec::Float {
+            eps: self.abs.tol,
+            ulp: self.ulp.tol,
+            flush_subnormals: self.flush_subnormals,
+        }
     }
 
-    #[test]
-    pub fn absolute_comparator_integer() {
-        let comp = AbsoluteElementwiseComparator { tol: 1 };
-
-        assert_eq!(comp.compare(&0, &0), Ok(()));
-        assert_eq!(comp.compare(&1, &0), Ok(()));
-        assert_eq!(comp.compare(&-1, &0), Ok(()));
-        assert_eq!(comp.compare(&2, &0), Err(AbsoluteError(2)));
-        assert_eq!(comp.compare(&-2, &0), Err(AbsoluteError(2)));
+    fn is_nan(&self, x: &T) -> bool {
+        x.is_nan()
     }
+}
 
-    #[test]
-    pub fn absolute_comparator_floating_point() {
-        let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+/// The `decimal` comparator used with [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// Two elements `x` and `y` are considered equal to `decimals` decimal places if
+///
+/// ```text
+///     |x - y| < 1.5 * 10^(-decimals),
+/// ```
+///
+/// which is the criterion used by `numpy.testing.assert_array_almost_equal`. As in NumPy,
+/// the difference is computed in the precision of the elements and compared against a
+/// threshold computed in double precision, elements that are equal (including infinities
+/// of the same sign) are always considered equal, and two NaNs are also considered equal.
+/// This lets tests ported from NumPy behave identically.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecimalPlacesComparator {
+    /// The number of decimal places that must agree.
+    pub decimals: u32,
+}
 
-        // Note: floating point math is not generally exact, but
-        // here we only compare with 0.0, so we can expect exact results.
-        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
-        assert_eq!(comp.compare(&1.0, &0.0), Ok(()));
-        assert_eq!(comp.compare(&-1.0, &0.0), Ok(()));
-        assert_eq!(comp.compare(&2.0, &0.0), Err(AbsoluteError(2.0)));
-        assert_eq!(comp.compare(&-2.0, &0.0), Err(AbsoluteError(2.0)));
-    }
+/// Computes the threshold `1.5 * 10^(-decimals)` in the same way as NumPy.
+fn decimal_places_threshold(decimals: u32) -> f64 {
+    // NumPy computes 1.5 * 10.0**(-decimals). Powers of ten up to 10^22 are exactly
+    // representable, so in that range the division yields the correctly rounded 10^(-decimals),
+    // just like the power in NumPy.
+    let power_of_ten = if decimals <= 22 {
+        1.0 / <f64 as FloatCore>::powi(10.0, decimals as i32)
+    } else {
+        <f64 as FloatCore>::powi(0.1, decimals as i32)
+    };
+    1.5 * power_of_ten
+}
 
-    quickcheck! {
-        fn property_absolute_comparator_is_symmetric_i64(a: i64, b: i64, tol: i64) -> TestResult {
-            if tol <= 0 {
-                return TestResult::discard()
-            }
+impl<T> ElementwiseComparator<T> for DecimalPlacesComparator
+where
+    T: FloatCore + Display,
+{
+    type Error = AbsoluteError<T>;
 
-            let comp = AbsoluteElementwiseComparator { tol: tol };
-            TestResult::from_bool(comp.compare(&a, &b) == comp.compare(&b, &a))
+    fn compare(&self, a: &T, b: &T) -> Result<(), AbsoluteError<T>> {
+        let abs_diff = (*a - *b).abs();
+        let is_equal = a == b || (a.is_nan() && b.is_nan());
+        let within_threshold = abs_diff
+            .to_f64()
+            .map(|diff| diff < decimal_places_threshold(self.decimals))
+            .unwrap_or(false);
+        if is_equal || within_threshold {
+            Ok(())
+        } else {
+            Err(AbsoluteError(abs_diff))
         }
     }
 
-    quickcheck! {
-        fn property_absolute_comparator_is_symmetric_f64(a: f64, b: f64, tol: f64) -> TestResult {
-            if tol <= 0.0 {
-                return TestResult::discard()
-            }
+    fn description(&self) -> String {
+        ElementwiseComparator::<T>::spec(self).to_string()
+    }
 
-            // Floating point math is not exact, but the AbsoluteElementwiseComparator is designed
-            // so that it gives exactly the same result when the argument positions are reversed
-            let comp = AbsoluteElementwiseComparator { tol: tol };
-            TestResult::from_bool(comp.compare(&a, &b) == comp.compare(&b, &a))
+    fn spec(&self) -> ComparatorSpec<T> {
+        ComparatorSpec::DecimalPlaces {
+            decimals: self.decimals,
         }
     }
 
-    quickcheck! {
-        fn property_absolute_comparator_tolerance_is_not_strict_f64(tol: f64) -> TestResult {
-            if tol <= 0.0 || !tol.is_finite() {
-                return TestResult::discard()
-            }
-
-            // The comparator is defined by <=, not <
-            let comp = AbsoluteElementwiseComparator { tol: tol };
-            let includes_tol = comp.compare(&tol, &0.0).is_ok();
-            let excludes_next_after_tol = comp.compare(&next_f64(tol), &0.0).is_err();
-            TestResult::from_bool(includes_tol && excludes_next_after_tol)
-        }
+    fn is_nan(&self, x: &T) -> bool {
+        x.is_nan()
     }
+}
 
-    #[test]
-    pub fn exact_comparator_integer() {
-        let comp = ExactElementwiseComparator;
+/// Comparator based on the relative difference between elements, used by the `percent`
+/// comparator of [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// Two elements `x` and `y` are considered equal if
+///
+/// ```text
+///     |x - y| <= tol * max(|x|, |y|).
+/// ```
+///
+/// The criterion is symmetric in `x` and `y`. Elements that are equal (including infinities
+/// of the same sign and two zeros) are always considered equal. Otherwise, an infinite element
+/// is never considered equal to another element, and neither is NaN. Note also that for
+/// `tol < 1`, zero is only considered equal to another zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RelativeElementwiseComparator<T> {
+    /// The maximum relative difference tolerated (inclusive), e.g. `0.05` for five percent.
+    pub tol: T,
+}
 
-        assert_eq!(comp.compare(&0, &0), Ok(()));
-        assert_eq!(comp.compare(&1, &0), Err(ExactError));
-        assert_eq!(comp.compare(&-1, &0), Err(ExactError));
-        assert_eq!(comp.compare(&1, &-1), Err(ExactError));
+/// The relative difference `|x - y| / max(|x|, |y|)` between two elements.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RelativeError<T>(pub T);
+
+impl<T> Display for RelativeError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Relative error: ")?;
+        self.0.fmt(f)?;
+        write!(f, ".")
     }
+}
 
-    #[test]
-    pub fn exact_comparator_floating_point() {
-        let comp = ExactElementwiseComparator;
+#[cfg(feature = "std")]
+impl<T> std::error::Error for RelativeError<T> where T: fmt::Debug + Display {}
 
-        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
-        assert_eq!(comp.compare(&-0.0, &-0.0), Ok(()));
-        assert_eq!(comp.compare(&-0.0, &0.0), Ok(()));
-        assert_eq!(comp.compare(&1.0, &0.0), Err(ExactError));
+impl<T> ElementwiseComparator<T> for RelativeElementwiseComparator<T>
+where
+    T: FloatCore + Display,
+{
+    type Error = RelativeError<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), RelativeError<T>> {
+        assert!(self.tol >= T::zero());
+
+        if a == b {
+            return Ok(());
+        } else if a.is_infinite() || b.is_infinite() {
+            return Err(RelativeError(T::infinity()));
+        }
+
+        let abs_diff = (*a - *b).abs();
+        let magnitude = a.abs().max(b.abs());
+        // Incomparable elements (e.g. NaN) fail the comparison below
+        if abs_diff <= self.tol * magnitude {
+            Ok(())
+        } else {
+            Err(RelativeError(abs_diff / magnitude))
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "relative difference, |x - y| <= {tol} * max(|x|, |y|).",
+            tol = self.tol
+        )
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        x.is_nan()
+    }
+}
+
+/// Comparator with a tolerance that depends on the compared elements.
+///
+/// Two elements `x` and `y` are considered equal if
+///
+/// ```text
+///     |x - y| <= tol(x, y),
+/// ```
+///
+/// where `tol` is a function that computes the allowed difference from the two elements.
+/// This generalizes both absolute and relative comparison, and allows for combinations such
+/// as a tolerance that grows with the magnitude of the elements. Usually constructed with
+/// [with_tolerance_fn].
+///
+/// The function should be symmetric, i.e. `tol(x, y) == tol(y, x)`, since the outcome of the
+/// comparison would otherwise depend on which matrix is passed as the left argument. The
+/// function must not return a negative tolerance. If it returns an incomparable tolerance,
+/// such as NaN, the elements are considered mismatched.
+///
+/// ```
+/// # use matrixcompare::comparators::with_tolerance_fn;
+/// # use matrixcompare::compare_slices;
+/// let comp = with_tolerance_fn(|x: &f64, y: &f64| 1e-6 + 1e-9 * x.abs().max(y.abs()));
+/// assert!(compare_slices(&[1.0, 1e6], &[1.0 + 1e-7, 1e6 + 1e-3], &comp).is_ok());
+/// assert!(compare_slices(&[1.0, 1e6], &[1.0 + 1e-5, 1e6], &comp).is_err());
+/// ```
+#[derive(Copy, Clone)]
+pub struct DynamicToleranceComparator<F> {
+    /// Computes the maximum absolute difference tolerated (inclusive) from `x` and `y`.
+    pub tol: F,
+}
+
+/// Creates a [DynamicToleranceComparator] with the given tolerance function.
+pub fn with_tolerance_fn<T, F>(tol: F) -> DynamicToleranceComparator<F>
+where
+    F: Fn(&T, &T) -> T,
+{
+    DynamicToleranceComparator { tol }
+}
+
+/// The error associated with [DynamicToleranceComparator].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DynamicToleranceError<T> {
+    /// The absolute difference `|x - y|`.
+    pub distance: T,
+    /// The tolerance computed for the elements.
+    pub tol: T,
+}
+
+impl<T> Display for DynamicToleranceError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Absolute error: ")?;
+        self.distance.fmt(f)?;
+        write!(f, ", tolerance: ")?;
+        self.tol.fmt(f)?;
+        write!(f, ".")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for DynamicToleranceError<T> where T: fmt::Debug + Display {}
+
+impl<T, F> ElementwiseComparator<T> for DynamicToleranceComparator<F>
+where
+    T: Clone + Display + Zero + Sub<Output = T> + PartialOrd<T>,
+    F: Fn(&T, &T) -> T,
+{
+    type Error = DynamicToleranceError<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), DynamicToleranceError<T>> {
+        let (larger, smaller) = match a.partial_cmp(b) {
+            Some(Ordering::Equal) => return Ok(()),
+            Some(Ordering::Greater) => (a, b),
+            // Incomparable elements (e.g. NaN) yield an incomparable distance, which fails below
+            _ => (b, a),
+        };
+
+        let tol = (self.tol)(a, b);
+        // A NaN tolerance, e.g. computed from a NaN element, fails the comparison below
+        assert_ne!(
+            tol.partial_cmp(&T::zero()),
+            Some(Ordering::Less),
+            "Tolerance must not be negative."
+        );
+        let distance = larger.clone() - smaller.clone();
+        if distance <= tol {
+            Ok(())
+        } else {
+            Err(DynamicToleranceError { distance, tol })
+        }
+    }
+
+    fn description(&self) -> String {
+        String::from("absolute difference with element-dependent tolerance, |x - y| <= tol(x, y).")
+    }
+}
+
+/// Comparator based on the ratio of the elements, used by the `ratio` comparator of
+/// [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// Two elements `x` and `y` are considered equal if
+///
+/// ```text
+///     |x / y - 1| <= tol.
+/// ```
+///
+/// This is useful for quantities that arise from multiplicative processes, where the natural
+/// measure of the deviation of `x` from the reference `y` is their ratio. Unlike
+/// [RelativeElementwiseComparator], the criterion is not symmetric: the right element `y` is
+/// the reference, and the tolerance applies to the ratio in the direction from `y` to `x`.
+///
+/// Elements that are equal (including infinities of the same sign and two zeros) are always
+/// considered equal. Otherwise, if `y` is zero, the ratio is infinite and the comparison fails,
+/// regardless of the tolerance. An infinite element is never considered equal to another
+/// element, and neither is NaN.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RatioComparator<T> {
+    /// The maximum deviation of the ratio `x / y` from one tolerated (inclusive),
+    /// e.g. `0.01` for one percent.
+    pub tol: T,
+}
+
+/// The ratio `x / y` of two elements that did not compare equal.
+///
+/// The ratio is infinite if `y` is zero, and may be NaN if either element is NaN or infinite.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RatioError<T>(pub T);
+
+impl<T> Display for RatioError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Ratio x / y: ")?;
+        self.0.fmt(f)?;
+        write!(f, ".")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for RatioError<T> where T: fmt::Debug + Display {}
+
+impl<T> ElementwiseComparator<T> for RatioComparator<T>
+where
+    T: FloatCore + Display,
+{
+    type Error = RatioError<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), RatioError<T>> {
+        assert!(self.tol >= T::zero());
+
+        let ratio = *a / *b;
+        if a == b {
+            return Ok(());
+        } else if a.is_infinite() || b.is_infinite() {
+            return Err(RatioError(ratio));
+        }
+
+        // A zero denominator yields an infinite ratio, and NaN yields a NaN ratio,
+        // both of which fail the comparison below
+        if (ratio - T::one()).abs() <= self.tol {
+            Ok(())
+        } else {
+            Err(RatioError(ratio))
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("ratio, |x / y - 1| <= {tol}.", tol = self.tol)
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        x.is_nan()
+    }
+}
+
+/// Comparator for validating single-precision results against a double-precision reference.
+///
+/// The first element `x` is expected to be an `f32` value that has been widened to `f64`
+/// (which is lossless), while the second element `y` is the `f64` reference. The reference is
+/// rounded to the nearest `f32`, after which the two are compared by their `f32` ULP difference.
+/// This way, the tolerance is interpreted in terms of the precision of the computed result.
+///
+/// This comparator is used by [compare_f32_against_f64_reference](crate::compare_f32_against_f64_reference).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct F32ReferenceUlpComparator {
+    /// The maximum difference in `f32` ULP units tolerated (inclusive).
+    pub tol: u64,
+}
+
+/// The error associated with [F32ReferenceUlpComparator].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct F32ReferenceError {
+    /// The reference value after rounding to `f32`.
+    pub rounded_reference: f32,
+    /// The ULP comparison error between the computed value and the rounded reference.
+    pub error: UlpError<f32>,
+}
+
+impl Display for F32ReferenceError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Reference rounded to f32: ")?;
+        self.rounded_reference.fmt(f)?;
+        write!(f, ".")?;
+
+        let error = match f.precision() {
+            Some(precision) => format!("{:.*}", precision, self.error),
+            None => format!("{}", self.error),
+        };
+        if !error.is_empty() {
+            write!(f, " {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for F32ReferenceError {}
+
+impl ElementwiseComparator<f64> for F32ReferenceUlpComparator {
+    type Error = F32ReferenceError;
+
+    fn compare(&self, computed: &f64, reference: &f64) -> Result<(), F32ReferenceError> {
+        let computed = *computed as f32;
+        let rounded_reference = *reference as f32;
+        UlpElementwiseComparator { tol: self.tol }
+            .compare(&computed, &rounded_reference)
+            .map_err(|error| F32ReferenceError {
+                rounded_reference,
+                error,
+            })
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "f32 ULP difference less than or equal to {tol}, \
+with the f64 reference rounded to f32.",
+            tol = self.tol
+        )
+    }
+
+    fn is_nan(&self, x: &f64) -> bool {
+        x.is_nan()
+    }
+}
+
+/// Comparator for complex numbers that compares the real and imaginary parts in ULP.
+///
+/// The real and imaginary parts are compared independently, as with
+/// [UlpElementwiseComparator], and the comparison fails if either part exceeds the tolerance.
+/// This is useful for checking bit-level reproducibility of complex results, such as the output
+/// of an FFT. Requires the `num-complex` feature.
+#[cfg(feature = "num-complex")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ComplexUlpComparator {
+    /// The maximum difference in ULP units tolerated for each part (inclusive).
+    pub tol: u64,
+}
+
+/// The error associated with [ComplexUlpComparator].
+///
+/// At least one of the parts is always present.
+#[cfg(feature = "num-complex")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ComplexUlpError<T> {
+    /// The error for the real part, if it failed.
+    pub re: Option<UlpError<T>>,
+    /// The error for the imaginary part, if it failed.
+    pub im: Option<UlpError<T>>,
+}
+
+#[cfg(feature = "num-complex")]
+impl<T> Display for ComplexUlpError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // UlpError displays nothing for NaN, so the part is named on its own
+        let parts = [("Real part", &self.re), ("Imaginary part", &self.im)];
+        let mut separator = "";
+        for (name, error) in parts.iter() {
+            if let Some(error) = error {
+                write!(f, "{}{}: ", separator, name)?;
+                if let UlpComparisonResult::Nan = error.result {
+                    write!(f, "NaN.")?;
+                } else {
+                    error.fmt(f)?;
+                }
+                separator = " ";
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "num-complex", feature = "std"))]
+impl<T> std::error::Error for ComplexUlpError<T> where T: fmt::Debug + Display {}
+
+#[cfg(feature = "num-complex")]
+impl<T> ElementwiseComparator<num_complex::Complex<T>> for ComplexUlpComparator
+where
+    T: Ulp + Clone + Display + Num + PartialOrd<T>,
+{
+    type Error = ComplexUlpError<T>;
+
+    fn compare(
+        &self,
+        x: &num_complex::Complex<T>,
+        y: &num_complex::Complex<T>,
+    ) -> Result<(), ComplexUlpError<T>> {
+        let comp = UlpElementwiseComparator { tol: self.tol };
+        let re = comp.compare(&x.re, &y.re).err();
+        let im = comp.compare(&x.im, &y.im).err();
+        if re.is_none() && im.is_none() {
+            Ok(())
+        } else {
+            Err(ComplexUlpError { re, im })
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "ULP difference of both the real and imaginary parts less than or equal to {tol}. \
+             See documentation for details.",
+            tol = self.tol
+        )
+    }
+
+    fn is_nan(&self, x: &num_complex::Complex<T>) -> bool {
+        is_unequal_to_itself(&x.re) || is_unequal_to_itself(&x.im)
+    }
+}
+
+/// Comparator that delegates to [approx::RelativeEq], used by the `approx` comparator of
+/// [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// This lets types that already implement the traits of the `approx` crate be compared
+/// directly. Two elements `x` and `y` are considered equal if
+/// `x.relative_eq(&y, epsilon, max_relative)` holds. By default, `epsilon` and `max_relative`
+/// are [AbsDiffEq::default_epsilon](approx::AbsDiffEq::default_epsilon) and
+/// [RelativeEq::default_max_relative](approx::RelativeEq::default_max_relative), which
+/// matches `approx::assert_relative_eq!` without additional arguments. Requires the `approx`
+/// feature.
+#[cfg(feature = "approx")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ApproxComparator<T>
+where
+    T: approx::RelativeEq,
+{
+    /// The absolute tolerance, used for elements close to zero.
+    pub epsilon: T::Epsilon,
+    /// The relative tolerance.
+    pub max_relative: T::Epsilon,
+}
+
+#[cfg(feature = "approx")]
+impl<T> Default for ApproxComparator<T>
+where
+    T: approx::RelativeEq,
+{
+    fn default() -> Self {
+        ApproxComparator {
+            epsilon: T::default_epsilon(),
+            max_relative: T::default_max_relative(),
+        }
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T> ApproxComparator<T>
+where
+    T: approx::RelativeEq,
+{
+    pub fn epsilon(self, epsilon: T::Epsilon) -> Self {
+        ApproxComparator { epsilon, ..self }
+    }
+
+    pub fn max_relative(self, max_relative: T::Epsilon) -> Self {
+        ApproxComparator {
+            max_relative,
+            ..self
+        }
+    }
+}
+
+/// The error associated with [ApproxComparator].
+///
+/// The traits of the `approx` crate do not expose the difference between the elements,
+/// so the error carries no further information.
+#[cfg(feature = "approx")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ApproxError;
+
+#[cfg(feature = "approx")]
+impl Display for ApproxError {
+    fn fmt(&self, _: &mut Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "approx", feature = "std"))]
+impl std::error::Error for ApproxError {}
+
+#[cfg(feature = "approx")]
+impl<T> ElementwiseComparator<T> for ApproxComparator<T>
+where
+    T: approx::RelativeEq,
+    T::Epsilon: Clone + Display,
+{
+    type Error = ApproxError;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), ApproxError> {
+        if a.relative_eq(b, self.epsilon.clone(), self.max_relative.clone()) {
+            Ok(())
+        } else {
+            Err(ApproxError)
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "approx::RelativeEq with epsilon = {epsilon} and max_relative = {max_relative}.",
+            epsilon = self.epsilon,
+            max_relative = self.max_relative
+        )
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
+    }
+}
+
+/// Comparator for signal data that compares magnitudes on a decibel scale.
+///
+/// Two elements `x` and `y` are considered equal if
+///
+/// ```text
+///     | 20 * log10(|x| / |y|) | <= tol_db.
+/// ```
+///
+/// If both operands are zero, they are considered equal. If exactly one of them is zero,
+/// the ratio is either zero or infinite, and the comparison fails with a difference of
+/// `-inf` dB (`x` is zero) or `+inf` dB (`y` is zero).
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecibelElementwiseComparator {
+    /// The maximum difference in decibels tolerated (inclusive).
+    pub tol_db: f64,
+}
+
+/// The difference in decibels between two elements, `20 * log10(|x| / |y|)`.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecibelError(pub f64);
+
+#[cfg(feature = "std")]
+impl Display for DecibelError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Difference: ")?;
+        self.0.fmt(f)?;
+        write!(f, " dB.")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecibelError {}
+
+#[cfg(feature = "std")]
+impl<T> ElementwiseComparator<T> for DecibelElementwiseComparator
+where
+    T: Clone + Into<f64>,
+{
+    type Error = DecibelError;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), DecibelError> {
+        assert!(self.tol_db >= 0.0);
+
+        let a: f64 = a.clone().into();
+        let b: f64 = b.clone().into();
+
+        let diff_db = if a == 0.0 && b == 0.0 {
+            0.0
+        } else if a == 0.0 {
+            f64::NEG_INFINITY
+        } else if b == 0.0 {
+            f64::INFINITY
+        } else {
+            20.0 * (a.abs() / b.abs()).log10()
+        };
+
+        // Note: NaN operands produce a NaN difference, which fails the comparison
+        if diff_db.abs() <= self.tol_db {
+            Ok(())
+        } else {
+            Err(DecibelError(diff_db))
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "decibel difference, |20 log10(|x| / |y|)| <= {tol} dB.",
+            tol = self.tol_db
+        )
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        x.clone().into().is_nan()
+    }
+}
+
+/// The error associated with [LessEqualElementwiseComparator] and
+/// [GreaterEqualElementwiseComparator].
+///
+/// Contains the amount by which the bound was violated, i.e. `x - y` for the `le` comparator
+/// and `y - x` for the `ge` comparator.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundViolation<T>(pub T);
+
+impl<T> Display for BoundViolation<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Bound violated by ")?;
+        self.0.fmt(f)?;
+        write!(f, ".")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for BoundViolation<T> where T: fmt::Debug + Display {}
+
+/// The `le` comparator used with [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// Checks that `x` is bounded from above by `y`, i.e. `x <= y + tol`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LessEqualElementwiseComparator<T> {
+    /// The maximum amount by which `x` may exceed `y` (inclusive).
+    pub tol: T,
+}
+
+impl<T> ElementwiseComparator<T> for LessEqualElementwiseComparator<T>
+where
+    T: Clone + Display + Num + PartialOrd<T>,
+{
+    type Error = BoundViolation<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), BoundViolation<T>> {
+        assert!(self.tol >= T::zero());
+
+        // Note: We only subtract when a > b, so that unsigned types can also be used.
+        if a <= b {
+            Ok(())
+        } else {
+            let overshoot = a.clone() - b.clone();
+            if overshoot <= self.tol {
+                Ok(())
+            } else {
+                Err(BoundViolation(overshoot))
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("upper bound, x <= y + {tol}.", tol = self.tol)
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
+    }
+}
+
+/// The `ge` comparator used with [assert_matrix_eq!](../macro.assert_matrix_eq!.html).
+///
+/// Checks that `x` is bounded from below by `y`, i.e. `x >= y - tol`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GreaterEqualElementwiseComparator<T> {
+    /// The maximum amount by which `x` may fall below `y` (inclusive).
+    pub tol: T,
+}
+
+impl<T> ElementwiseComparator<T> for GreaterEqualElementwiseComparator<T>
+where
+    T: Clone + Display + Num + PartialOrd<T>,
+{
+    type Error = BoundViolation<T>;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), BoundViolation<T>> {
+        assert!(self.tol >= T::zero());
+
+        // Note: We only subtract when a < b, so that unsigned types can also be used.
+        if a >= b {
+            Ok(())
+        } else {
+            let undershoot = b.clone() - a.clone();
+            if undershoot <= self.tol {
+                Ok(())
+            } else {
+                Err(BoundViolation(undershoot))
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("lower bound, x >= y - {tol}.", tol = self.tol)
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        is_unequal_to_itself(x)
+    }
+}
+
+/// Comparator for interval types that checks whether intervals overlap.
+///
+/// Two intervals `x = [a, b]` and `y = [c, d]` are considered equal if they overlap,
+/// or if the gap between them is at most `tol`. That is,
+///
+/// ```text
+///     a <= d + tol and c <= b + tol.
+/// ```
+///
+/// Since the bounds are only required to be partially ordered, the bounds of two intervals
+/// may be incomparable (for example if a bound is NaN), in which case the comparison fails.
+///
+/// See the [Interval] trait for which types can be compared.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IntervalElementwiseComparator<B> {
+    /// The maximum gap tolerated between two intervals (inclusive).
+    pub tol: B,
+}
+
+/// The error associated with [IntervalElementwiseComparator].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum IntervalError<B> {
+    /// The intervals are disjoint, and separated by the given gap.
+    Gap(B),
+    /// The bounds of the intervals could not be compared.
+    Incomparable,
+}
+
+impl<B> Display for IntervalError<B>
+where
+    B: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IntervalError::Gap(gap) => {
+                write!(f, "Intervals do not overlap, separated by a gap of ")?;
+                gap.fmt(f)?;
+                write!(f, ".")
+            }
+            IntervalError::Incomparable => write!(f, "Interval bounds are not comparable."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B> std::error::Error for IntervalError<B> where B: fmt::Debug + Display {}
+
+impl<I, B> ElementwiseComparator<I> for IntervalElementwiseComparator<B>
+where
+    I: Interval<Bound = B>,
+    B: Clone + Display + Num + PartialOrd<B>,
+{
+    type Error = IntervalError<B>;
+
+    fn compare(&self, x: &I, y: &I) -> Result<(), IntervalError<B>> {
+        assert!(self.tol >= B::zero());
+
+        let (x_lower, x_upper) = (x.lower(), x.upper());
+        let (y_lower, y_upper) = (y.lower(), y.upper());
+
+        // Note: We cannot rely on a total order, so each case must be checked explicitly
+        let gap = if x_lower > y_upper {
+            x_lower - y_upper
+        } else if y_lower > x_upper {
+            y_lower - x_upper
+        } else if x_lower <= y_upper && y_lower <= x_upper {
+            return Ok(());
+        } else {
+            return Err(IntervalError::Incomparable);
+        };
+
+        if gap <= self.tol {
+            Ok(())
+        } else {
+            Err(IntervalError::Gap(gap))
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "interval overlap, intervals may be separated by a gap of at most {tol}.",
+            tol = self.tol
+        )
+    }
+}
+
+/// Comparator adaptor that negates an inner comparator.
+///
+/// The comparison passes if the inner comparator fails, and fails if the inner comparator
+/// passes. This is useful for asserting that two matrices are *not* equal under
+/// some criterion.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Not<C>(pub C);
+
+/// The error associated with [Not].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NotError;
+
+impl Display for NotError {
+    fn fmt(&self, _: &mut Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotError {}
+
+impl<T, C> ElementwiseComparator<T> for Not<C>
+where
+    C: ElementwiseComparator<T>,
+{
+    type Error = NotError;
+
+    fn compare(&self, a: &T, b: &T) -> Result<(), NotError> {
+        match self.0.compare(a, b) {
+            Ok(()) => Err(NotError),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "values must NOT be equal under {inner}",
+            inner = self.0.description()
+        )
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        self.0.is_nan(x)
+    }
+}
+
+/// Comparator adaptor for optional values, such as measurements that may be missing.
+///
+/// Two present values are compared with the inner comparator, and two missing values are
+/// considered equal. A present value is never equal to a missing value.
+///
+/// See the [Optional] trait for which types can be compared.
+///
+/// ```
+/// # use matrixcompare::comparators::{AbsoluteElementwiseComparator, OptionComparator};
+/// # use matrixcompare::compare_slices;
+/// let comp = OptionComparator(AbsoluteElementwiseComparator { tol: 1e-6 });
+/// let a = [Some(1.0), None, Some(3.0)];
+/// let b = [Some(1.0), None, Some(3.0 + 1e-9)];
+/// assert!(compare_slices(&a, &b, &comp).is_ok());
+/// assert!(compare_slices(&a, &[Some(1.0), Some(2.0), Some(3.0)], &comp).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OptionComparator<C>(pub C);
+
+/// The error associated with [OptionComparator].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OptionError<E> {
+    /// The value is present in x, but missing in y.
+    MissingRight,
+    /// The value is missing in x, but present in y.
+    MissingLeft,
+    /// Both values are present, but the inner comparator failed.
+    Value(E),
+}
+
+impl<E> Display for OptionError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OptionError::MissingRight => {
+                write!(f, "Presence mismatch: x is present, y is missing.")
+            }
+            OptionError::MissingLeft => write!(f, "Presence mismatch: x is missing, y is present."),
+            OptionError::Value(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> std::error::Error for OptionError<E> where E: fmt::Debug + Display {}
+
+impl<O, C> ElementwiseComparator<O> for OptionComparator<C>
+where
+    O: Optional,
+    C: ElementwiseComparator<O::Value>,
+{
+    type Error = OptionError<C::Error>;
+
+    fn compare(&self, x: &O, y: &O) -> Result<(), Self::Error> {
+        match (x.as_option(), y.as_option()) {
+            (Some(x), Some(y)) => self.0.compare(x, y).map_err(OptionError::Value),
+            (None, None) => Ok(()),
+            (Some(_), None) => Err(OptionError::MissingRight),
+            (None, Some(_)) => Err(OptionError::MissingLeft),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "{inner}\nMissing values are only equal to missing values.",
+            inner = self.0.description()
+        )
+    }
+
+    fn is_nan(&self, x: &O) -> bool {
+        x.as_option().map(|x| self.0.is_nan(x)).unwrap_or(false)
+    }
+}
+
+/// Comparator adaptor that requires both of two comparators to pass.
+///
+/// Both comparators are always run, so that the error reports every criterion that failed.
+/// Usually constructed with [all]. Adaptors can be nested to combine more than two comparators.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AndComparator<A, B>(pub A, pub B);
+
+/// The error associated with [AndComparator].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AndError<EA, EB> {
+    /// Only the first comparator failed.
+    First(EA),
+    /// Only the second comparator failed.
+    Second(EB),
+    /// Both comparators failed.
+    Both(EA, EB),
+}
+
+impl<EA, EB> Display for AndError<EA, EB>
+where
+    EA: Display,
+    EB: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AndError::First(first) => write!(f, "{}", first),
+            AndError::Second(second) => write!(f, "{}", second),
+            AndError::Both(first, second) => write!(f, "{} {}", first, second),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<EA, EB> std::error::Error for AndError<EA, EB>
+where
+    EA: fmt::Debug + Display,
+    EB: fmt::Debug + Display,
+{
+}
+
+impl<T, A, B> ElementwiseComparator<T> for AndComparator<A, B>
+where
+    A: ElementwiseComparator<T>,
+    B: ElementwiseComparator<T>,
+{
+    type Error = AndError<A::Error, B::Error>;
+
+    fn compare(&self, x: &T, y: &T) -> Result<(), Self::Error> {
+        match (self.0.compare(x, y), self.1.compare(x, y)) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(first), Ok(())) => Err(AndError::First(first)),
+            (Ok(()), Err(second)) => Err(AndError::Second(second)),
+            (Err(first), Err(second)) => Err(AndError::Both(first, second)),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "all of the following criteria:\n - {first}\n - {second}",
+            first = self.0.description(),
+            second = self.1.description()
+        )
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        self.0.is_nan(x) || self.1.is_nan(x)
+    }
+}
+
+/// Comparator adaptor that requires at least one of two comparators to pass.
+///
+/// Usually constructed with [any]. Adaptors can be nested to combine more than two comparators.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OrComparator<A, B>(pub A, pub B);
+
+/// The error associated with [OrComparator], holding the errors of both comparators.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OrError<EA, EB>(pub EA, pub EB);
+
+impl<EA, EB> Display for OrError<EA, EB>
+where
+    EA: Display,
+    EB: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.0, self.1)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<EA, EB> std::error::Error for OrError<EA, EB>
+where
+    EA: fmt::Debug + Display,
+    EB: fmt::Debug + Display,
+{
+}
+
+impl<T, A, B> ElementwiseComparator<T> for OrComparator<A, B>
+where
+    A: ElementwiseComparator<T>,
+    B: ElementwiseComparator<T>,
+{
+    type Error = OrError<A::Error, B::Error>;
+
+    fn compare(&self, x: &T, y: &T) -> Result<(), Self::Error> {
+        let first = match self.0.compare(x, y) {
+            Ok(()) => return Ok(()),
+            Err(first) => first,
+        };
+        match self.1.compare(x, y) {
+            Ok(()) => Ok(()),
+            Err(second) => Err(OrError(first, second)),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "any of the following criteria:\n - {first}\n - {second}",
+            first = self.0.description(),
+            second = self.1.description()
+        )
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        self.0.is_nan(x) || self.1.is_nan(x)
+    }
+}
+
+/// Combines two comparators into one that passes only if both of them pass.
+///
+/// ```
+/// # use matrixcompare::comparators::{all, AbsoluteElementwiseComparator, UlpElementwiseComparator};
+/// # use matrixcompare::compare_matrices; use matrixcompare_mock::mock_matrix;
+/// let comp = all((
+///     AbsoluteElementwiseComparator { tol: 1e-6 },
+///     UlpElementwiseComparator { tol: 4 },
+/// ));
+/// let a = mock_matrix![1.0, 2.0];
+/// let b = mock_matrix![1.0, 2.1];
+/// assert!(compare_matrices(&a, &a, &comp).is_ok());
+/// assert!(compare_matrices(&a, &b, &comp).is_err());
+/// ```
+pub fn all<A, B>((first, second): (A, B)) -> AndComparator<A, B> {
+    AndComparator(first, second)
+}
+
+/// Combines two comparators into one that passes if either of them passes.
+pub fn any<A, B>((first, second): (A, B)) -> OrComparator<A, B> {
+    OrComparator(first, second)
+}
+
+/// Object-safe counterpart of [ElementwiseComparator], with the error type erased.
+///
+/// [ElementwiseComparator] has an associated error type, and so it can not be used as a trait
+/// object. Every comparator whose error type is `'static` implements this trait, which allows
+/// comparators to be selected at runtime and stored as `Box<dyn DynComparator<T>>`.
+/// See [compare_matrices_dyn_comparator](crate::compare_matrices_dyn_comparator).
+pub trait DynComparator<T> {
+    /// Compares two elements, as with [ElementwiseComparator::compare].
+    fn compare_dyn(&self, x: &T, y: &T) -> Result<(), Box<dyn Display>>;
+
+    /// A description of the comparator, as with [ElementwiseComparator::description].
+    fn description_dyn(&self) -> String;
+
+    /// Whether the element is NaN, as with [ElementwiseComparator::is_nan].
+    fn is_nan_dyn(&self, x: &T) -> bool;
+}
+
+impl<T, C> DynComparator<T> for C
+where
+    C: ElementwiseComparator<T>,
+    C::Error: 'static,
+{
+    fn compare_dyn(&self, x: &T, y: &T) -> Result<(), Box<dyn Display>> {
+        self.compare(x, y)
+            .map_err(|error| Box::new(error) as Box<dyn Display>)
+    }
+
+    fn description_dyn(&self) -> String {
+        self.description()
+    }
+
+    fn is_nan_dyn(&self, x: &T) -> bool {
+        self.is_nan(x)
+    }
+}
+
+/// The error of a [DynComparator], wrapping the error of the underlying comparator.
+pub struct DynError(pub Box<dyn Display>);
+
+impl Display for DynError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for DynError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "DynError({:?})", self.0.to_string())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DynError {}
+
+/// Adaptor that lets a [DynComparator] trait object be used as an [ElementwiseComparator].
+pub(crate) struct DynComparatorAdaptor<'a, T>(pub(crate) &'a dyn DynComparator<T>);
+
+impl<'a, T> ElementwiseComparator<T> for DynComparatorAdaptor<'a, T> {
+    type Error = DynError;
+
+    fn compare(&self, x: &T, y: &T) -> Result<(), DynError> {
+        self.0.compare_dyn(x, y).map_err(DynError)
+    }
+
+    fn description(&self) -> String {
+        self.0.description_dyn()
+    }
+
+    fn is_nan(&self, x: &T) -> bool {
+        self.0.is_nan_dyn(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::comparators::{all, any, AndError, OrError};
+    use crate::comparators::{
+        with_tolerance_fn, AbsoluteElementwiseComparator, AbsoluteError, BoundViolation,
+        ComparatorSpec, DecibelElementwiseComparator, DecibelError, DecimalPlacesComparator,
+        DynamicToleranceError, ElementwiseComparator, ExactElementwiseComparator, ExactError,
+        FloatElementwiseComparator, GreaterEqualElementwiseComparator,
+        IntervalElementwiseComparator, IntervalError, LessEqualElementwiseComparator, Not,
+        NotError, OptionComparator, OptionError, RatioComparator, RatioError,
+        RelativeElementwiseComparator, RelativeError, UlpElementwiseComparator, UlpError,
+    };
+    use crate::ulp::{Ulp, UlpComparisonResult};
+    use core::cmp::Ordering;
+    use quickcheck::TestResult;
+    use std::f64;
+
+    /// Returns the next adjacent floating point number (in the direction of positive infinity)
+    fn next_f64(x: f64) -> f64 {
+        f64::from_bits(x.to_bits() + 1)
+    }
+
+    #[test]
+    pub fn absolute_comparator_integer() {
+        let comp = AbsoluteElementwiseComparator { tol: 1 };
+
+        assert_eq!(comp.compare(&0, &0), Ok(()));
+        assert_eq!(comp.compare(&1, &0), Ok(()));
+        assert_eq!(comp.compare(&-1, &0), Ok(()));
+        assert_eq!(comp.compare(&2, &0), Err(AbsoluteError(2)));
+        assert_eq!(comp.compare(&-2, &0), Err(AbsoluteError(2)));
+    }
+
+    #[test]
+    pub fn absolute_comparator_floating_point() {
+        let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+        // Note: floating point math is not generally exact, but
+        // here we only compare with 0.0, so we can expect exact results.
+        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&1.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&-1.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&2.0, &0.0), Err(AbsoluteError(2.0)));
+        assert_eq!(comp.compare(&-2.0, &0.0), Err(AbsoluteError(2.0)));
+    }
+
+    quickcheck! {
+        fn property_absolute_comparator_is_symmetric_i64(a: i64, b: i64, tol: i64) -> TestResult {
+            if tol <= 0 {
+                return TestResult::discard()
+            }
+
+            let comp = AbsoluteElementwiseComparator { tol };
+            TestResult::from_bool(comp.compare(&a, &b) == comp.compare(&b, &a))
+        }
+    }
+
+    quickcheck! {
+        fn property_absolute_comparator_is_symmetric_f64(a: f64, b: f64, tol: f64) -> TestResult {
+            if tol <= 0.0 {
+                return TestResult::discard()
+            }
+
+            // Floating point math is not exact, but the AbsoluteElementwiseComparator is designed
+            // so that it gives exactly the same result when the argument positions are reversed
+            let comp = AbsoluteElementwiseComparator { tol };
+            TestResult::from_bool(comp.compare(&a, &b) == comp.compare(&b, &a))
+        }
+    }
+
+    quickcheck! {
+        fn property_absolute_comparator_tolerance_is_not_strict_f64(tol: f64) -> TestResult {
+            if tol <= 0.0 || !tol.is_finite() {
+                return TestResult::discard()
+            }
+
+            // The comparator is defined by <=, not <
+            let comp = AbsoluteElementwiseComparator { tol };
+            let includes_tol = comp.compare(&tol, &0.0).is_ok();
+            let excludes_next_after_tol = comp.compare(&next_f64(tol), &0.0).is_err();
+            TestResult::from_bool(includes_tol && excludes_next_after_tol)
+        }
+    }
+
+    #[test]
+    pub fn exact_comparator_integer() {
+        let comp = ExactElementwiseComparator;
+
+        assert_eq!(comp.compare(&0, &0), Ok(()));
+        assert_eq!(comp.compare(&1, &0), Err(ExactError));
+        assert_eq!(comp.compare(&-1, &0), Err(ExactError));
+        assert_eq!(comp.compare(&1, &-1), Err(ExactError));
+    }
+
+    #[test]
+    pub fn order_exact_comparator() {
+        use crate::comparators::{OrderError, OrderExactComparator};
+        let comp = OrderExactComparator;
+
+        assert_eq!(comp.compare(&1, &1), Ok(()));
+        assert_eq!(comp.compare(&1, &2), Err(OrderError(Ordering::Less)));
+        assert_eq!(comp.compare(&2, &1), Err(OrderError(Ordering::Greater)));
+        assert_eq!(comp.compare(&-0.0, &0.0), Ok(()));
+
+        // NaN is unordered, and therefore equal to everything
+        assert_eq!(comp.compare(&f64::NAN, &f64::NAN), Ok(()));
+        assert_eq!(comp.compare(&f64::NAN, &1.0), Ok(()));
+        assert_eq!(comp.compare(&1.0, &f64::NAN), Ok(()));
+        assert!(comp.is_nan(&f64::NAN));
+        assert!(!comp.is_nan(&f64::INFINITY));
+
+        assert_eq!(OrderError(Ordering::Less).to_string(), "x < y.");
+        assert_eq!(
+            ElementwiseComparator::<f64>::description(&comp),
+            "order-based equality, neither x < y nor y < x."
+        );
+    }
+
+    quickcheck! {
+        fn property_order_exact_comparator_agrees_with_exact_for_non_nan(a: f64, b: f64) -> TestResult {
+            use crate::comparators::OrderExactComparator;
+            if a.is_nan() || b.is_nan() {
+                return TestResult::discard();
+            }
+            let order = OrderExactComparator.compare(&a, &b).is_ok();
+            TestResult::from_bool(order == ExactElementwiseComparator.compare(&a, &b).is_ok())
+        }
+    }
+
+    #[test]
+    pub fn exact_comparator_floating_point() {
+        let comp = ExactElementwiseComparator;
+
+        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&-0.0, &-0.0), Ok(()));
+        assert_eq!(comp.compare(&-0.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&1.0, &0.0), Err(ExactError));
         assert_eq!(comp.compare(&-1.0, &0.0), Err(ExactError));
         assert_eq!(comp.compare(&f64::NAN, &5.0), Err(ExactError));
     }
 
+    #[test]
+    pub fn comparators_detect_nan() {
+        assert!(ExactElementwiseComparator.is_nan(&f64::NAN));
+        assert!(!ExactElementwiseComparator.is_nan(&f64::INFINITY));
+        assert!(!ExactElementwiseComparator.is_nan(&1));
+        assert!(AbsoluteElementwiseComparator { tol: 1.0 }.is_nan(&f64::NAN));
+        assert!(!AbsoluteElementwiseComparator { tol: 1.0 }.is_nan(&0.0));
+        assert!(UlpElementwiseComparator { tol: 1 }.is_nan(&f32::NAN));
+        assert!(!UlpElementwiseComparator { tol: 1 }.is_nan(&0.0f32));
+    }
+
     quickcheck! {
         fn property_exact_comparator_is_symmetric_i64(a: i64, b: i64) -> bool {
             let comp = ExactElementwiseComparator;
@@ -360,25 +2006,132 @@ mod tests {
     }
 
     #[test]
-    pub fn ulp_comparator_f64() {
-        // The Ulp implementation has its own set of tests, so we just want
-        // to make a sample here
-        let comp = UlpElementwiseComparator { tol: 1 };
+    pub fn ulp_comparator_f64() {
+        // The Ulp implementation has its own set of tests, so we just want
+        // to make a sample here
+        let comp = UlpElementwiseComparator { tol: 1 };
+
+        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&0.0, &-0.0), Ok(()));
+        assert_eq!(
+            comp.compare(&-1.0, &1.0),
+            Err(UlpError::new(UlpComparisonResult::IncompatibleSigns, 2.0))
+        );
+        assert_eq!(
+            comp.compare(&1.0, &0.0),
+            Err(UlpError::new(f64::ulp_diff(&1.0, &0.0), 1.0))
+        );
+        assert_eq!(
+            comp.compare(&f64::NAN, &0.0).map_err(|err| err.result),
+            Err(UlpComparisonResult::Nan)
+        );
+    }
+
+    #[test]
+    pub fn ulp_comparator_near_zero_abs() {
+        let strict = UlpElementwiseComparator { tol: 4 };
+        let comp = strict.near_zero_abs(1e-12);
+
+        assert!(strict.compare(&1e-300, &1e-310).is_err());
+        assert!(strict.compare(&1e-15, &-1e-15).is_err());
+        assert_eq!(comp.compare(&1e-300, &1e-310), Ok(()));
+        assert_eq!(comp.compare(&1e-15, &-1e-15), Ok(()));
+        assert_eq!(comp.compare(&-1e-12, &0.0), Ok(()));
+
+        // Both values must be near zero
+        assert!(comp.compare(&1e-13, &1e-6).is_err());
+        assert!(comp.compare(&f64::NAN, &0.0).is_err());
+        assert_eq!(comp.compare(&1.0, &1.0), Ok(()));
+
+        assert_eq!(
+            comp.description(),
+            "ULP difference less than or equal to 4, unless |x| <= 0.000000000001 and \
+             |y| <= 0.000000000001. See documentation for details."
+        );
+    }
+
+    quickcheck! {
+        fn property_ulp_comparator_near_zero_abs_only_relaxes(a: f64, b: f64, tol: u64, abs: f64) -> bool {
+            let strict = UlpElementwiseComparator { tol };
+            let relaxed = strict.near_zero_abs(abs.abs());
+            let symmetric = relaxed.compare(&a, &b).is_ok() == relaxed.compare(&b, &a).is_ok();
+            symmetric && (strict.compare(&a, &b).is_err() || relaxed.compare(&a, &b).is_ok())
+        }
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    pub fn complex_ulp_comparator_reports_failed_parts() {
+        use crate::comparators::ComplexUlpComparator;
+        use num_complex::Complex;
+
+        let comp = ComplexUlpComparator { tol: 1 };
+        let x = Complex::new(1.0f64, -2.0);
+        let next = |v: f64| f64::from_bits(v.to_bits() + 1);
+
+        assert_eq!(comp.compare(&x, &x), Ok(()));
+        assert_eq!(
+            comp.compare(&x, &Complex::new(next(1.0), next(-2.0))),
+            Ok(())
+        );
+
+        let y = Complex::new(next(next(1.0)), -2.0);
+        let error = comp.compare(&x, &y).unwrap_err();
+        assert_eq!(
+            error.re.map(|err| err.result),
+            Some(UlpComparisonResult::Difference(2))
+        );
+        assert_eq!(error.im, None);
+
+        let y = Complex::new(1.0, 2.0);
+        let error = comp.compare(&x, &y).unwrap_err();
+        assert_eq!(error.re, None);
+        assert_eq!(
+            error.to_string(),
+            "Imaginary part: Numbers have incompatible signs. Absolute difference: 4."
+        );
+
+        let y = Complex::new(f64::NAN, 5.0);
+        assert_eq!(
+            comp.compare(&x, &y).unwrap_err().to_string(),
+            "Real part: NaN. Imaginary part: Numbers have incompatible signs. \
+             Absolute difference: 7."
+        );
+        assert!(comp.is_nan(&y));
+        assert!(!comp.is_nan(&x));
+    }
+
+    #[test]
+    pub fn ulp_error_caps_large_differences() {
+        let error = |diff| UlpError::new(UlpComparisonResult::Difference(diff), 0.5);
 
-        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
-        assert_eq!(comp.compare(&0.0, &-0.0), Ok(()));
         assert_eq!(
-            comp.compare(&-1.0, &1.0),
-            Err(UlpError(UlpComparisonResult::IncompatibleSigns))
+            error(1_000_000_000).to_string(),
+            "Difference: 1000000000 ULP. Absolute difference: 0.5."
         );
         assert_eq!(
-            comp.compare(&1.0, &0.0),
-            Err(UlpError(f64::ulp_diff(&1.0, &0.0)))
+            error(1_000_000_001).to_string(),
+            "Difference: > 1000000000 ULP. Absolute difference: 0.5."
+        );
+        assert_eq!(
+            format!("{:#}", error(u64::MAX)),
+            "Difference: 18446744073709551615 ULP. Absolute difference: 0.5."
+        );
+        assert_eq!(error(u64::MAX).ulp_difference(), Some(u64::MAX));
+
+        let capped = error(2_000).with_max_displayed_difference(Some(1_000));
+        assert_eq!(
+            capped.to_string(),
+            "Difference: > 1000 ULP. Absolute difference: 0.5."
         );
+        let uncapped = error(u64::MAX).with_max_displayed_difference(None);
         assert_eq!(
-            comp.compare(&f64::NAN, &0.0),
-            Err(UlpError(UlpComparisonResult::Nan))
+            uncapped.to_string(),
+            "Difference: 18446744073709551615 ULP. Absolute difference: 0.5."
         );
+
+        let incompatible = UlpError::new(UlpComparisonResult::IncompatibleSigns, 6.0);
+        assert_eq!(incompatible.ulp_difference(), None);
     }
 
     quickcheck! {
@@ -387,14 +2140,14 @@ mod tests {
                 return TestResult::discard()
             }
 
-            let comp = UlpElementwiseComparator { tol: tol };
+            let comp = UlpElementwiseComparator { tol };
             TestResult::from_bool(comp.compare(&a, &b) == comp.compare(&b, &a))
         }
     }
 
     quickcheck! {
         fn property_ulp_comparator_matches_ulp_trait(a: f64, b: f64, tol: u64) -> bool {
-            let comp = UlpElementwiseComparator { tol: tol };
+            let comp = UlpElementwiseComparator { tol };
             let result = comp.compare(&a, &b);
 
             use UlpComparisonResult::{ExactMatch, Difference};
@@ -402,7 +2155,7 @@ mod tests {
             match f64::ulp_diff(&a, &b) {
                 ExactMatch =>                      result.is_ok(),
                 Difference(diff) if diff <= tol => result.is_ok(),
-                otherwise =>                       result == Err(UlpError(otherwise))
+                otherwise =>                       result.map_err(|err| err.result) == Err(otherwise)
             }
         }
     }
@@ -418,7 +2171,10 @@ mod tests {
             let comp0 = UlpElementwiseComparator { tol: 0 };
             let comp1 = UlpElementwiseComparator { tol: 1 };
 
-            let tol_0_fails = comp0.compare(&x, &y) == Err(UlpError(UlpComparisonResult::Difference(1)));
+            let tol_0_fails = comp0.compare(&x, &y) == Err(UlpError::new(
+                UlpComparisonResult::Difference(1),
+                (y - x).abs()
+            ));
             let tol_1_succeeds = comp1.compare(&x, &y) == Ok(());
 
             TestResult::from_bool(tol_0_fails && tol_1_succeeds)
@@ -453,4 +2209,629 @@ mod tests {
             comp.compare(&a, &b) == ulpcomp.compare(&a, &b)
         }
     }
+
+    #[test]
+    pub fn float_comparator_default_tolerances_f32() {
+        let comp = FloatElementwiseComparator::<f32>::default();
+        let next = |x: f32| f32::from_bits(x.to_bits() + 1);
+        let nth_next = |x: f32, n: usize| (0..n).fold(x, |x, _| next(x));
+
+        // Values near one are compared in ULP, four of which are accepted
+        assert_eq!(comp.compare(&1.0, &nth_next(1.0, 4)), Ok(()));
+        assert!(comp.compare(&1.0, &nth_next(1.0, 5)).is_err());
+        assert_eq!(comp.compare(&1e6, &nth_next(1e6, 4)), Ok(()));
+        assert!(comp.compare(&1e6, &nth_next(1e6, 5)).is_err());
+
+        // Values much smaller than one are covered by the absolute tolerance
+        assert_eq!(comp.compare(&1e-7, &-1e-7), Ok(()));
+        assert_eq!(comp.compare(&0.0, &(4.0 * f32::EPSILON)), Ok(()));
+        assert!(comp.compare(&0.0, &(5.0 * f32::EPSILON)).is_err());
+
+        // A single-precision rounding error is not accepted in double precision
+        assert_eq!(comp.compare(&1.0, &(1.0 + f32::EPSILON)), Ok(()));
+        assert!(FloatElementwiseComparator::<f64>::default()
+            .compare(&1.0, &(1.0 + f64::from(f32::EPSILON)))
+            .is_err());
+    }
+
+    quickcheck! {
+        fn property_float_comparator_accepts_self_comparison_f32(a: f32) -> TestResult {
+            if a.is_nan() {
+                return TestResult::discard()
+            }
+            let comp = FloatElementwiseComparator::default();
+            TestResult::from_bool(comp.compare(&a, &a).is_ok())
+        }
+    }
+
+    quickcheck! {
+        fn property_float_comparator_accepts_default_ulp_tolerance_f32(a: f32, ulp: u8) -> TestResult {
+            let ulp = u32::from(ulp % 5);
+            if !a.is_finite() || !f32::from_bits(a.to_bits() + ulp).is_finite() {
+                return TestResult::discard()
+            }
+            let b = f32::from_bits(a.to_bits() + ulp);
+            let comp = FloatElementwiseComparator::default();
+            TestResult::from_bool(comp.compare(&a, &b).is_ok() && comp.compare(&b, &a).is_ok())
+        }
+    }
+
+    #[test]
+    pub fn float_comparator_subnormals_f64() {
+        let subnormal = f64::MIN_POSITIVE / 4.0;
+        let comp = FloatElementwiseComparator::default().eps(0.0).ulp(0);
+        let flushing = comp.flush_subnormals(true);
+
+        assert!(comp.compare(&subnormal, &0.0).is_err());
+        assert!(comp.compare(&subnormal, &-subnormal).is_err());
+        assert!(comp.compare(&subnormal, &(2.0 * subnormal)).is_err());
+        assert_eq!(flushing.compare(&subnormal, &0.0), Ok(()));
+        assert_eq!(flushing.compare(&subnormal, &-subnormal), Ok(()));
+        assert_eq!(flushing.compare(&subnormal, &(2.0 * subnormal)), Ok(()));
+
+        // Normal values are unaffected by flushing
+        assert!(flushing.compare(&f64::MIN_POSITIVE, &0.0).is_err());
+        assert!(flushing.compare(&1.0, &(1.0 + f64::EPSILON)).is_err());
+    }
+
+    #[test]
+    pub fn float_comparator_subnormals_f32() {
+        let subnormal = f32::MIN_POSITIVE / 4.0;
+        let comp = FloatElementwiseComparator::default().eps(0.0).ulp(0);
+        let flushing = comp.flush_subnormals(true);
+
+        assert!(comp.compare(&-subnormal, &0.0).is_err());
+        assert_eq!(flushing.compare(&-subnormal, &0.0), Ok(()));
+        assert!(flushing.compare(&-f32::MIN_POSITIVE, &0.0).is_err());
+    }
+
+    #[test]
+    pub fn float_comparator_description_mentions_flushing() {
+        let comp = FloatElementwiseComparator::<f64>::default();
+        assert!(!comp.description().contains("flushed"));
+        let comp = comp.flush_subnormals(true);
+        assert!(comp
+            .description()
+            .ends_with("\nSubnormal values are flushed to zero."));
+    }
+
+    #[test]
+    pub fn decibel_comparator_f64() {
+        let comp = DecibelElementwiseComparator { tol_db: 1.0 };
+
+        assert_eq!(comp.compare(&0.0, &0.0), Ok(()));
+        assert_eq!(comp.compare(&1.0, &1.0), Ok(()));
+        assert_eq!(comp.compare(&-1.0, &1.0), Ok(()));
+        // 20 * log10(1.1) is roughly 0.83 dB
+        assert_eq!(comp.compare(&1.1, &1.0), Ok(()));
+        assert_eq!(comp.compare(&10.0, &1.0), Err(DecibelError(20.0)));
+        assert_eq!(comp.compare(&1.0, &10.0), Err(DecibelError(-20.0)));
+        assert_eq!(
+            comp.compare(&0.0, &1.0),
+            Err(DecibelError(f64::NEG_INFINITY))
+        );
+        assert_eq!(comp.compare(&1.0, &0.0), Err(DecibelError(f64::INFINITY)));
+        assert!(comp.compare(&f64::NAN, &1.0).is_err());
+    }
+
+    #[test]
+    pub fn decibel_comparator_f32() {
+        let comp = DecibelElementwiseComparator { tol_db: 0.5 };
+
+        assert_eq!(comp.compare(&2.0f32, &2.0f32), Ok(()));
+        assert!(comp.compare(&2.0f32, &1.0f32).is_err());
+    }
+
+    #[test]
+    pub fn not_comparator_inverts_inner() {
+        let comp = Not(AbsoluteElementwiseComparator { tol: 1 });
+
+        assert_eq!(comp.compare(&0, &0), Err(NotError));
+        assert_eq!(comp.compare(&1, &0), Err(NotError));
+        assert_eq!(comp.compare(&2, &0), Ok(()));
+        assert_eq!(comp.compare(&-3, &0), Ok(()));
+        assert_eq!(
+            comp.description(),
+            "values must NOT be equal under absolute difference, |x - y| <= 1."
+        );
+    }
+
+    quickcheck! {
+        fn property_not_comparator_passes_iff_inner_fails(a: i64, b: i64) -> bool {
+            let comp = Not(ExactElementwiseComparator);
+            let inner_result = ExactElementwiseComparator.compare(&a, &b);
+            comp.compare(&a, &b).is_ok() == inner_result.is_err()
+        }
+    }
+
+    quickcheck! {
+        fn property_double_negation_matches_inner(a: f64, b: f64, max_ulp: u64) -> bool {
+            let inner = UlpElementwiseComparator { tol: max_ulp };
+            let comp = Not(Not(inner));
+            comp.compare(&a, &b).is_ok() == inner.compare(&a, &b).is_ok()
+        }
+    }
+
+    #[test]
+    pub fn dyn_comparator_erases_error_type() {
+        use crate::comparators::DynComparator;
+        use alloc::boxed::Box;
+        use alloc::vec::Vec;
+
+        let comparators: Vec<Box<dyn DynComparator<f64>>> = vec![
+            Box::new(AbsoluteElementwiseComparator { tol: 0.5 }),
+            Box::new(UlpElementwiseComparator { tol: 4 }),
+        ];
+
+        assert!(comparators[0].compare_dyn(&1.0, &1.25).is_ok());
+        assert_eq!(
+            comparators[0]
+                .compare_dyn(&1.0, &2.0)
+                .unwrap_err()
+                .to_string(),
+            "Absolute error: 1."
+        );
+        assert_eq!(
+            comparators[1]
+                .compare_dyn(&3.0, &-3.0)
+                .unwrap_err()
+                .to_string(),
+            "Numbers have incompatible signs. Absolute difference: 6."
+        );
+        assert_eq!(
+            comparators[1].description_dyn(),
+            "ULP difference less than or equal to 4. See documentation for details."
+        );
+        assert!(comparators[1].is_nan_dyn(&f64::NAN));
+    }
+
+    #[test]
+    pub fn option_comparator_distinguishes_presence_and_value_mismatches() {
+        let comp = OptionComparator(AbsoluteElementwiseComparator { tol: 1 });
+
+        assert_eq!(comp.compare(&Some(2), &Some(3)), Ok(()));
+        assert_eq!(comp.compare(&None, &None), Ok(()));
+        assert_eq!(
+            comp.compare(&Some(2), &Some(4)),
+            Err(OptionError::Value(AbsoluteError(2)))
+        );
+        assert_eq!(
+            comp.compare(&Some(2), &None),
+            Err(OptionError::MissingRight)
+        );
+        assert_eq!(comp.compare(&None, &Some(2)), Err(OptionError::MissingLeft));
+        assert_eq!(
+            OptionError::<ExactError>::MissingRight.to_string(),
+            "Presence mismatch: x is present, y is missing."
+        );
+        assert_eq!(
+            ElementwiseComparator::<Option<i32>>::description(&comp),
+            "absolute difference, |x - y| <= 1.\nMissing values are only equal to missing values."
+        );
+
+        let comp = OptionComparator(FloatElementwiseComparator::default());
+        assert!(comp.is_nan(&Some(f64::NAN)));
+        assert!(!comp.is_nan(&Some(1.0)));
+        assert!(!comp.is_nan(&None));
+    }
+
+    #[test]
+    pub fn and_comparator_reports_every_failure() {
+        let comp = all((
+            AbsoluteElementwiseComparator { tol: 2 },
+            LessEqualElementwiseComparator { tol: 0 },
+        ));
+
+        assert_eq!(comp.compare(&1, &2), Ok(()));
+        assert_eq!(comp.compare(&0, &3), Err(AndError::First(AbsoluteError(3))));
+        assert_eq!(
+            comp.compare(&3, &2),
+            Err(AndError::Second(BoundViolation(1)))
+        );
+        assert_eq!(
+            comp.compare(&5, &2),
+            Err(AndError::Both(AbsoluteError(3), BoundViolation(3)))
+        );
+        assert_eq!(
+            comp.description(),
+            "all of the following criteria:\n - absolute difference, |x - y| <= 2.\n \
+             - upper bound, x <= y + 0."
+        );
+    }
+
+    #[test]
+    pub fn or_comparator_fails_only_if_both_fail() {
+        let comp = any((
+            ExactElementwiseComparator,
+            AbsoluteElementwiseComparator { tol: 1 },
+        ));
+
+        assert_eq!(comp.compare(&2, &2), Ok(()));
+        assert_eq!(comp.compare(&2, &3), Ok(()));
+        assert_eq!(
+            comp.compare(&2, &4),
+            Err(OrError(ExactError, AbsoluteError(2)))
+        );
+    }
+
+    quickcheck! {
+        fn property_and_comparator_passes_iff_both_pass(a: f64, b: f64, tol: f64, max_ulp: u64) -> bool {
+            let abs = AbsoluteElementwiseComparator { tol: tol.abs() };
+            let ulp = UlpElementwiseComparator { tol: max_ulp };
+            let expected = abs.compare(&a, &b).is_ok() && ulp.compare(&a, &b).is_ok();
+            all((abs, ulp)).compare(&a, &b).is_ok() == expected
+        }
+    }
+
+    quickcheck! {
+        fn property_or_comparator_passes_iff_either_passes(a: f64, b: f64, tol: f64, max_ulp: u64) -> bool {
+            let abs = AbsoluteElementwiseComparator { tol: tol.abs() };
+            let ulp = UlpElementwiseComparator { tol: max_ulp };
+            let expected = abs.compare(&a, &b).is_ok() || ulp.compare(&a, &b).is_ok();
+            any((abs, ulp)).compare(&a, &b).is_ok() == expected
+        }
+    }
+
+    #[test]
+    pub fn less_equal_comparator_integer() {
+        let comp = LessEqualElementwiseComparator { tol: 1u32 };
+
+        assert_eq!(comp.compare(&0, &5), Ok(()));
+        assert_eq!(comp.compare(&5, &5), Ok(()));
+        assert_eq!(comp.compare(&6, &5), Ok(()));
+        assert_eq!(comp.compare(&8, &5), Err(BoundViolation(3)));
+    }
+
+    #[test]
+    pub fn greater_equal_comparator_integer() {
+        let comp = GreaterEqualElementwiseComparator { tol: 1u32 };
+
+        assert_eq!(comp.compare(&5, &0), Ok(()));
+        assert_eq!(comp.compare(&5, &5), Ok(()));
+        assert_eq!(comp.compare(&4, &5), Ok(()));
+        assert_eq!(comp.compare(&2, &5), Err(BoundViolation(3)));
+    }
+
+    #[test]
+    pub fn bound_comparators_nan() {
+        let le = LessEqualElementwiseComparator { tol: 0.0 };
+        let ge = GreaterEqualElementwiseComparator { tol: 0.0 };
+
+        assert!(le.compare(&f64::NAN, &0.0).is_err());
+        assert!(le.compare(&0.0, &f64::NAN).is_err());
+        assert!(ge.compare(&f64::NAN, &0.0).is_err());
+        assert!(ge.compare(&0.0, &f64::NAN).is_err());
+    }
+
+    quickcheck! {
+        fn property_less_equal_is_greater_equal_with_swapped_arguments(a: i32, b: i32, tol: u16) -> bool {
+            let (a, b, tol) = (i64::from(a), i64::from(b), i64::from(tol));
+            let le = LessEqualElementwiseComparator { tol };
+            let ge = GreaterEqualElementwiseComparator { tol };
+            le.compare(&a, &b) == ge.compare(&b, &a)
+        }
+    }
+
+    quickcheck! {
+        fn property_less_equal_matches_definition(a: i32, b: i32, tol: u16) -> bool {
+            let (a, b, tol) = (i64::from(a), i64::from(b), i64::from(tol));
+            let comp = LessEqualElementwiseComparator { tol };
+            comp.compare(&a, &b).is_ok() == (a <= b + tol)
+        }
+    }
+
+    #[test]
+    pub fn interval_comparator_overlap() {
+        let comp = IntervalElementwiseComparator { tol: 0.0 };
+
+        assert_eq!(comp.compare(&(0.0..=1.0), &(0.5..=2.0)), Ok(()));
+        assert_eq!(comp.compare(&(0.0..=1.0), &(1.0..=2.0)), Ok(()));
+        assert_eq!(comp.compare(&(0.0..=3.0), &(1.0..=2.0)), Ok(()));
+        assert_eq!(
+            comp.compare(&(0.0..=1.0), &(1.5..=2.0)),
+            Err(IntervalError::Gap(0.5))
+        );
+        assert_eq!(
+            comp.compare(&(1.5..=2.0), &(0.0..=1.0)),
+            Err(IntervalError::Gap(0.5))
+        );
+        assert_eq!(
+            comp.compare(&(0.0..=f64::NAN), &(1.0..=2.0)),
+            Err(IntervalError::Incomparable)
+        );
+    }
+
+    #[test]
+    pub fn interval_comparator_tolerance() {
+        let comp = IntervalElementwiseComparator { tol: 1 };
+
+        assert_eq!(comp.compare(&(0..=1), &(2..=3)), Ok(()));
+        assert_eq!(comp.compare(&(0..=1), &(3..=4)), Err(IntervalError::Gap(2)));
+    }
+
+    quickcheck! {
+        fn property_interval_comparator_is_symmetric(a: i32, b: i32, c: i32, d: i32, tol: u16) -> bool {
+            let x = i64::from(a.min(b))..=i64::from(a.max(b));
+            let y = i64::from(c.min(d))..=i64::from(c.max(d));
+            let comp = IntervalElementwiseComparator { tol: i64::from(tol) };
+            comp.compare(&x, &y) == comp.compare(&y, &x)
+        }
+    }
+
+    quickcheck! {
+        fn property_degenerate_intervals_match_abs_comparator(a: i32, b: i32, tol: u16) -> bool {
+            let (a, b, tol) = (i64::from(a), i64::from(b), i64::from(tol));
+            let comp = IntervalElementwiseComparator { tol };
+            let abscomp = AbsoluteElementwiseComparator { tol };
+            comp.compare(&(a..=a), &(b..=b)).is_ok() == abscomp.compare(&a, &b).is_ok()
+        }
+    }
+
+    #[test]
+    pub fn comparator_specs() {
+        let abs = AbsoluteElementwiseComparator { tol: 0.5 };
+        let ulp = UlpElementwiseComparator { tol: 3 };
+        let decimal = DecimalPlacesComparator { decimals: 6 };
+        let float = FloatElementwiseComparator::default().eps(1e-6).ulp(2);
+
+        assert_eq!(
+            ElementwiseComparator::<i32>::spec(&ExactElementwiseComparator),
+            ComparatorSpec::Exact
+        );
+        assert_eq!(abs.spec(), ComparatorSpec::Absolute { tol: 0.5 });
+        assert_eq!(
+            ElementwiseComparator::<f64>::spec(&ulp),
+            ComparatorSpec::Ulp { tol: 3 }
+        );
+        assert_eq!(
+            float.spec(),
+            ComparatorSpec::Float {
+                eps: 1e-6,
+                ulp: 2,
+                flush_subnormals: false
+            }
+        );
+        assert_eq!(
+            ElementwiseComparator::<f64>::spec(&decimal),
+            ComparatorSpec::DecimalPlaces { decimals: 6 }
+        );
+        assert_eq!(
+            ElementwiseComparator::<f64>::description(&decimal),
+            "equality to 6 decimal places, |x - y| < 0.0000015."
+        );
+
+        let not = Not(ExactElementwiseComparator);
+        assert_eq!(
+            ElementwiseComparator::<i32>::spec(&not),
+            ComparatorSpec::Custom(ElementwiseComparator::<i32>::description(&not))
+        );
+    }
+
+    #[test]
+    pub fn comparator_spec_display_matches_description() {
+        let abs = AbsoluteElementwiseComparator { tol: 0.5 };
+        let float = FloatElementwiseComparator::default().eps(1e-6).ulp(2);
+
+        assert_eq!(abs.spec().to_string(), abs.description());
+        assert_eq!(float.spec().to_string(), float.description());
+        assert_eq!(
+            ElementwiseComparator::<f64>::spec(&ExactElementwiseComparator).to_string(),
+            "exact equality x == y."
+        );
+        assert_eq!(
+            ElementwiseComparator::<f64>::spec(&UlpElementwiseComparator { tol: 3 }).to_string(),
+            "ULP difference less than or equal to 3. See documentation for details."
+        );
+    }
+
+    #[test]
+    pub fn decimal_places_comparator_matches_numpy_examples() {
+        // From the documentation of numpy.testing.assert_almost_equal
+        let (x, y) = (2.3333333333333, 2.33333334);
+        assert!(DecimalPlacesComparator { decimals: 7 }
+            .compare(&x, &y)
+            .is_ok());
+        assert!(DecimalPlacesComparator { decimals: 10 }
+            .compare(&x, &y)
+            .is_err());
+
+        // From the documentation of numpy.testing.assert_array_almost_equal
+        let comp = DecimalPlacesComparator { decimals: 5 };
+        assert!(comp.compare(&2.333, &2.333).is_ok());
+        assert!(comp.compare(&f64::NAN, &f64::NAN).is_ok());
+        assert_eq!(
+            comp.compare(&2.33333, &2.33339),
+            Err(AbsoluteError(2.33339 - 2.33333))
+        );
+    }
+
+    #[test]
+    pub fn decimal_places_comparator_boundary_matches_numpy() {
+        // In NumPy, 1.5 * 10.0**(-1) == 0.15000000000000002, so that
+        // |0.15 - 0| < threshold holds, but |0.15000000000000002 - 0| < threshold does not.
+        let comp = DecimalPlacesComparator { decimals: 1 };
+        assert!(comp.compare(&0.15, &0.0).is_ok());
+        assert_eq!(
+            comp.compare(&0.15000000000000002, &0.0),
+            Err(AbsoluteError(0.15000000000000002))
+        );
+    }
+
+    #[test]
+    pub fn decimal_places_comparator_special_values() {
+        let comp = DecimalPlacesComparator { decimals: 3 };
+        assert!(comp.compare(&f64::INFINITY, &f64::INFINITY).is_ok());
+        assert!(comp.compare(&f64::INFINITY, &f64::NEG_INFINITY).is_err());
+        assert!(comp.compare(&f64::NAN, &1.0).is_err());
+        assert!(comp.compare(&1.0f32, &1.0001f32).is_ok());
+        assert!(comp.compare(&1.0f32, &1.01f32).is_err());
+    }
+
+    #[test]
+    pub fn relative_comparator_examples() {
+        let comp = RelativeElementwiseComparator { tol: 0.05 };
+        assert!(comp.compare(&100.0, &105.0).is_ok());
+        assert!(comp.compare(&105.0, &100.0).is_ok());
+        assert!(comp.compare(&-100.0, &-104.0).is_ok());
+        assert!(comp.compare(&0.0, &-0.0).is_ok());
+        assert_eq!(
+            comp.compare(&100.0, &110.0),
+            Err(RelativeError(10.0 / 110.0))
+        );
+        assert_eq!(comp.compare(&0.0, &1e-300), Err(RelativeError(1.0)));
+        assert!(comp.compare(&1.0, &-1.0).is_err());
+    }
+
+    #[test]
+    pub fn relative_comparator_special_values() {
+        let comp = RelativeElementwiseComparator { tol: 0.5f64 };
+        assert!(comp.compare(&f64::INFINITY, &f64::INFINITY).is_ok());
+        assert_eq!(
+            comp.compare(&f64::INFINITY, &1e308),
+            Err(RelativeError(f64::INFINITY))
+        );
+        assert!(comp.compare(&f64::INFINITY, &f64::NEG_INFINITY).is_err());
+        assert!(comp.compare(&f64::NAN, &f64::NAN).is_err());
+        assert!(comp.compare(&f64::NAN, &1.0).is_err());
+        assert!(comp.is_nan(&f64::NAN));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn relative_comparator_negative_tolerance() {
+        let comp = RelativeElementwiseComparator { tol: -0.05 };
+        let _ = comp.compare(&1.0, &1.0);
+    }
+
+    #[test]
+    pub fn relative_comparator_description() {
+        let comp = RelativeElementwiseComparator { tol: 0.05 };
+        assert_eq!(
+            ElementwiseComparator::<f64>::description(&comp),
+            "relative difference, |x - y| <= 0.05 * max(|x|, |y|)."
+        );
+        assert_eq!(format!("{}", RelativeError(0.25)), "Relative error: 0.25.");
+    }
+
+    #[test]
+    pub fn dynamic_tolerance_comparator_examples() {
+        let comp = with_tolerance_fn(|x: &f64, y: &f64| 1e-6 + 1e-9 * x.abs().max(y.abs()));
+        assert!(comp.compare(&1.0, &1.0).is_ok());
+        assert!(comp.compare(&1.0, &(1.0 + 5e-7)).is_ok());
+        assert!(comp.compare(&1e6, &(1e6 + 1e-3)).is_ok());
+        assert!(comp.compare(&(1e6 + 1e-3), &1e6).is_ok());
+        assert!(comp.compare(&f64::NAN, &1.0).is_err());
+
+        let err = comp.compare(&1.0, &2.0).unwrap_err();
+        assert_eq!(err.distance, 1.0);
+        assert_eq!(err.tol, 1e-6 + 2e-9);
+        assert_eq!(
+            format!("{:.3}", err),
+            "Absolute error: 1.000, tolerance: 0.000."
+        );
+
+        // Purely relative tolerance on unsigned integers
+        let comp = with_tolerance_fn(|x: &u32, y: &u32| x.max(y) / 10);
+        assert!(comp.compare(&100, &90).is_ok());
+        assert_eq!(
+            comp.compare(&100, &89),
+            Err(DynamicToleranceError {
+                distance: 11,
+                tol: 10
+            })
+        );
+    }
+
+    #[test]
+    pub fn dynamic_tolerance_comparator_nan_tolerance() {
+        let comp = with_tolerance_fn(|x: &f64, _: &f64| 1e-6 + 1e-9 * x.abs());
+        let err = comp.compare(&f64::NAN, &1.0).unwrap_err();
+        assert!(err.distance.is_nan());
+        assert!(err.tol.is_nan());
+        assert!(comp.compare(&1.0, &f64::NAN).is_err());
+        assert!(comp.compare(&f64::NAN, &f64::NAN).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn dynamic_tolerance_comparator_negative_tolerance() {
+        let comp = with_tolerance_fn(|_: &f64, _: &f64| -1.0);
+        let _ = comp.compare(&1.0, &2.0);
+    }
+
+    #[test]
+    pub fn ratio_comparator_examples() {
+        let comp = RatioComparator { tol: 0.01 };
+        assert!(comp.compare(&100.5, &100.0).is_ok());
+        assert!(comp.compare(&-99.5, &-100.0).is_ok());
+        assert!(comp.compare(&0.0, &-0.0).is_ok());
+        assert_eq!(comp.compare(&102.0, &100.0), Err(RatioError(1.02)));
+        assert_eq!(comp.compare(&1.0, &-1.0), Err(RatioError(-1.0)));
+
+        // The criterion is not symmetric, since y is the reference
+        let comp = RatioComparator { tol: 0.1 };
+        assert!(comp.compare(&1.05, &1.0).is_ok());
+        assert_eq!(comp.compare(&1.0, &0.9), Err(RatioError(1.0 / 0.9)));
+        assert!(comp.compare(&0.9, &1.0).is_ok());
+    }
+
+    #[test]
+    pub fn ratio_comparator_special_values() {
+        let comp = RatioComparator { tol: 0.5f64 };
+        assert_eq!(comp.compare(&1.0, &0.0), Err(RatioError(f64::INFINITY)));
+        assert_eq!(
+            comp.compare(&-1.0, &0.0),
+            Err(RatioError(f64::NEG_INFINITY))
+        );
+        assert_eq!(comp.compare(&0.0, &1.0), Err(RatioError(0.0)));
+        assert!(comp.compare(&f64::INFINITY, &f64::INFINITY).is_ok());
+        assert_eq!(
+            comp.compare(&f64::INFINITY, &1e308),
+            Err(RatioError(f64::INFINITY))
+        );
+        assert!(comp.compare(&1e308, &f64::INFINITY).is_err());
+        assert!(comp.compare(&f64::NAN, &f64::NAN).is_err());
+        assert!(comp.compare(&f64::NAN, &1.0).is_err());
+        assert!(comp.is_nan(&f64::NAN));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn ratio_comparator_negative_tolerance() {
+        let comp = RatioComparator { tol: -0.05 };
+        let _ = comp.compare(&1.0, &1.0);
+    }
+
+    #[test]
+    pub fn ratio_comparator_description() {
+        let comp = RatioComparator { tol: 0.01 };
+        assert_eq!(
+            ElementwiseComparator::<f64>::description(&comp),
+            "ratio, |x / y - 1| <= 0.01."
+        );
+        assert_eq!(format!("{}", RatioError(1.25)), "Ratio x / y: 1.25.");
+    }
+
+    #[test]
+    pub fn error_display_respects_precision() {
+        let ulp_error = UlpError::new(UlpComparisonResult::Difference(4), 0.012345);
+
+        assert_eq!(
+            format!("{:.2}", AbsoluteError(0.012345)),
+            "Absolute error: 0.01."
+        );
+        assert_eq!(format!("{}", AbsoluteError(0.5)), "Absolute error: 0.5.");
+        assert_eq!(
+            format!("{:.3}", ulp_error),
+            "Difference: 4 ULP. Absolute difference: 0.012."
+        );
+        assert_eq!(
+            format!("{:.1}", BoundViolation(0.25)),
+            "Bound violated by 0.2."
+        );
+        assert_eq!(
+            format!("{:.1}", IntervalError::Gap(1.25)),
+            "Intervals do not overlap, separated by a gap of 1.2."
+        );
+    }
 }
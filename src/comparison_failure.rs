@@ -1,60 +1,153 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt;
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
+use core::hash::{Hash, Hasher};
+use matrixcompare_core::AccessError;
+use num_traits::float::FloatCore;
 
-const MAX_MISMATCH_REPORTS: usize = 12;
+pub(crate) const MAX_MISMATCH_REPORTS: usize = 12;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// The largest number of rows or columns for which a side-by-side view is rendered.
+const MAX_SIDE_BY_SIDE_DIM: usize = 10;
+
+/// The names used for the left and right matrices in the output, unless other labels are given.
+const DEFAULT_LABELS: (&str, &str) = ("X", "Y");
+
+/// A pair of elements that did not compare equal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct MatrixElementComparisonFailure<T, E> {
+    /// The element `x` of the left matrix X.
     pub left: T,
+    /// The element `y` of the right matrix Y.
     pub right: T,
     pub error: E,
     pub row: usize,
     pub col: usize,
 }
 
+impl<T, E> MatrixElementComparisonFailure<T, E> {
+    /// Swaps the roles of the left and right elements.
+    ///
+    /// The error is kept as-is, which assumes that the error metric is symmetric.
+    pub fn reverse(self) -> Self {
+        Self {
+            left: self.right,
+            right: self.left,
+            error: self.error,
+            row: self.row,
+            col: self.col,
+        }
+    }
+
+    /// The ordering of the left element `x` relative to the right element `y`.
+    ///
+    /// `Some(Ordering::Greater)` if `x > y`, which shows that the left matrix overestimates the
+    /// right matrix at this element, and `Some(Ordering::Less)` if `x < y`. Returns `None` if the
+    /// elements cannot be ordered, e.g. if one of them is NaN. Since [reverse](Self::reverse)
+    /// swaps the elements, it also reverses the ordering.
+    pub fn ordering(&self) -> Option<Ordering>
+    where
+        T: PartialOrd,
+    {
+        self.left.partial_cmp(&self.right)
+    }
+}
+
 impl<T, E> Display for MatrixElementComparisonFailure<T, E>
 where
     T: Display,
     E: Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The precision of the formatter, as in `{:.3}`, applies to the elements and the error
+        let precision = f.precision();
         write!(
             f,
             "({i}, {j}): x = {x}, y = {y}.",
             i = self.row,
             j = self.col,
-            x = self.left,
-            y = self.right
+            x = with_precision(&self.left, precision),
+            y = with_precision(&self.right, precision)
         )?;
 
         // Write the error into a string first, so that we can add a space between
         // the element output and the error output only if there is something to report.
-        let error = format!("{}", self.error);
+        let error = with_precision(&self.error, precision);
         if !error.is_empty() {
-            write!(f, " {}", self.error)?;
+            write!(f, " {}", error)?;
         }
         Ok(())
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Formats a value with the given precision, or with its default precision if `None`.
+fn with_precision<T: Display>(value: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => value.to_string(),
+    }
+}
+
+/// The dimensions of the matrices being compared do not match.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct DimensionMismatch {
+    /// The dimensions (rows, columns) of the left matrix X.
     pub dim_left: (usize, usize),
+    /// The dimensions (rows, columns) of the right matrix Y.
     pub dim_right: (usize, usize),
 }
 
-impl Display for DimensionMismatch {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+impl DimensionMismatch {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            dim_left: self.dim_right,
+            dim_right: self.dim_left,
+        }
+    }
+}
+
+impl DimensionMismatch {
+    fn fmt_labeled(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
         write!(
             f,
-            r"Dimensions of matrices X (left) and Y (right) do not match.
- dim(X) = {x_rows} x {x_cols}
- dim(Y) = {y_rows} x {y_cols}",
-            x_rows = self.dim_left.0,
-            x_cols = self.dim_left.1,
-            y_rows = self.dim_right.0,
-            y_cols = self.dim_right.1
-        )
+            "Dimensions of matrices {x} (left) and {y} (right) do not match.",
+            x = x,
+            y = y
+        )?;
+        self.fmt_dims(f, (x, y))
+    }
+
+    fn fmt_non_square(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
+        write!(
+            f,
+            "Matrices {x} (left) and {y} (right) must be square.",
+            x = x,
+            y = y
+        )?;
+        self.fmt_dims(f, (x, y))
+    }
+
+    fn fmt_dims(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
+        for &(label, (rows, cols)) in &[(x, self.dim_left), (y, self.dim_right)] {
+            write!(f, "\n dim({}) = {} x {}", label, rows, cols)?;
+            // Accidentally empty matrices are a common cause of mismatched dimensions
+            if rows == 0 || cols == 0 {
+                write!(f, " (matrix {} is empty)", label)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for DimensionMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_labeled(f, DEFAULT_LABELS)
     }
 }
 
@@ -62,7 +155,7 @@ impl Display for DimensionMismatch {
 pub type Coordinate = (usize, usize);
 
 /// A coordinate in the left or right matrix being compared.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Entry {
     Left(Coordinate),
     Right(Coordinate),
@@ -77,68 +170,1046 @@ impl Display for Entry {
     }
 }
 
+impl Entry {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Left(coord) => Self::Right(coord),
+            Self::Right(coord) => Self::Left(coord),
+        }
+    }
+}
+
+/// Mismatched elements of two matrices with matching dimensions.
+///
+/// Implements `Eq` and `Hash` if the elements and errors do, so that failures can be
+/// deduplicated, for example in a `HashSet`. The attached [statistics](Self::statistics) are
+/// not hashed, since they are floating-point numbers.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ElementsMismatch<T, Error> {
     pub comparator_description: String,
     pub mismatches: Vec<MatrixElementComparisonFailure<T, Error>>,
+    /// The number of leading rows that were compared, if the comparison was restricted
+    /// to a prefix of the rows.
+    pub compared_rows: Option<usize>,
+    /// Summary statistics of the absolute differences of all mismatches, if requested.
+    ///
+    /// See [ElementsMismatch::with_statistics].
+    pub statistics: Option<MismatchStatistics>,
+    /// Whether mirrored mismatches were deduplicated under the assumption that the matrices
+    /// are symmetric.
+    pub symmetric: bool,
+    /// Whether the comparison stopped early, in which case the mismatches are only a subset
+    /// of all mismatched elements.
+    pub stopped_early: bool,
+    /// The dimensions `(rows, cols)` of the compared matrices, if known.
+    ///
+    /// Both matrices have the same dimensions, so these are unaffected by
+    /// [reverse](Self::reverse). All matrix comparison functions in this crate provide the
+    /// dimensions; they are only unknown for mismatches constructed elsewhere.
+    pub dims: Option<(usize, usize)>,
+    /// Settings that only affect how the mismatch is displayed.
+    pub display: DisplayOptions,
+    /// The number of explicitly stored entries in the left and right matrices, respectively,
+    /// if both matrices are sparse.
+    ///
+    /// The counts are only displayed if they differ, in which case they indicate which matrix
+    /// stores more entries.
+    pub nnz: Option<(usize, usize)>,
 }
 
-impl<T, Error> Display for ElementsMismatch<T, Error>
+/// Settings for displaying an [ElementsMismatch].
+///
+/// These are taken from the [ComparisonOptions](crate::ComparisonOptions) of the comparison,
+/// and do not affect its outcome. New settings may be added in the future, so the struct can
+/// only be constructed from its default and then modified.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DisplayOptions {
+    /// The number of decimal places used when displaying the mismatched elements and their
+    /// errors.
+    ///
+    /// See [ComparisonOptions::decimal_places](crate::ComparisonOptions::decimal_places).
+    pub decimal_places: Option<usize>,
+    /// The maximum number of mismatched elements listed when the failure is displayed.
+    ///
+    /// See [ComparisonOptions::max_reports](crate::ComparisonOptions::max_reports).
+    /// The default, `None`, lists a fixed number of mismatches.
+    pub max_reports: Option<usize>,
+    /// Whether the mismatches are displayed as the mismatched columns of each row, rather than
+    /// as a list of individual elements.
+    ///
+    /// See [ComparisonOptions::group_by_row](crate::ComparisonOptions::group_by_row).
+    pub group_by_row: bool,
+}
+
+/// Summary statistics of the absolute differences `|x - y|` of a set of mismatched elements.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MismatchStatistics {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub max: f64,
+}
+
+impl MismatchStatistics {
+    fn from_abs_diffs(mut diffs: Vec<f64>) -> Option<Self> {
+        if diffs.is_empty() {
+            return None;
+        }
+
+        let count = diffs.len();
+        diffs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        let median = if count % 2 == 1 {
+            diffs[count / 2]
+        } else {
+            0.5 * (diffs[count / 2 - 1] + diffs[count / 2])
+        };
+        let mean = diffs.iter().sum::<f64>() / count as f64;
+        let max = diffs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        Some(Self {
+            count,
+            mean,
+            median,
+            max,
+        })
+    }
+}
+
+impl Display for MismatchStatistics {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Statistics of absolute differences |x - y| over all {count} mismatched elements:
+ mean = {mean}, median = {median}, max = {max}",
+            count = self.count,
+            mean = self.mean,
+            median = self.median,
+            max = self.max
+        )
+    }
+}
+
+// The statistics hold floating-point numbers, but are NaN only if the mismatched elements are,
+// in which case the elements themselves cannot satisfy `Eq` either.
+impl<T: Eq, Error: Eq> Eq for ElementsMismatch<T, Error> {}
+
+impl<T: Hash, Error: Hash> Hash for ElementsMismatch<T, Error> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Equal mismatches have equal statistics, so leaving them out is consistent with `Eq`
+        self.comparator_description.hash(state);
+        self.mismatches.hash(state);
+        self.compared_rows.hash(state);
+        self.symmetric.hash(state);
+        self.stopped_early.hash(state);
+        self.dims.hash(state);
+        self.display.hash(state);
+        self.nnz.hash(state);
+    }
+}
+
+impl<T, Error> ElementsMismatch<T, Error> {
+    /// Swaps the roles of the left and right matrices.
+    ///
+    /// See [MatrixComparisonFailure::reverse] for details.
+    pub fn reverse(self) -> Self {
+        Self {
+            comparator_description: self.comparator_description,
+            mismatches: self
+                .mismatches
+                .into_iter()
+                .map(MatrixElementComparisonFailure::reverse)
+                .collect(),
+            compared_rows: self.compared_rows,
+            statistics: self.statistics,
+            symmetric: self.symmetric,
+            stopped_early: self.stopped_early,
+            dims: self.dims,
+            display: self.display,
+            nnz: self.nnz.map(|(left, right)| (right, left)),
+        }
+    }
+}
+
+impl<T, Error> ElementsMismatch<T, Error> {
+    /// The total number of element pairs in the compared matrices, if the dimensions are known
+    /// and the number fits in a `usize`.
+    ///
+    /// This is `rows * cols` regardless of whether the matrices are dense or sparse, so that
+    /// the fraction of mismatched element pairs has the same meaning for all storage formats,
+    /// even though only the explicitly stored entries of sparse matrices are visited.
+    pub fn total_element_pairs(&self) -> Option<usize> {
+        self.dims.and_then(|(rows, cols)| rows.checked_mul(cols))
+    }
+}
+
+/// Formats `num / total` as a percentage with at most two decimals.
+///
+/// Fractions that would round to 0% or 100% without being exactly 0 or 1 are shown as
+/// `<0.01%` and `>99.99%`, so that a few mismatches are never displayed as none or all.
+fn format_percentage(num: usize, total: usize) -> String {
+    let (num, total) = (num as u128, total as u128);
+    // The percentage in hundredths, rounded to the nearest integer
+    let hundredths = (20000 * num + total) / (2 * total);
+    if hundredths == 0 && num > 0 {
+        String::from("<0.01%")
+    } else if hundredths == 10000 && num < total {
+        String::from(">99.99%")
+    } else if hundredths % 100 == 0 {
+        format!("{}%", hundredths / 100)
+    } else {
+        let decimals = format!("{:02}", hundredths % 100);
+        format!("{}.{}%", hundredths / 100, decimals.trim_end_matches('0'))
+    }
+}
+
+impl<T, Error> ElementsMismatch<T, Error>
+where
+    T: Display,
+{
+    /// Renders the mismatched elements of the left and right matrices as two grids placed side
+    /// by side.
+    ///
+    /// The grids are headed by the names of the left and right matrices, given by `labels`,
+    /// which are usually `("X", "Y")` or the labels of a [LabeledComparisonFailure].
+    /// Mismatched elements are shown with their values, marked by an asterisk. Since only the
+    /// mismatched elements are stored, elements that compare equal are shown as `.`.
+    /// For example, with the labels `("X", "Y")`:
+    ///
+    /// ```text
+    /// X (left)  Y (right)
+    ///   . 2*      . 3*
+    ///   .  .      .  .
+    /// ```
+    ///
+    /// Returns `None` if the dimensions of the matrices are unknown or exceed 10 rows or columns,
+    /// or if not every mismatch is stored, because the comparison stopped early or mirrored
+    /// mismatches of symmetric matrices were deduplicated.
+    pub fn side_by_side(&self, labels: (&str, &str)) -> Option<String> {
+        let (rows, cols) = self.dims?;
+        let is_complete = !self.stopped_early && !self.symmetric;
+        if rows > MAX_SIDE_BY_SIDE_DIM || cols > MAX_SIDE_BY_SIDE_DIM || !is_complete {
+            return None;
+        }
+
+        let mut left_cells = vec![vec![String::from("."); cols]; rows];
+        let mut right_cells = left_cells.clone();
+        for mismatch in &self.mismatches {
+            let (left, right) = (&mismatch.left, &mismatch.right);
+            left_cells[mismatch.row][mismatch.col] =
+                format!("{}*", with_precision(left, self.display.decimal_places));
+            right_cells[mismatch.row][mismatch.col] =
+                format!("{}*", with_precision(right, self.display.decimal_places));
+        }
+
+        let cell_width = left_cells
+            .iter()
+            .chain(&right_cells)
+            .flatten()
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(0);
+        let format_row = |cells: &[String]| {
+            cells
+                .iter()
+                .map(|cell| format!(" {:>width$}", cell, width = cell_width))
+                .collect::<String>()
+        };
+
+        let left_header = format!("{} (left)", labels.0);
+        let right_header = format!("{} (right)", labels.1);
+        let left_width = (cols * (cell_width + 1)).max(left_header.chars().count());
+        let mut output = format!(
+            "{:<width$}  {}",
+            left_header,
+            right_header,
+            width = left_width
+        );
+        for (left_row, right_row) in left_cells.iter().zip(&right_cells) {
+            output.push('\n');
+            output.push_str(&format!(
+                "{:<width$}  {}",
+                format_row(left_row),
+                format_row(right_row),
+                width = left_width
+            ));
+        }
+        Some(output)
+    }
+}
+
+impl<T, Error> ElementsMismatch<T, Error>
+where
+    T: Clone + Into<f64>,
+{
+    /// Computes summary statistics of the absolute differences of all mismatched elements.
+    ///
+    /// Returns `None` if there are no mismatches.
+    pub fn compute_statistics(&self) -> Option<MismatchStatistics> {
+        let diffs = self
+            .mismatches
+            .iter()
+            .map(|mismatch| {
+                let x: f64 = mismatch.left.clone().into();
+                let y: f64 = mismatch.right.clone().into();
+                if x > y {
+                    x - y
+                } else {
+                    y - x
+                }
+            })
+            .collect();
+        MismatchStatistics::from_abs_diffs(diffs)
+    }
+
+    /// Attaches summary statistics of the absolute differences to the mismatch.
+    ///
+    /// The statistics are only displayed when there are more mismatches than can be listed
+    /// individually, in which case they help to judge whether the failures are uniformly small
+    /// or dominated by a few large outliers.
+    pub fn with_statistics(self) -> Self {
+        let statistics = self.compute_statistics();
+        Self { statistics, ..self }
+    }
+}
+
+impl<T, Error> ElementsMismatch<T, Error>
 where
     T: Display,
     Error: Display,
 {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    /// The mismatched columns of each row with mismatches, in increasing order.
+    fn mismatched_columns_by_row(&self) -> Vec<(usize, Vec<usize>)> {
+        let mut coords: Vec<_> = self.mismatches.iter().map(|m| (m.row, m.col)).collect();
+        coords.sort_unstable();
+        let mut rows: Vec<(usize, Vec<usize>)> = Vec::new();
+        for (i, j) in coords {
+            match rows.last_mut() {
+                Some((row, cols)) if *row == i => cols.push(j),
+                _ => rows.push((i, vec![j])),
+            }
+        }
+        rows
+    }
+
+    fn fmt_labeled(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
         // TODO: Aligned output
         let mut formatted_mismatches = String::new();
 
-        let mismatches_overflow = self.mismatches.len() > MAX_MISMATCH_REPORTS;
+        let max_reports = self.display.max_reports.unwrap_or(MAX_MISMATCH_REPORTS);
+        let mismatches_overflow;
         // TODO: Write directly to formatter
-        let overflow_msg = if mismatches_overflow {
-            let num_hidden_entries = self.mismatches.len() - MAX_MISMATCH_REPORTS;
-            format!(
-                " ... ({} mismatching elements not shown)\n",
-                num_hidden_entries
-            )
+        let overflow_msg;
+        let listing_msg;
+        if self.display.group_by_row {
+            let rows = self.mismatched_columns_by_row();
+            mismatches_overflow = rows.len() > max_reports;
+            overflow_msg = if mismatches_overflow {
+                format!(
+                    " ... ({} rows with mismatching elements not shown)\n",
+                    rows.len() - max_reports
+                )
+            } else {
+                String::new()
+            };
+            listing_msg = String::from(
+                "The columns of the mismatched elements are listed below, grouped by row.",
+            );
+
+            for (row, cols) in rows.iter().take(max_reports) {
+                let cols: Vec<_> = cols.iter().map(ToString::to_string).collect();
+                formatted_mismatches.push_str(&format!(
+                    " Row {}: cols [{}] mismatched\n",
+                    row,
+                    cols.join(", ")
+                ));
+            }
         } else {
-            String::new()
-        };
+            mismatches_overflow = self.mismatches.len() > max_reports;
+            overflow_msg = if mismatches_overflow {
+                let num_hidden_entries = self.mismatches.len() - max_reports;
+                format!(
+                    " ... ({} mismatching elements not shown)\n",
+                    num_hidden_entries
+                )
+            } else {
+                String::new()
+            };
+            listing_msg = format!(
+                "The mismatched elements are listed below, in the format
+(row, col): x = {x}[[row, col]], y = {y}[[row, col]].",
+                x = x,
+                y = y
+            );
 
-        for mismatch in self.mismatches.iter().take(MAX_MISMATCH_REPORTS) {
-            formatted_mismatches.push_str(" ");
-            formatted_mismatches.push_str(&mismatch.to_string());
-            formatted_mismatches.push_str("\n");
+            for mismatch in self.mismatches.iter().take(max_reports) {
+                formatted_mismatches.push(' ');
+                formatted_mismatches
+                    .push_str(&with_precision(mismatch, self.display.decimal_places));
+                formatted_mismatches.push('\n');
+            }
         }
 
         // Strip off the last newline from the above
         formatted_mismatches = formatted_mismatches.trim_end().to_string();
 
+        let statistics_msg = match self.statistics {
+            Some(ref statistics) if mismatches_overflow => format!("{}\n", statistics),
+            _ => String::new(),
+        };
+
+        let at_least = if self.stopped_early { "at least " } else { "" };
+        let num = format!("{}{}", at_least, self.mismatches.len());
+        let fraction = match self.total_element_pairs() {
+            Some(total) if total > 0 => format!(
+                " out of {total} ({at_least}{percentage})",
+                total = total,
+                at_least = at_least,
+                percentage = format_percentage(self.mismatches.len(), total)
+            ),
+            _ => String::new(),
+        };
+
+        let nnz_msg = match self.nnz {
+            Some((left, right)) if left != right => format!(
+                "The sparse matrices {x} and {y} explicitly store {left} and {right} entries, \
+                 respectively.\n",
+                x = x,
+                y = y,
+                left = left,
+                right = right
+            ),
+            _ => String::new(),
+        };
+
         write!(
             f,
-            "Matrices X (left) and Y (right) have {num} mismatched element pairs.
-The mismatched elements are listed below, in the format
-(row, col): x = X[[row, col]], y = Y[[row, col]].
+            "Matrices {x} (left) and {y} (right) have {num} mismatched element pairs{fraction}.
+{nnz_msg}{listing_msg}
 
 {mismatches}
-{overflow_msg}
+{overflow_msg}{statistics_msg}
 Comparison criterion: {description}",
-            num = self.mismatches.len(),
+            x = x,
+            y = y,
+            num = num,
+            fraction = fraction,
+            nnz_msg = nnz_msg,
+            listing_msg = listing_msg,
             description = self.comparator_description,
             mismatches = formatted_mismatches,
-            overflow_msg = overflow_msg
-        )
+            overflow_msg = overflow_msg,
+            statistics_msg = statistics_msg
+        )?;
+
+        if let Some(rows) = self.compared_rows {
+            write!(f, "\nNote: Only the first {} rows were compared.", rows)?;
+        }
+        if self.stopped_early {
+            write!(
+                f,
+                "\nNote: The comparison stopped early after finding {} mismatched elements.",
+                self.mismatches.len()
+            )?;
+        }
+        if self.symmetric {
+            write!(
+                f,
+                "\nNote: The matrices were assumed to be symmetric. \
+Mirrored mismatches in the lower triangle are not shown."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, Error> Display for ElementsMismatch<T, Error>
+where
+    T: Display,
+    Error: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_labeled(f, DEFAULT_LABELS)
+    }
+}
+
+/// Sparse entries whose coordinates are out of bounds.
+///
+/// Contains all out-of-bounds coordinates of the left and right matrices, sorted in
+/// lexicographical order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutOfBoundsEntries {
+    /// Out-of-bounds coordinates explicitly stored in the left matrix.
+    pub left: Vec<Coordinate>,
+    /// Out-of-bounds coordinates explicitly stored in the right matrix.
+    pub right: Vec<Coordinate>,
+}
+
+impl OutOfBoundsEntries {
+    /// Collects the given entries, sorting the coordinates of each matrix.
+    pub(crate) fn from_entries(entries: impl IntoIterator<Item = Entry>) -> Self {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for entry in entries {
+            match entry {
+                Entry::Left(coord) => left.push(coord),
+                Entry::Right(coord) => right.push(coord),
+            }
+        }
+        left.sort_unstable();
+        right.sort_unstable();
+        Self { left, right }
+    }
+
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            left: self.right,
+            right: self.left,
+        }
+    }
+
+    /// The total number of out-of-bounds entries in both matrices.
+    pub fn len(&self) -> usize {
+        self.left.len() + self.right.len()
+    }
+
+    /// Whether there are no out-of-bounds entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The smallest out-of-bounds entry of the left matrix, or of the right matrix if the left
+    /// matrix has no out-of-bounds entries.
+    pub fn example(&self) -> Option<Entry> {
+        self.left
+            .first()
+            .map(|&coord| Entry::Left(coord))
+            .or_else(|| self.right.first().map(|&coord| Entry::Right(coord)))
+    }
+
+    /// Converts the entries into a comparison failure.
+    ///
+    /// A single out-of-bounds entry is reported as
+    /// [MatrixComparisonFailure::SparseEntryOutOfBounds], and several entries as
+    /// [MatrixComparisonFailure::SparseEntriesOutOfBounds].
+    pub(crate) fn into_failure<T, E>(self) -> MatrixComparisonFailure<T, E> {
+        match self.example() {
+            Some(entry) if self.len() == 1 => {
+                MatrixComparisonFailure::SparseEntryOutOfBounds(entry)
+            }
+            _ => MatrixComparisonFailure::SparseEntriesOutOfBounds(self),
+        }
+    }
+}
+
+impl Display for OutOfBoundsEntries {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.len() == 1 {
+            write!(f, "1 sparse entry is out of bounds.")?;
+        } else {
+            write!(f, "{} sparse entries are out of bounds.", self.len())?;
+        }
+        if let Some(example) = self.example() {
+            write!(f, " Example: {}.", example)?;
+        }
+        Ok(())
+    }
+}
+
+/// Mismatch between the sparsity patterns of two matrices.
+///
+/// Contains the coordinates that are explicitly stored in only one of the two matrices,
+/// sorted in lexicographical order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PatternMismatch {
+    /// Coordinates explicitly stored in the left matrix, but not in the right matrix.
+    pub left_only: Vec<Coordinate>,
+    /// Coordinates explicitly stored in the right matrix, but not in the left matrix.
+    pub right_only: Vec<Coordinate>,
+}
+
+impl PatternMismatch {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            left_only: self.right_only,
+            right_only: self.left_only,
+        }
+    }
+}
+
+fn write_coordinate_list(f: &mut Formatter, coords: &[Coordinate]) -> fmt::Result {
+    if coords.is_empty() {
+        return write!(f, " (none)");
+    }
+
+    for (i, j) in coords.iter().take(MAX_MISMATCH_REPORTS) {
+        write!(f, " ({}, {})", i, j)?;
+    }
+
+    if coords.len() > MAX_MISMATCH_REPORTS {
+        write!(
+            f,
+            " ... ({} more not shown)",
+            coords.len() - MAX_MISMATCH_REPORTS
+        )?;
+    }
+    Ok(())
+}
+
+impl PatternMismatch {
+    fn fmt_labeled(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
+        writeln!(
+            f,
+            "Sparsity patterns of matrices {} (left) and {} (right) do not match.",
+            x, y
+        )?;
+        write!(f, "Entries stored in {} but not in {}:", x, y)?;
+        write_coordinate_list(f, &self.left_only)?;
+        writeln!(f)?;
+        write!(f, "Entries stored in {} but not in {}:", y, x)?;
+        write_coordinate_list(f, &self.right_only)
+    }
+}
+
+impl Display for PatternMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_labeled(f, DEFAULT_LABELS)
+    }
+}
+
+/// Failure to find a permutation of the rows of one matrix that matches another.
+///
+/// Contains the rows that could not be matched in a maximum matching between the rows
+/// of the two matrices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RowPermutationMismatch {
+    pub comparator_description: String,
+    /// Rows of the left matrix that could not be matched with any row of the right matrix.
+    pub unmatched_left: Vec<usize>,
+    /// Rows of the right matrix that could not be matched with any row of the left matrix.
+    pub unmatched_right: Vec<usize>,
+}
+
+impl RowPermutationMismatch {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            comparator_description: self.comparator_description,
+            unmatched_left: self.unmatched_right,
+            unmatched_right: self.unmatched_left,
+        }
+    }
+}
+
+fn write_row_list(f: &mut Formatter, rows: &[usize]) -> fmt::Result {
+    for row in rows.iter().take(MAX_MISMATCH_REPORTS) {
+        write!(f, " {}", row)?;
+    }
+
+    if rows.len() > MAX_MISMATCH_REPORTS {
+        write!(
+            f,
+            " ... ({} more not shown)",
+            rows.len() - MAX_MISMATCH_REPORTS
+        )?;
+    }
+    Ok(())
+}
+
+impl RowPermutationMismatch {
+    fn fmt_labeled(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
+        writeln!(
+            f,
+            "No permutation of the rows of matrix {} (right) matches matrix {} (left).",
+            y, x
+        )?;
+        write!(f, "Rows of {} without a matching row in {}:", x, y)?;
+        write_row_list(f, &self.unmatched_left)?;
+        writeln!(f)?;
+        write!(f, "Rows of {} without a matching row in {}:", y, x)?;
+        write_row_list(f, &self.unmatched_right)?;
+        writeln!(f)?;
+        writeln!(f)?;
+        write!(f, "Comparison criterion: {}", self.comparator_description)
+    }
+}
+
+impl Display for RowPermutationMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_labeled(f, DEFAULT_LABELS)
+    }
+}
+
+/// Failure of two matrices to agree with respect to a global, norm-based criterion.
+///
+/// See [compare_matrices_relative_norm](crate::compare_matrices_relative_norm).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NormMismatch<T> {
+    pub comparator_description: String,
+    /// The norm of the difference between the two matrices.
+    pub residual_norm: T,
+    /// The norm of the reference matrix.
+    pub reference_norm: T,
+    /// The ratio of the residual norm to the reference norm.
+    pub relative_residual: T,
+    /// Whether the reference matrix is the left matrix, which is the case after
+    /// [reverse](MatrixComparisonFailure::reverse). Otherwise, the reference is the right matrix.
+    pub reference_is_left: bool,
+}
+
+impl<T> NormMismatch<T> {
+    /// Swaps the roles of the left and right matrices.
+    ///
+    /// The norms are unaffected, since the residual norm is symmetric, but the reference
+    /// matrix changes sides.
+    pub fn reverse(self) -> Self {
+        Self {
+            reference_is_left: !self.reference_is_left,
+            ..self
+        }
+    }
+}
+
+impl<T> NormMismatch<T>
+where
+    T: Display,
+{
+    fn fmt_labeled(&self, f: &mut Formatter, (x, y): (&str, &str)) -> fmt::Result {
+        writeln!(
+            f,
+            "Matrices {x} (left) and {y} (right) have a relative residual of {rel}.",
+            x = x,
+            y = y,
+            rel = self.relative_residual
+        )?;
+        let reference_label = if self.reference_is_left { x } else { y };
+        writeln!(
+            f,
+            "||{x} - {y}|| = {residual}, ||{reference_label}|| = {reference} (reference).",
+            x = x,
+            y = y,
+            residual = self.residual_norm,
+            reference_label = reference_label,
+            reference = self.reference_norm
+        )?;
+        writeln!(f)?;
+        write!(f, "Comparison criterion: {}", self.comparator_description)
+    }
+}
+
+impl<T: Display> Display for NormMismatch<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_labeled(f, DEFAULT_LABELS)
+    }
+}
+
+/// Failure to fetch an element of a matrix with fallible access.
+///
+/// See [compare_matrices_fallible](crate::compare_matrices_fallible).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessFailure {
+    /// The element that could not be fetched.
+    pub entry: Entry,
+    pub error: AccessError,
+}
+
+impl AccessFailure {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            entry: self.entry.reverse(),
+            error: self.error,
+        }
+    }
+}
+
+/// A dense matrix whose elements cannot be fetched within its reported dimensions.
+///
+/// See [ComparisonOptions::validate_dimensions](crate::ComparisonOptions::validate_dimensions).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MalformedMatrix {
+    /// The element whose fetch panicked.
+    pub entry: Entry,
+    /// The dimensions reported by the matrix.
+    pub dims: (usize, usize),
+}
+
+impl MalformedMatrix {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            entry: self.entry.reverse(),
+            dims: self.dims,
+        }
+    }
+}
+
+/// The way in which the structure of a sparse matrix in a compressed (CSR or CSC) format is
+/// inconsistent.
+///
+/// The major dimension is the number of rows for CSR, and the number of columns for CSC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CompressedStorageError {
+    /// The number of offsets is not the major dimension plus one.
+    OffsetCount { expected: usize, actual: usize },
+    /// The offset at the given position is smaller than the offset before it.
+    DecreasingOffsets { position: usize },
+    /// The number of indices or values does not match the last offset.
+    LengthMismatch {
+        nnz: usize,
+        indices: usize,
+        values: usize,
+    },
+}
+
+impl Display for CompressedStorageError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            CompressedStorageError::OffsetCount { expected, actual } => {
+                write!(f, "expected {} offsets, but found {}", expected, actual)
+            }
+            CompressedStorageError::DecreasingOffsets { position } => {
+                write!(f, "the offsets decrease at position {}", position)
+            }
+            CompressedStorageError::LengthMismatch {
+                nnz,
+                indices,
+                values,
+            } => write!(
+                f,
+                "the last offset is {}, but there are {} indices and {} values",
+                nnz, indices, values
+            ),
+        }
+    }
+}
+
+/// A sparse matrix in a compressed (CSR or CSC) format whose offsets or indices are inconsistent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MalformedCompressedStorage {
+    /// Whether the left matrix is malformed, rather than the right matrix.
+    pub is_left: bool,
+    pub error: CompressedStorageError,
+}
+
+impl MalformedCompressedStorage {
+    /// Swaps the roles of the left and right matrices.
+    pub fn reverse(self) -> Self {
+        Self {
+            is_left: !self.is_left,
+            error: self.error,
+        }
+    }
+}
+
+/// The kind of a non-finite floating-point value.
+///
+/// See [compare_matrix_finite](crate::compare_matrix_finite).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SpecialValueKind {
+    Nan,
+    PositiveInfinity,
+    NegativeInfinity,
+}
+
+impl SpecialValueKind {
+    /// Returns the kind of the value, or `None` if the value is finite.
+    pub fn classify<T: FloatCore>(x: T) -> Option<Self> {
+        if x.is_nan() {
+            Some(SpecialValueKind::Nan)
+        } else if x == T::infinity() {
+            Some(SpecialValueKind::PositiveInfinity)
+        } else if x == T::neg_infinity() {
+            Some(SpecialValueKind::NegativeInfinity)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for SpecialValueKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let token = match self {
+            SpecialValueKind::Nan => "NaN",
+            SpecialValueKind::PositiveInfinity => "+inf",
+            SpecialValueKind::NegativeInfinity => "-inf",
+        };
+        write!(f, "{}", token)
+    }
+}
+
+/// Formats the non-finite elements found by [compare_matrix_finite](crate::compare_matrix_finite)
+/// for the panic message of [assert_matrix_finite!](crate::assert_matrix_finite).
+#[doc(hidden)]
+pub fn format_non_finite_elements(
+    label: &str,
+    elements: &[(Coordinate, SpecialValueKind)],
+) -> String {
+    let mut message = format!(
+        "Matrix {} has {} non-finite {}.",
+        label,
+        elements.len(),
+        if elements.len() == 1 {
+            "element"
+        } else {
+            "elements"
+        }
+    );
+    for ((i, j), kind) in elements.iter().take(MAX_MISMATCH_REPORTS) {
+        message += &format!("\n ({}, {}): {}", i, j, kind);
+    }
+    if elements.len() > MAX_MISMATCH_REPORTS {
+        message += &format!(
+            "\n ... ({} more not shown)",
+            elements.len() - MAX_MISMATCH_REPORTS
+        );
+    }
+    message
+}
+
+/// A compact discriminant of a [MatrixComparisonFailure], without any of its data.
+///
+/// The `Display` output is a short, stable token, which is suitable for logging and for
+/// aggregating failures across many tests.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FailureKind {
+    DimensionMismatch,
+    ElementMismatch,
+    SparseOutOfBounds,
+    DuplicateSparse,
+    PatternMismatch,
+    RowPermutationMismatch,
+    NormMismatch,
+    NanEncountered,
+    AccessError,
+    MalformedMatrix,
+}
+
+impl Display for FailureKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let token = match self {
+            FailureKind::DimensionMismatch => "dimension_mismatch",
+            FailureKind::ElementMismatch => "element_mismatch",
+            FailureKind::SparseOutOfBounds => "sparse_out_of_bounds",
+            FailureKind::DuplicateSparse => "duplicate_sparse",
+            FailureKind::PatternMismatch => "pattern_mismatch",
+            FailureKind::RowPermutationMismatch => "row_permutation_mismatch",
+            FailureKind::NormMismatch => "norm_mismatch",
+            FailureKind::NanEncountered => "nan_encountered",
+            FailureKind::AccessError => "access_error",
+            FailureKind::MalformedMatrix => "malformed_matrix",
+        };
+        write!(f, "{}", token)
     }
 }
 
 /// The error type associated with matrix comparison.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Implements `Eq` and `Hash` if both the element type `T` and the comparator error type do,
+/// which allows failures to be deduplicated, for example in a `HashSet`. This is the case for
+/// integer elements compared with the exact or absolute comparators, but not for floating-point
+/// elements, since `f32` and `f64` do not implement `Eq` or `Hash`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MatrixComparisonFailure<T, Error> {
     MismatchedDimensions(DimensionMismatch),
-    MismatchedElements(ElementsMismatch<T, Error>),
+    /// The dimensions of the matrices match, but the comparison requires square matrices.
+    NonSquareDimensions(DimensionMismatch),
+    /// Some elements do not match. The mismatch is boxed to keep the failure, and with it every
+    /// `Result` returned by the comparison functions, small.
+    MismatchedElements(Box<ElementsMismatch<T, Error>>),
+    /// A single sparse entry is out of bounds.
     SparseEntryOutOfBounds(Entry),
+    /// Several sparse entries are out of bounds, in one or both matrices.
+    SparseEntriesOutOfBounds(OutOfBoundsEntries),
     DuplicateSparseEntry(Entry),
+    PatternMismatch(PatternMismatch),
+    /// The rows cannot be permuted to match. Boxed for the same reason as `MismatchedElements`.
+    RowPermutationMismatch(Box<RowPermutationMismatch>),
+    NormMismatch(NormMismatch<T>),
+    /// A NaN element was found, with [ComparisonOptions::reject_nan](crate::ComparisonOptions)
+    /// enabled.
+    NanEncountered(Entry),
+    /// An element could not be fetched from a matrix with fallible access.
+    AccessError(AccessFailure),
+    /// A dense matrix failed to provide an element within its reported dimensions, with
+    /// [ComparisonOptions::validate_dimensions](crate::ComparisonOptions) enabled.
+    MalformedMatrix(MalformedMatrix),
+    /// A sparse matrix in a compressed format has inconsistent offsets or indices.
+    MalformedCompressedStorage(MalformedCompressedStorage),
+}
+
+impl<T, Error> MatrixComparisonFailure<T, Error> {
+    /// Swaps the roles of the left and right matrices.
+    ///
+    /// The reversed failure describes the comparison with the operands swapped, so that its
+    /// `Display` output refers to the original right matrix as X (left) and the original left
+    /// matrix as Y (right). Element errors are kept as-is, which assumes that the error metric
+    /// of the comparator is symmetric. For norm mismatches, the reference matrix changes sides,
+    /// but remains the reference of the relative criterion.
+    pub fn reverse(self) -> Self {
+        use MatrixComparisonFailure::*;
+        match self {
+            MismatchedDimensions(dim) => MismatchedDimensions(dim.reverse()),
+            NonSquareDimensions(dim) => NonSquareDimensions(dim.reverse()),
+            MismatchedElements(elements) => MismatchedElements(Box::new(elements.reverse())),
+            SparseEntryOutOfBounds(entry) => SparseEntryOutOfBounds(entry.reverse()),
+            SparseEntriesOutOfBounds(entries) => SparseEntriesOutOfBounds(entries.reverse()),
+            DuplicateSparseEntry(entry) => DuplicateSparseEntry(entry.reverse()),
+            PatternMismatch(pattern) => PatternMismatch(pattern.reverse()),
+            RowPermutationMismatch(mismatch) => {
+                RowPermutationMismatch(Box::new(mismatch.reverse()))
+            }
+            NormMismatch(mismatch) => NormMismatch(mismatch.reverse()),
+            NanEncountered(entry) => NanEncountered(entry.reverse()),
+            AccessError(failure) => AccessError(failure.reverse()),
+            MalformedMatrix(malformed) => MalformedMatrix(malformed.reverse()),
+            MalformedCompressedStorage(malformed) => {
+                MalformedCompressedStorage(malformed.reverse())
+            }
+        }
+    }
+
+    /// Returns the kind of failure.
+    pub fn kind(&self) -> FailureKind {
+        use MatrixComparisonFailure::*;
+        match self {
+            MismatchedDimensions(_) | NonSquareDimensions(_) => FailureKind::DimensionMismatch,
+            MismatchedElements(_) => FailureKind::ElementMismatch,
+            SparseEntryOutOfBounds(_) | SparseEntriesOutOfBounds(_) => {
+                FailureKind::SparseOutOfBounds
+            }
+            DuplicateSparseEntry(_) => FailureKind::DuplicateSparse,
+            PatternMismatch(_) => FailureKind::PatternMismatch,
+            RowPermutationMismatch(_) => FailureKind::RowPermutationMismatch,
+            NormMismatch(_) => FailureKind::NormMismatch,
+            NanEncountered(_) => FailureKind::NanEncountered,
+            AccessError(_) => FailureKind::AccessError,
+            MalformedMatrix(_) | MalformedCompressedStorage(_) => FailureKind::MalformedMatrix,
+        }
+    }
+}
+
+impl<T, Error> MatrixComparisonFailure<T, Error>
+where
+    T: Clone + Into<f64>,
+{
+    /// Attaches summary statistics to mismatched elements, if any.
+    ///
+    /// See [ElementsMismatch::with_statistics].
+    pub fn with_statistics(self) -> Self {
+        match self {
+            MatrixComparisonFailure::MismatchedElements(mismatch) => {
+                MatrixComparisonFailure::MismatchedElements(Box::new(mismatch.with_statistics()))
+            }
+            failure => failure,
+        }
+    }
 }
 
+#[cfg(feature = "std")]
 impl<T, E> std::error::Error for MatrixComparisonFailure<T, E>
 where
     T: fmt::Debug + Display,
@@ -146,25 +1217,457 @@ where
 {
 }
 
-impl<T, Error> Display for MatrixComparisonFailure<T, Error>
+/// A comparison failure that exposes the comparator error of its first mismatch as its
+/// [source](std::error::Error::source).
+///
+/// The failure types implement `std::error::Error` without a source, which only requires the
+/// comparator error to implement `Debug` and `Display`. Wrapping a failure with `with_source`
+/// opts in to chaining the comparator error, which then must itself implement
+/// `std::error::Error`, as do the error types of all comparators in this crate. Failures
+/// without mismatched elements have no source.
+///
+/// ```
+/// # use matrixcompare::compare_matrices;
+/// # use matrixcompare::comparators::AbsoluteElementwiseComparator;
+/// # use matrixcompare_mock::mock_matrix;
+/// use std::error::Error;
+///
+/// let comp = AbsoluteElementwiseComparator { tol: 0 };
+/// let failure = compare_matrices(mock_matrix![1, 2], mock_matrix![1, 3], &comp).unwrap_err();
+/// assert!(failure.source().is_none());
+/// assert_eq!(
+///     failure.with_source().source().unwrap().to_string(),
+///     "Absolute error: 1."
+/// );
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithSource<F>(pub F);
+
+#[cfg(feature = "std")]
+impl<F: Display> Display for WithSource<F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> MatrixComparisonFailure<T, E> {
+    /// Wraps the failure so that the comparator error of the first mismatch is its source.
+    ///
+    /// See [WithSource] for details.
+    pub fn with_source(self) -> WithSource<Self> {
+        WithSource(self)
+    }
+
+    fn first_error(&self) -> Option<&E> {
+        match self {
+            MatrixComparisonFailure::MismatchedElements(mismatch) => {
+                mismatch.mismatches.first().map(|first| &first.error)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> std::error::Error for WithSource<MatrixComparisonFailure<T, E>>
+where
+    T: fmt::Debug + Display,
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0
+            .first_error()
+            .map(|error| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> LabeledComparisonFailure<T, E> {
+    /// Wraps the failure so that the comparator error of the first mismatch is its source.
+    ///
+    /// See [WithSource] for details.
+    pub fn with_source(self) -> WithSource<Self> {
+        WithSource(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> std::error::Error for WithSource<LabeledComparisonFailure<T, E>>
+where
+    T: fmt::Debug + Display,
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0
+            .failure
+            .first_error()
+            .map(|error| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl<T, Error> MatrixComparisonFailure<T, Error>
 where
     T: Display,
     Error: Display,
 {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    fn fmt_labeled(&self, f: &mut Formatter, labels: (&str, &str)) -> fmt::Result {
         match self {
-            MatrixComparisonFailure::MismatchedElements(ref mismatch) => mismatch.fmt(f),
-            MatrixComparisonFailure::MismatchedDimensions(ref mismatch) => mismatch.fmt(f),
+            MatrixComparisonFailure::MismatchedElements(ref mismatch) => {
+                mismatch.fmt_labeled(f, labels)
+            }
+            MatrixComparisonFailure::MismatchedDimensions(ref mismatch) => {
+                mismatch.fmt_labeled(f, labels)
+            }
+            MatrixComparisonFailure::NonSquareDimensions(ref mismatch) => {
+                mismatch.fmt_non_square(f, labels)
+            }
             MatrixComparisonFailure::SparseEntryOutOfBounds(entry) => write!(
                 f,
                 r"At least one sparse entry is out of bounds. Example: {}.",
                 entry
             ),
+            MatrixComparisonFailure::SparseEntriesOutOfBounds(entries) => {
+                write!(f, "{}", entries)
+            }
             MatrixComparisonFailure::DuplicateSparseEntry(entry) => write!(
                 f,
                 r"At least one duplicate sparse entry detected. Example: {}.",
                 entry
             ),
+            MatrixComparisonFailure::PatternMismatch(ref mismatch) => {
+                mismatch.fmt_labeled(f, labels)
+            }
+            MatrixComparisonFailure::RowPermutationMismatch(ref mismatch) => {
+                mismatch.fmt_labeled(f, labels)
+            }
+            MatrixComparisonFailure::NormMismatch(ref mismatch) => mismatch.fmt_labeled(f, labels),
+            MatrixComparisonFailure::NanEncountered(entry) => {
+                let (label, side, (i, j)) = match *entry {
+                    Entry::Left(coord) => (labels.0, "left", coord),
+                    Entry::Right(coord) => (labels.1, "right", coord),
+                };
+                write!(
+                    f,
+                    "Matrix {label} ({side}) contains NaN at ({i}, {j}).",
+                    label = label,
+                    side = side,
+                    i = i,
+                    j = j
+                )
+            }
+            MatrixComparisonFailure::AccessError(failure) => {
+                let (label, side, (i, j)) = match failure.entry {
+                    Entry::Left(coord) => (labels.0, "left", coord),
+                    Entry::Right(coord) => (labels.1, "right", coord),
+                };
+                write!(
+                    f,
+                    "Failed to fetch the element at ({i}, {j}) of matrix {label} ({side}): {error}",
+                    label = label,
+                    side = side,
+                    i = i,
+                    j = j,
+                    error = failure.error
+                )
+            }
+            MatrixComparisonFailure::MalformedMatrix(malformed) => {
+                let (label, side, (i, j)) = match malformed.entry {
+                    Entry::Left(coord) => (labels.0, "left", coord),
+                    Entry::Right(coord) => (labels.1, "right", coord),
+                };
+                let (rows, cols) = malformed.dims;
+                write!(
+                    f,
+                    "Matrix {label} ({side}) is malformed: fetching the element at ({i}, {j}) \
+                     panicked, although the matrix reports dimensions {rows} x {cols}.",
+                    label = label,
+                    side = side,
+                    i = i,
+                    j = j,
+                    rows = rows,
+                    cols = cols
+                )
+            }
+            MatrixComparisonFailure::MalformedCompressedStorage(malformed) => {
+                let (label, side) = if malformed.is_left {
+                    (labels.0, "left")
+                } else {
+                    (labels.1, "right")
+                };
+                write!(
+                    f,
+                    "Matrix {label} ({side}) has malformed compressed sparse storage: {error}.",
+                    label = label,
+                    side = side,
+                    error = malformed.error
+                )
+            }
+        }
+    }
+}
+
+impl<T, Error> Display for MatrixComparisonFailure<T, Error>
+where
+    T: Display,
+    Error: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_labeled(f, DEFAULT_LABELS)
+    }
+}
+
+/// A comparison failure, together with the names used for the matrices in its output.
+///
+/// By default, the left and right matrices are referred to as X and Y. Attaching labels
+/// replaces these names with more meaningful ones, such as "predicted" and "expected".
+/// An optional context, such as the name of the test step, can be attached with
+/// [LabeledComparisonFailure::with_context], and is then written on a line of its own
+/// before the rest of the output.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LabeledComparisonFailure<T, Error> {
+    pub failure: MatrixComparisonFailure<T, Error>,
+    /// The names of the left and right matrices, respectively.
+    pub labels: (String, String),
+    /// A description of where the comparison took place, shown before the failure.
+    pub context: Option<String>,
+}
+
+impl<T, Error> MatrixComparisonFailure<T, Error> {
+    /// Attaches names for the left and right matrices to be used in the output.
+    pub fn with_labels(self, left: &str, right: &str) -> LabeledComparisonFailure<T, Error> {
+        LabeledComparisonFailure {
+            failure: self,
+            labels: (left.to_string(), right.to_string()),
+            context: None,
+        }
+    }
+}
+
+impl<T, Error> LabeledComparisonFailure<T, Error> {
+    /// Swaps the roles of the left and right matrices, together with their labels.
+    ///
+    /// See [MatrixComparisonFailure::reverse]. The context is kept as-is.
+    pub fn reverse(self) -> Self {
+        Self {
+            failure: self.failure.reverse(),
+            labels: (self.labels.1, self.labels.0),
+            context: self.context,
+        }
+    }
+
+    /// Attaches a context to be written before the failure in the output.
+    ///
+    /// ```
+    /// # use matrixcompare::compare_matrices_labeled;
+    /// # use matrixcompare::comparators::ExactElementwiseComparator;
+    /// # use matrixcompare_mock::mock_matrix;
+    /// let failure = compare_matrices_labeled(
+    ///     mock_matrix![1, 2],
+    ///     mock_matrix![1, 3],
+    ///     &ExactElementwiseComparator,
+    ///     ("X", "Y"),
+    /// )
+    /// .unwrap_err()
+    /// .with_context("step 3 residual");
+    /// assert!(failure.to_string().starts_with("step 3 residual\nMatrices X (left)"));
+    /// ```
+    pub fn with_context(self, context: &str) -> Self {
+        Self {
+            context: Some(context.to_string()),
+            ..self
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> std::error::Error for LabeledComparisonFailure<T, E>
+where
+    T: fmt::Debug + Display,
+    E: fmt::Debug + Display,
+{
+}
+
+impl<T, Error> Display for LabeledComparisonFailure<T, Error>
+where
+    T: Display,
+    Error: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(ref context) = self.context {
+            writeln!(f, "{}", context)?;
+        }
+        let (ref left, ref right) = self.labels;
+        self.failure.fmt_labeled(f, (left, right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn all_variants() -> Vec<MatrixComparisonFailure<i32, ()>> {
+        use MatrixComparisonFailure::*;
+        let mismatch = |row, col, left, right| MatrixElementComparisonFailure {
+            left,
+            right,
+            error: (),
+            row,
+            col,
+        };
+        vec![
+            MismatchedDimensions(DimensionMismatch {
+                dim_left: (1, 2),
+                dim_right: (3, 4),
+            }),
+            NonSquareDimensions(DimensionMismatch {
+                dim_left: (2, 3),
+                dim_right: (2, 3),
+            }),
+            MismatchedElements(Box::new(ElementsMismatch {
+                comparator_description: "description".to_string(),
+                mismatches: vec![mismatch(0, 1, 2, 3), mismatch(1, 0, 5, 4)],
+                compared_rows: Some(2),
+                statistics: None,
+                symmetric: false,
+                stopped_early: true,
+                dims: Some((2, 2)),
+                display: DisplayOptions::default(),
+                nnz: Some((3, 2)),
+            })),
+            SparseEntryOutOfBounds(Entry::Left((5, 0))),
+            SparseEntryOutOfBounds(Entry::Right((0, 5))),
+            SparseEntriesOutOfBounds(OutOfBoundsEntries {
+                left: vec![(3, 1)],
+                right: vec![(0, 5), (4, 4)],
+            }),
+            DuplicateSparseEntry(Entry::Left((1, 0))),
+            DuplicateSparseEntry(Entry::Right((0, 1))),
+            PatternMismatch(super::PatternMismatch {
+                left_only: vec![(0, 0)],
+                right_only: vec![(1, 1), (2, 2)],
+            }),
+            RowPermutationMismatch(Box::new(super::RowPermutationMismatch {
+                comparator_description: "description".to_string(),
+                unmatched_left: vec![0],
+                unmatched_right: vec![2],
+            })),
+            NormMismatch(super::NormMismatch {
+                comparator_description: "description".to_string(),
+                residual_norm: 2,
+                reference_norm: 1,
+                relative_residual: 2,
+                reference_is_left: false,
+            }),
+            NanEncountered(Entry::Left((1, 1))),
+            NanEncountered(Entry::Right((0, 1))),
+            AccessError(AccessFailure {
+                entry: Entry::Left((1, 0)),
+                error: matrixcompare_core::AccessError::new("I/O error"),
+            }),
+            MalformedMatrix(super::MalformedMatrix {
+                entry: Entry::Right((2, 0)),
+                dims: (3, 2),
+            }),
+            MalformedCompressedStorage(super::MalformedCompressedStorage {
+                is_left: true,
+                error: super::CompressedStorageError::DecreasingOffsets { position: 2 },
+            }),
+        ]
+    }
+
+    #[test]
+    fn percentages_are_rounded_to_two_decimals() {
+        assert_eq!(format_percentage(3, 6), "50%");
+        assert_eq!(format_percentage(1, 3), "33.33%");
+        assert_eq!(format_percentage(2, 3), "66.67%");
+        assert_eq!(format_percentage(1, 8), "12.5%");
+        assert_eq!(format_percentage(6, 6), "100%");
+        assert_eq!(format_percentage(1, 1_000_000), "<0.01%");
+        assert_eq!(format_percentage(999_999, 1_000_000), ">99.99%");
+        assert_eq!(format_percentage(usize::MAX, usize::MAX), "100%");
+    }
+
+    #[test]
+    fn fraction_is_omitted_if_total_element_pairs_overflows() {
+        let mismatch = ElementsMismatch {
+            comparator_description: "description".to_string(),
+            mismatches: vec![MatrixElementComparisonFailure {
+                left: 1,
+                right: 2,
+                error: crate::comparators::ExactError,
+                row: 0,
+                col: 0,
+            }],
+            compared_rows: None,
+            statistics: None,
+            symmetric: false,
+            stopped_early: false,
+            dims: Some((usize::MAX, 2)),
+            display: DisplayOptions::default(),
+            nnz: None,
+        };
+        assert_eq!(mismatch.total_element_pairs(), None);
+        assert!(mismatch
+            .to_string()
+            .starts_with("Matrices X (left) and Y (right) have 1 mismatched element pairs."));
+    }
+
+    #[test]
+    fn element_ordering_is_reversed_with_elements() {
+        let failure = MatrixElementComparisonFailure {
+            left: 2.0,
+            right: 3.0,
+            error: (),
+            row: 0,
+            col: 1,
+        };
+        assert_eq!(failure.ordering(), Some(Ordering::Less));
+        assert_eq!(failure.reverse().ordering(), Some(Ordering::Greater));
+
+        let failure = MatrixElementComparisonFailure {
+            right: f64::NAN,
+            ..failure
+        };
+        assert_eq!(failure.ordering(), None);
+    }
+
+    #[test]
+    fn reverse_is_involution() {
+        for failure in all_variants() {
+            assert_eq!(failure.clone().reverse().reverse(), failure);
+        }
+    }
+
+    #[test]
+    fn labeled_reverse_swaps_labels() {
+        for failure in all_variants() {
+            let labeled = failure
+                .clone()
+                .with_labels("pred", "exp")
+                .with_context("step 3");
+            let reversed = labeled.clone().reverse();
+            assert_eq!(reversed.failure, failure.reverse());
+            assert_eq!(reversed.labels, ("exp".to_string(), "pred".to_string()));
+            assert_eq!(reversed.context, labeled.context);
+            assert_eq!(reversed.reverse(), labeled);
+        }
+    }
+
+    #[test]
+    fn failures_can_be_deduplicated() {
+        use crate::collections::HashSet;
+        let variants = all_variants();
+        let unique: HashSet<_> = variants.iter().chain(&variants).cloned().collect();
+        assert_eq!(unique.len(), variants.len());
+    }
+
+    #[test]
+    fn reverse_preserves_kind() {
+        for failure in all_variants() {
+            assert_eq!(failure.clone().reverse().kind(), failure.kind());
         }
     }
 }
@@ -0,0 +1,114 @@
+//! Formatting of individual matrices for debugging.
+
+use crate::matrix_comparison::Storage;
+use crate::Matrix;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use num_traits::Zero;
+
+/// The maximum number of rows or columns that are displayed in full.
+const MAX_DISPLAYED: usize = 10;
+
+/// The number of rows or columns displayed at each end of a dimension that is abbreviated.
+const DISPLAYED_AT_EACH_END: usize = MAX_DISPLAYED / 2;
+
+const ELLIPSIS: &str = "...";
+
+/// Formats a matrix as a grid with right-aligned columns.
+///
+/// Sparse matrices are formatted as dense matrices, with zeros in place of entries that are not
+/// explicitly stored. Sparse entries outside the bounds of the matrix are ignored, and if an
+/// entry is stored several times, the last one is displayed. Rows are separated by newlines,
+/// without a trailing newline, so a matrix without rows is formatted as an empty string.
+///
+/// Matrices with more than 10 rows or columns are abbreviated, showing only the first and last
+/// 5 rows or columns with an ellipsis in between.
+///
+/// ```
+/// # use matrixcompare::format_matrix; use matrixcompare_mock::mock_matrix;
+/// let a = mock_matrix![1.0, -2.5;
+///                      10.0, 4.0];
+/// assert_eq!(format_matrix(&a), " 1  -2.5\n10     4");
+/// ```
+pub fn format_matrix<T>(matrix: &impl Matrix<T>) -> String
+where
+    T: Zero + Clone + Display,
+{
+    let row_indices = displayed_indices(matrix.rows());
+    let col_indices = displayed_indices(matrix.cols());
+
+    // Cells in abbreviated rows or columns are None, and are displayed as an ellipsis
+    let zero = T::zero().to_string();
+    let mut cells: Vec<Vec<Option<String>>> = row_indices
+        .iter()
+        .map(|i| {
+            col_indices
+                .iter()
+                .map(|j| i.and(*j).map(|_| zero.clone()))
+                .collect()
+        })
+        .collect();
+
+    match Storage::from(matrix.access()) {
+        Storage::Dense(access) => {
+            for (r, i) in row_indices.iter().enumerate() {
+                for (c, j) in col_indices.iter().enumerate() {
+                    if let (Some(i), Some(j)) = (i, j) {
+                        cells[r][c] = Some(access.fetch_single(*i, *j).to_string());
+                    }
+                }
+            }
+        }
+        Storage::Sparse(access) => {
+            for (i, j, v) in access.entries() {
+                let r = row_indices.iter().position(|&index| index == Some(i));
+                let c = col_indices.iter().position(|&index| index == Some(j));
+                if let (Some(r), Some(c)) = (r, c) {
+                    cells[r][c] = Some(v.to_string());
+                }
+            }
+        }
+    }
+
+    let text_width = |cell: &Option<String>| cell.as_deref().unwrap_or(ELLIPSIS).chars().count();
+    let widths: Vec<usize> = (0..col_indices.len())
+        .map(|c| {
+            cells
+                .iter()
+                .map(|row| text_width(&row[c]))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let lines: Vec<String> = cells
+        .iter()
+        .map(|row| {
+            let padded: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| {
+                    let cell = cell.as_deref().unwrap_or(ELLIPSIS);
+                    format!("{:>width$}", cell, width = width)
+                })
+                .collect();
+            padded.join("  ")
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// The indices of the displayed rows or columns, where `None` marks the ellipsis.
+fn displayed_indices(n: usize) -> Vec<Option<usize>> {
+    if n <= MAX_DISPLAYED {
+        (0..n).map(Some).collect()
+    } else {
+        (0..DISPLAYED_AT_EACH_END)
+            .map(Some)
+            .chain(core::iter::once(None))
+            .chain((n - DISPLAYED_AT_EACH_END..n).map(Some))
+            .collect()
+    }
+}
@@ -0,0 +1,40 @@
+//! Tools for comparing intervals, such as those used in interval arithmetic.
+
+use core::ops::RangeInclusive;
+
+/// Types that represent a closed interval `[lower, upper]`.
+///
+/// Implementing this trait enables the usage of the
+/// [IntervalElementwiseComparator](crate::comparators::IntervalElementwiseComparator)
+/// for the given type.
+///
+/// Interval types typically only have a partial order, so comparing them by
+/// their difference, as done by the `abs` comparator, is not meaningful. Instead, intervals
+/// are compared by checking whether they overlap.
+///
+/// An implementation for [RangeInclusive] is provided.
+pub trait Interval {
+    /// The type of the bounds of the interval.
+    type Bound;
+
+    /// Returns the lower bound of the interval.
+    fn lower(&self) -> Self::Bound;
+
+    /// Returns the upper bound of the interval.
+    fn upper(&self) -> Self::Bound;
+}
+
+impl<T> Interval for RangeInclusive<T>
+where
+    T: Clone,
+{
+    type Bound = T;
+
+    fn lower(&self) -> T {
+        self.start().clone()
+    }
+
+    fn upper(&self) -> T {
+        self.end().clone()
+    }
+}
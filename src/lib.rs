@@ -23,7 +23,7 @@ assert_matrix_eq!(a, b, comp = abs, tol = 1e-8);
 The above example panics with the following error message
 
 ```text
-Matrices X and Y have 2 mismatched element pairs.
+Matrices X (left) and Y (right) have 2 mismatched element pairs out of 4 (50%).
 The mismatched elements are listed below, in the format
 (row, col): x = X[[row, col]], y = Y[[row, col]].
 
@@ -40,22 +40,34 @@ See the documentation for the [assert_matrix_eq!] macro for more information.
 `matrixcompare` is designed to be easy to integrate with any linear algebra library. In particular:
 
 - The core traits are defined in `matrixcompare-core`. This crate has no dependencies other than
-the standard library, and only contains a very small amount of code that defines the interface
-through which the rest of `matrixcompare` is able to access the data contained in matrices.
+  `core` and `alloc`, and only contains a very small amount of code that defines the interface
+  through which the rest of `matrixcompare` is able to access the data contained in matrices.
 - The `core` split allows the actual comparison logic and output format to evolve separately
-from the `core` crate. This way we can minimize breaking changes in `matrixcompare-core` and
-hopefully relatively soon stabilize it, without having to stabilize the entire `matrixcompare` crate.
+  from the `core` crate. This way we can minimize breaking changes in `matrixcompare-core` and
+  hopefully relatively soon stabilize it, without having to stabilize the entire `matrixcompare` crate.
 - Linear algebra library authors should only depend on and implement the traits in
-`matrixcompare-core`, while end users can use any functionality provided in `matrixcompare`.
+  `matrixcompare-core`, while end users can use any functionality provided in `matrixcompare`.
 - Since access to the underlying structures are abstracted, `matrixcompare` can be used to
-compare matrices originating from different linear algebra libraries, provided that the libraries
-in question implement the traits found in `matrixcompare-core`.
+  compare matrices originating from different linear algebra libraries, provided that the libraries
+  in question implement the traits found in `matrixcompare-core`.
 
 The design of `matrixcompare` heavily favors ease of use/integration, correctness and
 flexibility over performance. It is intended to be used for automated tests, and as such does
 not belong in performance sensitive code. There are no particular guarantees about performance,
 other than that the asymptotic complexity is roughly the same as a more optimized implementation.
 
+For quick tests that don't warrant a dependency on a linear algebra library, two-dimensional arrays
+`[[T; C]; R]` can be compared directly. Each inner array is interpreted as a row of the matrix.
+
+```rust
+# use matrixcompare::assert_matrix_eq;
+let a = [[1.0, 2.0],
+         [3.0, 4.0]];
+let b = [[1.0, 2.0],
+         [3.0, 4.0 + 1e-12]];
+assert_matrix_eq!(a, b, comp = abs, tol = 1e-8);
+```
+
 ## `proptest` integration
 
 `proptest` ships its own macros for use with its tests. Although it's possible to directly
@@ -68,10 +80,63 @@ with `proptest`.
 To use this feature, the `proptest-support` feature must be enabled. See also the `proptest`
 example in the repository.
 
+## Complex numbers
+
+Enabling the `num-complex` feature provides
+[ComplexUlpComparator](comparators::ComplexUlpComparator), which compares the real and imaginary
+parts of complex numbers from the `num-complex` crate in ULP.
+
+## The `approx` crate
+
+Enabling the `approx` feature provides [ApproxComparator](comparators::ApproxComparator), which
+delegates the comparison of elements to the `RelativeEq` trait of the
+[approx](https://crates.io/crates/approx) crate. It is available in the assertion macros as
+`comp = approx`, so that types which already implement the traits of `approx` can be compared
+without writing a custom comparator.
+
+## Detecting self-comparison
+
+An assertion such as `assert_matrix_eq!(x, x)`, where the same matrix is accidentally passed
+twice, always succeeds and may therefore hide bugs. With the opt-in `self-comparison-warning`
+feature, [compare_matrices] and the functions and macros built on it detect when both
+arguments refer to the same matrix object and write a warning with the location of the call
+to the error output. The matrices are considered the same if the matrix providing access to
+the elements has the same address, so distinct matrices with equal elements are not reported.
+The check is only performed in builds with debug assertions, and does not affect the result
+of the comparison.
+
+## `no_std` support
+
+`matrixcompare` only requires `alloc`. To use it in a `no_std` environment, disable the default
+`std` feature and enable the `hashbrown` feature instead. Without `std`, comparators that rely on
+floating-point functions from the standard library, such as the decibel comparator, are not available.
+
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::float_cmp)]
 
+#[cfg(not(any(feature = "std", feature = "hashbrown")))]
+compile_error!("matrixcompare requires either the `std` or the `hashbrown` feature to be enabled.");
+
+extern crate alloc;
+
+mod collections {
+    #[cfg(not(feature = "std"))]
+    pub(crate) use hashbrown::{HashMap, HashSet};
+    #[cfg(feature = "std")]
+    pub(crate) use std::collections::{HashMap, HashSet};
+}
+
+/// Re-exports used by the exported macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::comparison_failure::format_non_finite_elements;
+    pub use crate::triplets::TripletMatrix;
+    pub use alloc::format;
+    pub use alloc::vec;
+}
+
 #[macro_use]
 mod matrix_comparison;
 
@@ -85,17 +150,47 @@ mod comparison_failure;
 extern crate quickcheck;
 
 pub mod comparators;
+mod display;
+pub mod interval;
 mod macros;
+mod norm;
+pub mod optional;
+mod options;
+mod report;
+mod triplets;
 pub mod ulp;
 
-pub use self::matrix_comparison::compare_matrices;
-pub use self::scalar_comparison::{compare_scalars, ScalarComparisonFailure};
+pub use self::display::format_matrix;
+#[cfg(feature = "std")]
+pub use self::matrix_comparison::compare_matrices_relative_norm;
+pub use self::matrix_comparison::{
+    analyze_matrices, canonical_triplets, compare_entries, compare_f32_against_f64_reference,
+    compare_head, compare_matrices, compare_matrices_dyn, compare_matrices_dyn_comparator,
+    compare_matrices_fallible, compare_matrices_labeled, compare_matrices_scaled,
+    compare_matrices_up_to_row_permutation, compare_matrices_up_to_scale,
+    compare_matrices_with_options, compare_matrices_with_zero_threshold, compare_matrix_finite,
+    compare_matrix_to_transpose_of, compare_sparsity_patterns, compare_triangular,
+    count_mismatches, error_matrix, iter_mismatches, DuplicateEntries, ErrorGrid, Triangle,
+    Triplet,
+};
+pub use self::norm::Norm;
+pub use self::options::ComparisonOptions;
+pub use self::report::{ComparisonReport, MultiComparisonReport};
+pub use self::scalar_comparison::{
+    compare_scalars, compare_slices, LengthMismatch, ScalarComparisonFailure,
+    SliceComparisonFailure, SliceElementComparisonFailure, SliceElementsMismatch,
+};
 
 pub use self::comparison_failure::{
-    Coordinate, DimensionMismatch, ElementsMismatch, Entry, MatrixComparisonFailure,
-    MatrixElementComparisonFailure,
+    AccessFailure, CompressedStorageError, Coordinate, DimensionMismatch, DisplayOptions,
+    ElementsMismatch, Entry, FailureKind, LabeledComparisonFailure, MalformedCompressedStorage,
+    MalformedMatrix, MatrixComparisonFailure, MatrixElementComparisonFailure, MismatchStatistics,
+    NormMismatch, OutOfBoundsEntries, PatternMismatch, RowPermutationMismatch, SpecialValueKind,
 };
 
+#[cfg(feature = "std")]
+pub use self::comparison_failure::WithSource;
+
 pub use matrixcompare_core::*;
 
 #[cfg(feature = "proptest-support")]
@@ -2,91 +2,237 @@
 #[doc(hidden)]
 #[macro_export]
 macro_rules! base_matrix_eq {
-    ($failure_handler:expr, $x:expr, $y:expr) => {
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr) => {
         {
-            use $crate::{compare_matrices};
+            use $crate::{compare_matrices_labeled};
             use $crate::comparators::ExactElementwiseComparator;
 
             let comp = ExactElementwiseComparator;
-            let result = compare_matrices(&$x, &$y, &comp);
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
             if let Err(failure) = result {
                 // Note: We need the panic to incur here inside of the macro in order
                 // for the line number to be correct when using it for tests,
                 // hence we build the panic message in code, but panic here.
-                let message = format!("{}\n
+                let message = $crate::__private::format!("{}\n
 Please see the documentation for ways to compare matrices approximately.\n",
                     failure);
                 return $failure_handler(message);
             }
         }
     };
-    ($failure_handler:expr, $x:expr, $y:expr, comp = exact) => {
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = exact) => {
         {
-            use $crate::{compare_matrices};
+            use $crate::{compare_matrices_labeled};
             use $crate::comparators::ExactElementwiseComparator;
 
             let comp = ExactElementwiseComparator;
-            let result = compare_matrices(&$x, &$y, &comp);
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
             if let Err(failure) = result {
-                let message = format!("{}\n", failure);
+                let message = $crate::__private::format!("{}\n", failure);
                 return $failure_handler(message);
             }
         }
     };
-    ($failure_handler:expr, $x:expr, $y:expr, comp = abs, tol = $tol:expr) => {
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = abs, tol = $tol:expr) => {
         {
-            use $crate::{compare_matrices};
+            use $crate::{compare_matrices_labeled};
             use $crate::comparators::AbsoluteElementwiseComparator;
 
             let comp = AbsoluteElementwiseComparator { tol: $tol };
-            let result = compare_matrices(&$x, &$y, &comp);
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
             if let Err(failure) = result {
-                let message = format!("{}\n", failure);
+                let message = $crate::__private::format!("{}\n", failure);
                 return $failure_handler(message);
             }
         }
     };
-    ($failure_handler:expr, $x:expr, $y:expr, comp = ulp, tol = $tol:expr) => {
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = ulp, tol = $tol:expr) => {
         {
-            use $crate::{compare_matrices};
+            use $crate::{compare_matrices_labeled};
             use $crate::comparators::UlpElementwiseComparator;
 
             let comp = UlpElementwiseComparator { tol: $tol };
-            let result = compare_matrices(&$x, &$y, &comp);
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
             if let Err(failure) = result {
-                let message = format!("{}\n", failure);
+                let message = $crate::__private::format!("{}\n", failure);
                 return $failure_handler(message);
             }
         }
     };
-    ($failure_handler:expr, $x:expr, $y:expr, comp = float) => {
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = le, tol = $tol:expr) => {
+        {
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::LessEqualElementwiseComparator;
+
+            let comp = LessEqualElementwiseComparator { tol: $tol };
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = ge, tol = $tol:expr) => {
+        {
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::GreaterEqualElementwiseComparator;
+
+            let comp = GreaterEqualElementwiseComparator { tol: $tol };
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = percent, tol = $tol:expr) => {
         {
-            use $crate::{compare_matrices};
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::RelativeElementwiseComparator;
+
+            let comp = RelativeElementwiseComparator { tol: $tol / 100.0 };
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = ratio, tol = $tol:expr) => {
+        {
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::RatioComparator;
+
+            let comp = RatioComparator { tol: $tol };
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = decimal, places = $places:expr) => {
+        {
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::DecimalPlacesComparator;
+
+            let comp = DecimalPlacesComparator { decimals: $places };
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = float) => {
+        {
+            use $crate::{compare_matrices_labeled};
             use $crate::comparators::FloatElementwiseComparator;
 
             let comp = FloatElementwiseComparator::default();
-            let result = compare_matrices(&$x, &$y, &comp);
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
             if let Err(failure) = result {
-                let message = format!("{}", failure);
+                let message = $crate::__private::format!("{}", failure);
                 return $failure_handler(message);
             }
         }
     };
     // This following allows us to optionally tweak the epsilon and ulp tolerances
     // used in the default float comparator.
-    ($failure_handler:expr, $x:expr, $y:expr, comp = float, $($key:ident = $val:expr),+) => {
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = float, $($key:ident = $val:expr),+) => {
         {
-            use $crate::{compare_matrices};
+            use $crate::{compare_matrices_labeled};
             use $crate::comparators::FloatElementwiseComparator;
 
             let comp = FloatElementwiseComparator::default()$(.$key($val))+;
-            let result = compare_matrices(&$x, &$y, &comp);
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = approx) => {
+        {
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::ApproxComparator;
+
+            let comp = ApproxComparator::default();
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
             if let Err(failure) = result {
-                let message = format!("{}", failure);
+                let message = $crate::__private::format!("{}\n", failure);
                 return $failure_handler(message);
             }
         }
     };
+    // Optionally tweak the epsilon and max_relative tolerances passed on to approx
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = approx, $($key:ident = $val:expr),+) => {
+        {
+            use $crate::{compare_matrices_labeled};
+            use $crate::comparators::ApproxComparator;
+
+            let comp = ApproxComparator::default()$(.$key($val))+;
+            let result = compare_matrices_labeled(&$x, &$y, &comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    // Any comparator value. This must come last, so that the keywords above take precedence.
+    ($failure_handler:expr, $labels:expr, $x:expr, $y:expr, comp = $comp:expr) => {
+        {
+            use $crate::{compare_matrices_labeled};
+
+            let result = compare_matrices_labeled(&$x, &$y, &$comp, $labels);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+}
+
+/// Internal macro that extracts the optional trailing `labels = (..)` and `context = ..`
+/// arguments of the matrix comparison macros, and passes the remaining arguments on to
+/// [base_matrix_eq!].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_matrix_eq_args {
+    // Munch the arguments one token at a time, looking for trailing labels and context
+    (@labels $failure_handler:expr, [$($args:tt)*] , labels = ($left:expr, $right:expr)) => {
+        $crate::base_matrix_eq!($failure_handler, ($left, $right), $($args)*);
+    };
+    (@labels $failure_handler:expr, [$($args:tt)*] ,
+        labels = ($left:expr, $right:expr), context = $context:expr) => {
+        $crate::base_matrix_eq_args!(@context $failure_handler, $context, ($left, $right),
+            $($args)*);
+    };
+    (@labels $failure_handler:expr, [$($args:tt)*] ,
+        context = $context:expr, labels = ($left:expr, $right:expr)) => {
+        $crate::base_matrix_eq_args!(@context $failure_handler, $context, ($left, $right),
+            $($args)*);
+    };
+    (@labels $failure_handler:expr, [$($args:tt)*] , context = $context:expr) => {
+        $crate::base_matrix_eq_args!(@context $failure_handler, $context, ("X", "Y"), $($args)*);
+    };
+    (@labels $failure_handler:expr, [$($args:tt)*]) => {
+        $crate::base_matrix_eq!($failure_handler, ("X", "Y"), $($args)*);
+    };
+    (@labels $failure_handler:expr, [$($args:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::base_matrix_eq_args!(@labels $failure_handler, [$($args)* $next] $($rest)*);
+    };
+    // Write the context on the line before the failure
+    (@context $failure_handler:expr, $context:expr, $labels:expr, $($args:tt)*) => {
+        $crate::base_matrix_eq!(
+            |msg| ($failure_handler)($crate::__private::format!("{}\n{}", $context, msg)),
+            $labels,
+            $($args)*
+        );
+    };
+    ($failure_handler:expr, $($args:tt)*) => {
+        $crate::base_matrix_eq_args!(@labels $failure_handler, [] $($args)*);
+    };
 }
 
 /// Compare matrices for exact or approximate equality.
@@ -123,17 +269,52 @@ Please see the documentation for ways to compare matrices approximately.\n",
 /// assert_matrix_eq!(x, y, comp = float);
 /// assert_matrix_eq!(x, y, comp = abs, tol = 1e-12);
 /// assert_matrix_eq!(x, y, comp = ulp, tol = 8);
+/// assert_matrix_eq!(x, y, comp = le, tol = 0.0);
+/// assert_matrix_eq!(x, y, comp = ge, tol = 0.0);
+/// assert_matrix_eq!(x, y, comp = decimal, places = 6);
+/// assert_matrix_eq!(x, y, comp = percent, tol = 5.0);
+/// assert_matrix_eq!(x, y, comp = ratio, tol = 0.01);
 /// ```
 /// **Note**: The `comp` argument *must* be specified after `x` and `y`, and cannot come
 /// after comparator-specific options. This is a deliberate design decision,
 /// with the rationale that assertions should look as uniform as possible for
 /// the sake of readability.
 ///
+/// By default, the matrices are referred to as X and Y in the output. More meaningful names
+/// can be given with a trailing `labels` argument:
+///
+/// ```
+/// # use matrixcompare::assert_matrix_eq; use matrixcompare_mock::mock_matrix;
+/// # let predicted = mock_matrix![1.0f64]; let expected = mock_matrix![1.0f64];
+/// assert_matrix_eq!(predicted, expected, comp = abs, tol = 1e-12, labels = ("predicted", "expected"));
+/// ```
+///
+/// When many assertions are made in the same test, it can be hard to tell from the output alone
+/// which of them failed. A trailing `context` argument is written on a line of its own before the
+/// rest of the output. It may be combined with `labels`, in either order:
+///
+/// ```
+/// # use matrixcompare::assert_matrix_eq; use matrixcompare_mock::mock_matrix;
+/// # let x = mock_matrix![1.0f64]; let y = mock_matrix![1.0f64];
+/// assert_matrix_eq!(x, y, comp = abs, tol = 1e-12, context = "step 3 residual");
+/// assert_matrix_eq!(x, y, labels = ("x", "y"), context = "step 3 residual");
+/// ```
+///
+/// The context is any expression implementing `Display`, and is only evaluated if the
+/// assertion fails. See also
+/// [LabeledComparisonFailure::with_context](crate::LabeledComparisonFailure::with_context).
+///
 ///
 /// ### The `exact` comparator
 /// This comparator simply uses the default `==` operator to compare each pair of elements.
 /// The default comparator delegates the comparison to the `exact` comparator.
 ///
+/// The only difference between the two is that when no comparator is given, the failure
+/// message ends with a note pointing to this documentation, since the assertion may
+/// unintentionally require floating-point numbers to be exactly equal. Specify
+/// `comp = exact` to omit this note when exact equality is intended. The same applies to
+/// `assert_scalar_eq!` and `assert_slice_eq!`.
+///
 /// ### The `float` comparator
 /// The `float` comparator is designed to be a conservative default for comparing floating-point numbers.
 /// It is inspired by the `AlmostEqualUlpsAndAbs` comparison function proposed in the excellent blog post
@@ -163,6 +344,16 @@ Please see the documentation for ways to compare matrices approximately.\n",
 /// These additional parameters can be specified in any order after the choice of comparator,
 /// and do not both need to be present.
 ///
+/// By default, subnormal values are compared as they are. To match the flush-to-zero mode of
+/// some hardware, subnormal values can instead be replaced by zero before comparison:
+///
+/// ```
+/// # use matrixcompare::assert_matrix_eq; use matrixcompare_mock::mock_matrix;
+/// let x = mock_matrix![1e-310f64];
+/// let y = mock_matrix![0.0f64];
+/// assert_matrix_eq!(x, y, comp = float, eps = 0.0, flush_subnormals = true);
+/// ```
+///
 /// ### The `abs` comparator
 /// Compares the absolute difference between individual elements against the specified tolerance.
 /// Specifically, for every pair of elements x and y picked from the same row and column in X and Y
@@ -193,13 +384,105 @@ Please see the documentation for ways to compare matrices approximately.\n",
 ///
 /// ULP-based comparison is typically used when two numbers are expected to be very,
 /// very close to each other. However, it is typically not very useful very close to zero,
-/// which is discussed in the linked blog post above.
+/// which is discussed in the linked blog post above. For values near zero, either use the
+/// `float` comparator, or pass a
+/// [NearZeroUlpComparator](crate::comparators::NearZeroUlpComparator) to
+/// [compare_matrices](crate::compare_matrices).
 /// The error in many mathematical functions can often be bounded by a certain number of ULP, and so
 /// this comparator is particularly useful if this number is known.
 ///
 /// Note that the scalar type of the matrix must implement the [Ulp trait](crate::ulp::Ulp) in order
 /// to be used with this comparator. By default, `f32` and `f64` implementations are provided.
 ///
+/// ### The `le` and `ge` comparators
+/// Rather than checking for equality, these comparators check that `X` is an elementwise upper
+/// (`le`) or lower (`ge`) bound for `Y`, up to the given tolerance. Specifically, for every pair
+/// of elements x and y, the criteria are defined by
+///
+/// ```text
+///     x <= y + tol    (le)
+///     x >= y - tol    (ge)
+/// ```
+///
+/// This is useful for testing monotonicity properties. If the bound is violated, the amount by which
+/// it was violated is reported. As with the `abs` comparator, the comparison never subtracts a larger
+/// number from a smaller one, so unsigned integers are supported.
+///
+/// ### The `decimal` comparator
+/// Checks that the elements are equal to the given number of decimal places, using the same
+/// criterion as `numpy.testing.assert_array_almost_equal`. For every pair of elements x and y,
+/// the criterion is defined by
+///
+/// ```text
+///     | x - y | < 1.5 * 10^(-places).
+/// ```
+///
+/// This is mainly useful for porting tests from NumPy. See
+/// [DecimalPlacesComparator](crate::comparators::DecimalPlacesComparator) for details.
+///
+/// ### Custom comparators
+/// Instead of one of the keywords above, `comp` also accepts any value implementing
+/// [ElementwiseComparator](crate::comparators::ElementwiseComparator). This is useful if the
+/// comparator is only known at runtime, for example if the tolerance depends on the input:
+///
+/// ```
+/// # use matrixcompare::assert_matrix_eq; use matrixcompare_mock::mock_matrix;
+/// # use matrixcompare::comparators::AbsoluteElementwiseComparator;
+/// let x = mock_matrix![100.0, 200.0];
+/// let y = mock_matrix![100.1, 199.9];
+/// let comparator = AbsoluteElementwiseComparator { tol: 1e-3 * 200.0 };
+/// assert_matrix_eq!(x, y, comp = comparator);
+/// ```
+///
+/// The comparator is borrowed, so it can be reused in subsequent assertions. Since the keywords
+/// take precedence, a variable that has the same name as one of the keywords, such as `exact`,
+/// must be wrapped in parentheses, as in `comp = (exact)`.
+///
+/// ### The `approx` comparator
+/// Delegates the comparison to the `RelativeEq` trait of the
+/// [approx](https://crates.io/crates/approx) crate, so that types which already implement
+/// the traits of `approx` can be compared directly. The criterion is that of
+/// `approx::relative_eq!`, with the default tolerances of the element type unless they are
+/// overridden by the optional `epsilon` and `max_relative` parameters:
+///
+/// ```ignore
+/// assert_matrix_eq!(x, y, comp = approx);
+/// assert_matrix_eq!(x, y, comp = approx, epsilon = 1e-12, max_relative = 1e-8);
+/// ```
+///
+/// As with the `float` comparator, the parameters can be given in any order, and do not both
+/// need to be present. Requires the `approx` feature. See
+/// [ApproxComparator](crate::comparators::ApproxComparator) for details.
+///
+/// ### The `percent` comparator
+/// Compares the relative difference between individual elements against a tolerance given
+/// in percent. With `tol = 5.0`, for every pair of elements x and y, the criterion is
+/// defined by
+///
+/// ```text
+///     | x - y | <= 0.05 * max(|x|, |y|).
+/// ```
+///
+/// In other words, the tolerance is divided by 100 and passed on to
+/// [RelativeElementwiseComparator](crate::comparators::RelativeElementwiseComparator),
+/// which also describes the treatment of zeros, infinities and NaN. The criterion is relative
+/// to the larger of the two magnitudes, so it is symmetric in x and y. The tolerance must be
+/// a non-negative floating-point number of the same type as the elements.
+///
+/// ### The `ratio` comparator
+/// Compares the ratio of individual elements against one. For every pair of elements x and y,
+/// the criterion is defined by
+///
+/// ```text
+///     | x / y - 1 | <= tol.
+/// ```
+///
+/// On failure, the ratio `x / y` is reported. Unlike the `percent` comparator, the criterion is
+/// directed: `y` is the reference, so swapping the matrices may change the outcome. If `y` is
+/// zero and `x` is not, the ratio is infinite and the comparison fails regardless of the
+/// tolerance. See [RatioComparator](crate::comparators::RatioComparator) for the treatment of
+/// other special values.
+///
 /// # Error reporting
 ///
 /// One of the main motivations for the `assert_matrix_eq!` macro is the ability to give
@@ -220,7 +503,7 @@ Please see the documentation for ways to compare matrices approximately.\n",
 /// which yields the output
 ///
 /// ```text
-/// Matrices X and Y have 2 mismatched element pairs.
+/// Matrices X (left) and Y (right) have 2 mismatched element pairs out of 4 (50%).
 /// The mismatched elements are listed below, in the format
 /// (row, col): x = X[[row, col]], y = Y[[row, col]].
 ///
@@ -259,10 +542,117 @@ Please see the documentation for ways to compare matrices approximately.\n",
 #[macro_export]
 macro_rules! assert_matrix_eq {
     ($($args:tt)*) => {
-        $crate::base_matrix_eq!(|msg| panic!("{}", msg), $($args)*);
+        $crate::base_matrix_eq_args!(|msg| panic!("{}", msg), $($args)*);
+    };
+}
+
+/// Asserts that two matrices compare equal, and returns a report of how close they are.
+///
+/// Accepts the same arguments as [assert_matrix_eq!] and panics under the same conditions.
+/// On success, returns a [ComparisonReport](crate::ComparisonReport) obtained from
+/// [analyze_matrices](crate::analyze_matrices), which holds the largest absolute difference
+/// between the matrices. This is useful for recording how close the match actually was,
+/// for example to track numerical drift over time.
+///
+/// The matrix expressions are only evaluated once.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::assert_matrix_approx_eq_with_report; use matrixcompare_mock::mock_matrix;
+/// let x = mock_matrix![1.000, 2.000;
+///                      3.000, 4.000f64];
+/// let y = mock_matrix![1.000, 2.000;
+///                      3.000, 4.001f64];
+///
+/// let report = assert_matrix_approx_eq_with_report!(x, y, comp = abs, tol = 0.01);
+/// assert_eq!(report.max_abs_diff_coord, Some((1, 1)));
+/// println!("{}", report);
+/// ```
+#[macro_export]
+macro_rules! assert_matrix_approx_eq_with_report {
+    ($x:expr, $y:expr $(, $($args:tt)*)?) => {
+        {
+            let (x, y) = (&$x, &$y);
+            $crate::base_matrix_eq_args!(|msg| panic!("{}", msg), x, y $(, $($args)*)?);
+            match $crate::analyze_matrices(x, y) {
+                Ok(report) => report,
+                Err(failure) => panic!("{}\n", failure),
+            }
+        }
+    };
+}
+
+/// Compare a matrix against an explicit list of expected `(row, col, value)` triplets.
+///
+/// The triplets describe a sparse matrix with the same dimensions as the given matrix, in which
+/// every unlisted coordinate is implicitly zero. Apart from the list of triplets taking the place
+/// of the second matrix, the macro accepts the same arguments as [assert_matrix_eq!]. Duplicate
+/// or out-of-bounds triplets are reported as for any other sparse matrix.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::assert_matrix_eq_triplets; use matrixcompare_mock::mock_matrix;
+/// let x = mock_matrix![1.0, 0.0, 0.0;
+///                      0.0, 0.0, 4.0];
+///
+/// assert_matrix_eq_triplets!(x, [(0, 0, 1.0), (1, 2, 4.0)]);
+/// assert_matrix_eq_triplets!(x, [(0, 0, 1.0), (1, 2, 4.000001)], comp = abs, tol = 1e-5);
+/// ```
+#[macro_export]
+macro_rules! assert_matrix_eq_triplets {
+    ($x:expr, [$($triplet:expr),* $(,)?] $(, $($args:tt)*)?) => {
+        {
+            let x = &$x;
+            let y = $crate::__private::TripletMatrix::with_dims_of(
+                x,
+                $crate::__private::vec![$($triplet),*]
+            );
+            $crate::assert_matrix_eq!(x, y $(, $($args)*)?);
+        }
     };
 }
 
+/// Asserts that every element of a floating-point matrix is finite.
+///
+/// Panics if the matrix contains NaN or infinite elements, listing the coordinates and kinds of
+/// the non-finite elements. This is a useful sanity check before comparing matrices, since NaN
+/// and infinity often propagate through a computation. See
+/// [compare_matrix_finite](crate::compare_matrix_finite) for details.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::assert_matrix_finite; use matrixcompare_mock::mock_matrix;
+/// let m = mock_matrix![1.0, 2.0;
+///                      3.0, 4.0];
+/// assert_matrix_finite!(m);
+/// ```
+///
+/// ```should_panic
+/// # use matrixcompare::assert_matrix_finite; use matrixcompare_mock::mock_matrix;
+/// let m = mock_matrix![1.0, f64::INFINITY;
+///                      3.0, f64::NAN];
+/// // Panics with the message
+/// //     Matrix `m` has 2 non-finite elements.
+/// //      (0, 1): +inf
+/// //      (1, 1): NaN
+/// assert_matrix_finite!(m);
+/// ```
+#[macro_export]
+macro_rules! assert_matrix_finite {
+    ($m:expr) => {{
+        let label = $crate::__private::format!("`{}`", stringify!($m));
+        if let Err(elements) = $crate::compare_matrix_finite(&$m) {
+            panic!(
+                "{}\n",
+                $crate::__private::format_non_finite_elements(&label, &elements)
+            );
+        }
+    }};
+}
+
 /// Internal macro used for providing consistent macro arguments across several scalar comparison
 /// macros.
 #[doc(hidden)]
@@ -272,11 +662,11 @@ macro_rules! base_scalar_eq {
         {
             use $crate::{compare_scalars};
             use $crate::comparators::ExactElementwiseComparator;
-            use std::borrow::Borrow;
+            use core::borrow::Borrow;
             let comp = ExactElementwiseComparator;
-            let result = compare_scalars($x.borrow(), $y.borrow(), comp);
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
             if let Err(error) = result {
-                let message = format!("{}\n
+                let message = $crate::__private::format!("{}\n
 Please see the documentation for ways to compare scalars approximately.\n",
                     error);
                 return $failure_handler(message);
@@ -287,11 +677,11 @@ Please see the documentation for ways to compare scalars approximately.\n",
         {
             use $crate::{compare_scalars};
             use $crate::comparators::ExactElementwiseComparator;
-            use std::borrow::Borrow;
+            use core::borrow::Borrow;
             let comp = ExactElementwiseComparator;
-            let result = compare_scalars($x.borrow(), $y.borrow(), comp);
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
             if let Err(error) = result {
-                let message = format!("{}\n", error);
+                let message = $crate::__private::format!("{}\n", error);
                 return $failure_handler(message);
             }
         }
@@ -300,11 +690,11 @@ Please see the documentation for ways to compare scalars approximately.\n",
         {
             use $crate::{compare_scalars};
             use $crate::comparators::AbsoluteElementwiseComparator;
-            use std::borrow::Borrow;
+            use core::borrow::Borrow;
             let comp = AbsoluteElementwiseComparator { tol: $tol.clone() };
-            let result = compare_scalars($x.borrow(), $y.borrow(), comp);
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
             if let Err(error) = result {
-                let message = format!("{}\n", error);
+                let message = $crate::__private::format!("{}\n", error);
                 return $failure_handler(message);
             }
         }
@@ -313,11 +703,76 @@ Please see the documentation for ways to compare scalars approximately.\n",
         {
             use $crate::{compare_scalars};
             use $crate::comparators::UlpElementwiseComparator;
-            use std::borrow::Borrow;
+            use core::borrow::Borrow;
             let comp = UlpElementwiseComparator { tol: $tol.clone() };
-            let result = compare_scalars($x.borrow(), $y.borrow(), comp);
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = le, tol = $tol:expr) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::LessEqualElementwiseComparator;
+            use core::borrow::Borrow;
+            let comp = LessEqualElementwiseComparator { tol: $tol.clone() };
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = ge, tol = $tol:expr) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::GreaterEqualElementwiseComparator;
+            use core::borrow::Borrow;
+            let comp = GreaterEqualElementwiseComparator { tol: $tol.clone() };
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
             if let Err(error) = result {
-                let message = format!("{}\n", error);
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = percent, tol = $tol:expr) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::RelativeElementwiseComparator;
+            use core::borrow::Borrow;
+            let comp = RelativeElementwiseComparator { tol: $tol.clone() / 100.0 };
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = ratio, tol = $tol:expr) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::RatioComparator;
+            use core::borrow::Borrow;
+            let comp = RatioComparator { tol: $tol.clone() };
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = decimal, places = $places:expr) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::DecimalPlacesComparator;
+            use core::borrow::Borrow;
+            let comp = DecimalPlacesComparator { decimals: $places };
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
                 return $failure_handler(message);
             }
         }
@@ -326,11 +781,11 @@ Please see the documentation for ways to compare scalars approximately.\n",
         {
             use $crate::{compare_scalars};
             use $crate::comparators::FloatElementwiseComparator;
-            use std::borrow::Borrow;
+            use core::borrow::Borrow;
             let comp = FloatElementwiseComparator::default();
-            let result = compare_scalars($x.borrow(), $y.borrow(), comp);
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
             if let Err(error) = result {
-                let message = format!("{}\n", error);
+                let message = $crate::__private::format!("{}\n", error);
                 return $failure_handler(message);
             }
         }
@@ -341,11 +796,50 @@ Please see the documentation for ways to compare scalars approximately.\n",
         {
             use $crate::{compare_scalars};
             use $crate::comparators::FloatElementwiseComparator;
-            use std::borrow::Borrow;
+            use core::borrow::Borrow;
             let comp = FloatElementwiseComparator::default()$(.$key($val))+;
-            let result = compare_scalars($x.borrow(), $y.borrow(), comp);
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
             if let Err(error) = result {
-                let message = format!("{}\n", error);
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = approx) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::ApproxComparator;
+            use core::borrow::Borrow;
+            let comp = ApproxComparator::default();
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    // Optionally tweak the epsilon and max_relative tolerances passed on to approx
+    ($failure_handler:expr, $x:expr, $y:expr, comp = approx, $($key:ident = $val:expr),+) => {
+        {
+            use $crate::{compare_scalars};
+            use $crate::comparators::ApproxComparator;
+            use core::borrow::Borrow;
+            let comp = ApproxComparator::default()$(.$key($val))+;
+            let result = compare_scalars($x.borrow(), $y.borrow(), &comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
+                return $failure_handler(message);
+            }
+        }
+    };
+    // Any comparator value. This must come last, so that the keywords above take precedence.
+    ($failure_handler:expr, $x:expr, $y:expr, comp = $comp:expr) => {
+        {
+            use $crate::{compare_scalars};
+            use core::borrow::Borrow;
+            let result = compare_scalars($x.borrow(), $y.borrow(), &$comp);
+            if let Err(error) = result {
+                let message = $crate::__private::format!("{}\n", error);
                 return $failure_handler(message);
             }
         }
@@ -376,3 +870,166 @@ macro_rules! assert_scalar_eq {
         $crate::base_scalar_eq!(|msg| panic!("{}", msg), $($args)*);
     };
 }
+
+/// Compare scalars, and assert that they do *not* compare equal.
+///
+/// Takes the same arguments as [assert_scalar_eq!], but panics if the scalars compare equal
+/// under the chosen comparison criterion. This is useful for negative tests, for example
+/// to check that a tolerance is not so loose that it accepts values that should be rejected.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::{assert_scalar_ne};
+/// let x = 3.00;
+/// let y = 3.05;
+/// // Assert that |x - y| > 0.01
+/// assert_scalar_ne!(x, y, comp = abs, tol = 0.01);
+/// ```
+#[macro_export]
+macro_rules! assert_scalar_ne {
+    ($x:expr, $y:expr $(, $($args:tt)*)?) => {
+        {
+            let compare = || -> ::core::result::Result<(), ()> {
+                $crate::base_scalar_eq!(|_| ::core::result::Result::Err(()), $x, $y $(, $($args)*)?);
+                ::core::result::Result::Ok(())
+            };
+            if compare().is_ok() {
+                panic!("Scalars `{x}` and `{y}` compare equal, but were expected not to.\n",
+                    x = stringify!($x),
+                    y = stringify!($y));
+            }
+        }
+    };
+}
+
+/// Internal macro used for providing consistent macro arguments across several slice comparison
+/// macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! base_slice_eq {
+    ($failure_handler:expr, $x:expr, $y:expr) => {
+        {
+            use $crate::{compare_slices};
+            use $crate::comparators::ExactElementwiseComparator;
+            let comp = ExactElementwiseComparator;
+            let result = compare_slices(&$x[..], &$y[..], &comp);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n
+Please see the documentation for ways to compare slices approximately.\n",
+                    failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+    // The keyword arms below only construct the comparator, binding each tolerance exactly once,
+    // and defer the comparison itself to the final arm.
+    ($failure_handler:expr, $x:expr, $y:expr, comp = exact) => {
+        $crate::base_slice_eq!($failure_handler, $x, $y,
+            comp = $crate::comparators::ExactElementwiseComparator)
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = abs, tol = $tol:expr) => {
+        {
+            let tol = $tol;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::AbsoluteElementwiseComparator { tol })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = ulp, tol = $tol:expr) => {
+        {
+            let tol = $tol;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::UlpElementwiseComparator { tol })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = le, tol = $tol:expr) => {
+        {
+            let tol = $tol;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::LessEqualElementwiseComparator { tol })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = ge, tol = $tol:expr) => {
+        {
+            let tol = $tol;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::GreaterEqualElementwiseComparator { tol })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = percent, tol = $tol:expr) => {
+        {
+            let tol = $tol / 100.0;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::RelativeElementwiseComparator { tol })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = ratio, tol = $tol:expr) => {
+        {
+            let tol = $tol;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::RatioComparator { tol })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = decimal, places = $places:expr) => {
+        {
+            let decimals = $places;
+            $crate::base_slice_eq!($failure_handler, $x, $y,
+                comp = $crate::comparators::DecimalPlacesComparator { decimals })
+        }
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = float) => {
+        $crate::base_slice_eq!($failure_handler, $x, $y,
+            comp = $crate::comparators::FloatElementwiseComparator::default())
+    };
+    // The following allows us to optionally tweak the epsilon and ulp tolerances
+    // used in the default float comparator.
+    ($failure_handler:expr, $x:expr, $y:expr, comp = float, $($key:ident = $val:expr),+) => {
+        $crate::base_slice_eq!($failure_handler, $x, $y,
+            comp = $crate::comparators::FloatElementwiseComparator::default()$(.$key($val))+)
+    };
+    ($failure_handler:expr, $x:expr, $y:expr, comp = approx) => {
+        $crate::base_slice_eq!($failure_handler, $x, $y,
+            comp = $crate::comparators::ApproxComparator::default())
+    };
+    // Optionally tweak the epsilon and max_relative tolerances passed on to approx
+    ($failure_handler:expr, $x:expr, $y:expr, comp = approx, $($key:ident = $val:expr),+) => {
+        $crate::base_slice_eq!($failure_handler, $x, $y,
+            comp = $crate::comparators::ApproxComparator::default()$(.$key($val))+)
+    };
+    // Any comparator value. This must come last, so that the keywords above take precedence.
+    ($failure_handler:expr, $x:expr, $y:expr, comp = $comp:expr) => {
+        {
+            use $crate::{compare_slices};
+            let comp = $comp;
+            let result = compare_slices(&$x[..], &$y[..], &comp);
+            if let Err(failure) = result {
+                let message = $crate::__private::format!("{}\n", failure);
+                return $failure_handler(message);
+            }
+        }
+    };
+}
+
+/// Compare slices of scalars elementwise for exact or approximate equality.
+///
+/// This macro works analogously to [assert_scalar_eq!], and accepts the same comparison
+/// arguments, but compares two slices element by element. Any expression that can be indexed
+/// by a full range `[..]`, such as a `Vec`, an array or a slice, is accepted. The macro panics
+/// if the slices have different lengths, or if any pair of elements does not compare equal,
+/// in which case the indices of all mismatched elements are listed.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::assert_slice_eq;
+/// let x = vec![1.00, 2.00, 3.00];
+/// let y = vec![1.00, 2.01, 3.00];
+/// // Assert that |x[i] - y[i]| <= 0.1 for every i
+/// assert_slice_eq!(x, y, comp = abs, tol = 0.1);
+/// ```
+#[macro_export]
+macro_rules! assert_slice_eq {
+    ($($args:tt)*) => {
+        $crate::base_slice_eq!(|msg| panic!("{}", msg), $($args)*);
+    };
+}
@@ -1,43 +1,335 @@
-use crate::comparators::ElementwiseComparator;
+use crate::collections::{HashMap, HashSet};
+use crate::comparators::{
+    DynComparator, DynComparatorAdaptor, DynError, ElementwiseComparator, F32ReferenceError,
+    F32ReferenceUlpComparator,
+};
 use crate::{
-    Access, Coordinate, DenseAccess, DimensionMismatch, ElementsMismatch, Matrix,
-    MatrixComparisonFailure, MatrixElementComparisonFailure, SparseAccess,
+    Access, AccessFailure, ComparisonOptions, CompressedStorageError, Coordinate, CscAccess,
+    CsrAccess, DenseAccess, DimensionMismatch, DisplayOptions, ElementsMismatch,
+    LabeledComparisonFailure, MalformedCompressedStorage, Matrix, MatrixComparisonFailure,
+    MatrixElementComparisonFailure, OutOfBoundsEntries, PatternMismatch, RowPermutationMismatch,
+    SparseAccess, SpecialValueKind, Transpose, TryDenseAccess,
 };
+#[cfg(feature = "std")]
+use crate::{MalformedMatrix, Norm, NormMismatch};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::convert::Infallible;
+use core::fmt::Display;
+use core::marker::PhantomData;
+use core::ops::{Mul, Sub};
+use num_traits::float::FloatCore;
+#[cfg(feature = "std")]
+use num_traits::Float;
 use num_traits::Zero;
-use std::collections::{HashMap, HashSet};
 
+use crate::report::ComparisonReport;
 use crate::Entry;
 
+type SparseHashMap<T> = HashMap<(usize, usize), T>;
+
 enum HashMapBuildError {
-    OutOfBoundsCoord(Coordinate),
+    /// All out-of-bounds coordinates, sorted in lexicographical order.
+    OutOfBoundsCoords(Vec<Coordinate>),
     DuplicateCoord(Coordinate),
+    MalformedStorage(CompressedStorageError),
+}
+
+impl HashMapBuildError {
+    fn into_failure<T, E>(
+        self,
+        make_entry: impl Fn(Coordinate) -> Entry,
+    ) -> MatrixComparisonFailure<T, E> {
+        match self {
+            HashMapBuildError::OutOfBoundsCoords(coords) => {
+                OutOfBoundsEntries::from_entries(coords.into_iter().map(make_entry)).into_failure()
+            }
+            HashMapBuildError::DuplicateCoord(coord) => {
+                MatrixComparisonFailure::DuplicateSparseEntry(make_entry(coord))
+            }
+            HashMapBuildError::MalformedStorage(error) => {
+                // The side of the matrix is the same for any coordinate
+                let is_left = matches!(make_entry((0, 0)), Entry::Left(_));
+                MatrixComparisonFailure::MalformedCompressedStorage(MalformedCompressedStorage {
+                    is_left,
+                    error,
+                })
+            }
+        }
+    }
+}
+
+/// Combines the hash maps built for the left and right matrices.
+///
+/// If both matrices have out-of-bounds entries, all of them are reported together. Otherwise,
+/// errors in the left matrix take precedence.
+fn combine_hash_maps<T, E>(
+    left: Result<SparseHashMap<T>, HashMapBuildError>,
+    right: Result<SparseHashMap<T>, HashMapBuildError>,
+) -> Result<(SparseHashMap<T>, SparseHashMap<T>), MatrixComparisonFailure<T, E>> {
+    match (left, right) {
+        (Ok(left), Ok(right)) => Ok((left, right)),
+        (
+            Err(HashMapBuildError::OutOfBoundsCoords(left)),
+            Err(HashMapBuildError::OutOfBoundsCoords(right)),
+        ) => Err(OutOfBoundsEntries { left, right }.into_failure()),
+        (Err(build_error), _) => Err(build_error.into_failure(Entry::Left)),
+        (_, Err(build_error)) => Err(build_error.into_failure(Entry::Right)),
+    }
 }
 
+/// Builds a hash map from the given triplets, after validating their coordinates.
+///
+/// Coordinates are only compared against the dimensions, and no arithmetic is performed on them,
+/// so arbitrarily large coordinates such as `usize::MAX` are reported as out of bounds.
+/// The returned hash map only contains valid coordinates, which may then be used freely.
+///
+/// All triplets are visited before an error is returned. If the smallest offending coordinate in
+/// lexicographical order is out of bounds, all out-of-bounds coordinates are reported. Otherwise,
+/// the smallest duplicate coordinate is reported. This way, the reported error does not depend
+/// on the order of the triplets.
 fn try_build_sparse_hash_map<T>(
     rows: usize,
     cols: usize,
-    triplets: &[(usize, usize, T)],
-) -> Result<HashMap<(usize, usize), T>, HashMapBuildError>
+    triplets: impl IntoIterator<Item = (usize, usize, T)>,
+) -> Result<HashMap<(usize, usize), T>, HashMapBuildError> {
+    let mut matrix = HashMap::new();
+    let mut out_of_bounds = Vec::new();
+    let mut first_duplicate: Option<Coordinate> = None;
+
+    for (i, j, v) in triplets {
+        if i >= rows || j >= cols {
+            out_of_bounds.push((i, j));
+        } else if matrix.insert((i, j), v).is_some()
+            && first_duplicate.map(|first| (i, j) < first).unwrap_or(true)
+        {
+            first_duplicate = Some((i, j));
+        }
+    }
+
+    out_of_bounds.sort_unstable();
+    match (out_of_bounds.first(), first_duplicate) {
+        (Some(&first_out_of_bounds), Some(duplicate)) if duplicate < first_out_of_bounds => {
+            Err(HashMapBuildError::DuplicateCoord(duplicate))
+        }
+        (Some(_), _) => Err(HashMapBuildError::OutOfBoundsCoords(out_of_bounds)),
+        (None, Some(duplicate)) => Err(HashMapBuildError::DuplicateCoord(duplicate)),
+        (None, None) => Ok(matrix),
+    }
+}
+
+/// Checks that the offsets and indices of a matrix in a compressed (CSR or CSC) format are
+/// consistent, so that its entries can be iterated.
+fn validate_compressed<T>(
+    major_dim: usize,
+    offsets: &[usize],
+    minor_indices: &[usize],
+    values: &[T],
+) -> Result<(), CompressedStorageError> {
+    if offsets.len() != major_dim + 1 {
+        return Err(CompressedStorageError::OffsetCount {
+            expected: major_dim + 1,
+            actual: offsets.len(),
+        });
+    }
+    if let Some(position) = offsets.windows(2).position(|w| w[0] > w[1]) {
+        return Err(CompressedStorageError::DecreasingOffsets {
+            position: position + 1,
+        });
+    }
+    let nnz = offsets[major_dim];
+    if minor_indices.len() != nnz || values.len() != nnz {
+        return Err(CompressedStorageError::LengthMismatch {
+            nnz,
+            indices: minor_indices.len(),
+            values: values.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Iterates over the entries of a matrix in a compressed (CSR or CSC) format.
+///
+/// The entries are returned as (major, minor, value) triplets, where (major, minor) corresponds to
+/// (row, col) for CSR and (col, row) for CSC. Malformed storage is reported by
+/// [validate_compressed], so any entries that cannot be located are skipped here rather than
+/// causing a panic.
+fn compressed_entries<'a, T>(
+    major_dim: usize,
+    offsets: &'a [usize],
+    minor_indices: &'a [usize],
+    values: &'a [T],
+) -> impl Iterator<Item = (usize, usize, T)> + 'a
 where
     T: Clone,
 {
-    let mut matrix = HashMap::new();
+    (0..major_dim).flat_map(move |major| {
+        let range = match offsets.get(major..major + 2) {
+            Some(&[start, end]) if start <= end => start..end,
+            _ => 0..0,
+        };
+        let minor_indices = minor_indices.get(range.clone()).unwrap_or(&[]);
+        let values = values.get(range).unwrap_or(&[]);
+        minor_indices
+            .iter()
+            .zip(values)
+            .map(move |(minor, v)| (major, *minor, v.clone()))
+    })
+}
 
-    for (i, j, v) in triplets.iter().cloned() {
-        if i >= rows || j >= cols {
-            return Err(HashMapBuildError::OutOfBoundsCoord((i, j)));
-        } else if matrix.insert((i, j), v).is_some() {
-            return Err(HashMapBuildError::DuplicateCoord((i, j)));
+/// Access to a sparse matrix in any of the supported sparse formats.
+#[derive(Copy, Clone)]
+pub(crate) enum SparseStorage<'a, T> {
+    Coo(&'a dyn SparseAccess<T>),
+    Csr(&'a dyn CsrAccess<T>),
+    Csc(&'a dyn CscAccess<T>),
+}
+
+impl<'a, T> SparseStorage<'a, T>
+where
+    T: Clone,
+{
+    fn rows(&self) -> usize {
+        match self {
+            Self::Coo(access) => access.rows(),
+            Self::Csr(access) => access.rows(),
+            Self::Csc(access) => access.rows(),
+        }
+    }
+
+    fn cols(&self) -> usize {
+        match self {
+            Self::Coo(access) => access.cols(),
+            Self::Csr(access) => access.cols(),
+            Self::Csc(access) => access.cols(),
+        }
+    }
+
+    /// The number of explicitly stored entries, as reported by the matrix.
+    fn nnz(&self) -> usize {
+        match self {
+            Self::Coo(access) => access.nnz(),
+            Self::Csr(access) => access.values().len(),
+            Self::Csc(access) => access.values().len(),
+        }
+    }
+
+    /// Iterates over the explicitly stored entries as (row, col, value) triplets.
+    pub(crate) fn entries(&self) -> Box<dyn Iterator<Item = (usize, usize, T)> + 'a> {
+        match *self {
+            Self::Coo(access) => match access.fetch_triplets_cow() {
+                Cow::Borrowed(triplets) => Box::new(triplets.iter().cloned()),
+                Cow::Owned(triplets) => Box::new(triplets.into_iter()),
+            },
+            Self::Csr(access) => Box::new(compressed_entries(
+                access.rows(),
+                access.row_offsets(),
+                access.column_indices(),
+                access.values(),
+            )),
+            Self::Csc(access) => Box::new(
+                compressed_entries(
+                    access.cols(),
+                    access.col_offsets(),
+                    access.row_indices(),
+                    access.values(),
+                )
+                .map(|(j, i, v)| (i, j, v)),
+            ),
+        }
+    }
+
+    /// Checks the structure of compressed storage. Coordinate storage is always well-formed.
+    fn validate(&self) -> Result<(), CompressedStorageError> {
+        match *self {
+            Self::Coo(_) => Ok(()),
+            Self::Csr(access) => validate_compressed(
+                access.rows(),
+                access.row_offsets(),
+                access.column_indices(),
+                access.values(),
+            ),
+            Self::Csc(access) => validate_compressed(
+                access.cols(),
+                access.col_offsets(),
+                access.row_indices(),
+                access.values(),
+            ),
+        }
+    }
+
+    fn try_build_hash_map(&self) -> Result<HashMap<(usize, usize), T>, HashMapBuildError> {
+        self.validate()
+            .map_err(HashMapBuildError::MalformedStorage)?;
+        try_build_sparse_hash_map(self.rows(), self.cols(), self.entries())
+    }
+}
+
+/// Collects mismatched elements, optionally stopping once a given number has been collected.
+struct MismatchCollector<T, E> {
+    mismatches: Vec<MatrixElementComparisonFailure<T, E>>,
+    limit: Option<usize>,
+    stopped_early: bool,
+}
+
+impl<T, E> MismatchCollector<T, E> {
+    fn new(options: &ComparisonOptions) -> Self {
+        Self {
+            mismatches: Vec::new(),
+            // We need at least one mismatch in order to report a failure
+            limit: options.early_exit_after.map(|limit| limit.max(1)),
+            stopped_early: false,
+        }
+    }
+
+    /// Adds a mismatch, and returns `true` if the comparison should stop.
+    ///
+    /// Mismatches beyond the limit are not collected, but the first of them stops the comparison,
+    /// so that we only report stopping early if some mismatches were actually left out.
+    fn push(&mut self, mismatch: MatrixElementComparisonFailure<T, E>) -> bool {
+        if self.is_full() {
+            self.stopped_early = true;
+        } else {
+            self.mismatches.push(mismatch);
         }
+        self.stopped_early
+    }
+
+    fn is_full(&self) -> bool {
+        self.limit
+            .map(|limit| self.mismatches.len() >= limit)
+            .unwrap_or(false)
     }
 
-    Ok(matrix)
+    fn into_elements_mismatch<C>(self, comparator: &C) -> Option<ElementsMismatch<T, E>>
+    where
+        C: ElementwiseComparator<T, Error = E>,
+    {
+        if self.mismatches.is_empty() {
+            None
+        } else {
+            Some(ElementsMismatch {
+                comparator_description: comparator.description(),
+                stopped_early: self.stopped_early,
+                mismatches: self.mismatches,
+                compared_rows: None,
+                statistics: None,
+                symmetric: false,
+                dims: None,
+                display: DisplayOptions::default(),
+                nnz: None,
+            })
+        }
+    }
 }
 
 fn compare_sparse_sparse<T, C>(
-    left: &dyn SparseAccess<T>,
-    right: &dyn SparseAccess<T>,
+    left: SparseStorage<T>,
+    right: SparseStorage<T>,
     comparator: &C,
+    options: &ComparisonOptions,
 ) -> Result<(), MatrixComparisonFailure<T, C::Error>>
 where
     T: Zero + Clone,
@@ -46,58 +338,154 @@ where
     // We assume the compatibility of dimensions have been checked by the outer calling function
     assert!(left.rows() == right.rows() && left.cols() == right.cols());
 
-    let left_hash = try_build_sparse_hash_map(left.rows(), left.cols(), &left.fetch_triplets())
-        .map_err(|build_error| match build_error {
-            HashMapBuildError::OutOfBoundsCoord(coord) => {
-                MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Left(coord))
-            }
-            HashMapBuildError::DuplicateCoord(coord) => {
-                MatrixComparisonFailure::DuplicateSparseEntry(Entry::Left(coord))
-            }
-        })?;
+    let (left_hash, right_hash) =
+        combine_hash_maps(left.try_build_hash_map(), right.try_build_hash_map())?;
 
-    let right_hash = try_build_sparse_hash_map(right.rows(), right.cols(), &right.fetch_triplets())
-        .map_err(|build_error| match build_error {
-            HashMapBuildError::OutOfBoundsCoord(coord) => {
-                MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Right(coord))
-            }
-            HashMapBuildError::DuplicateCoord(coord) => {
-                MatrixComparisonFailure::DuplicateSparseEntry(Entry::Right(coord))
+    compare_hash_maps(&left_hash, &right_hash, comparator, options).map_err(
+        |failure| match failure {
+            MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+                mismatch.nnz = Some((left.nnz(), right.nnz()));
+                MatrixComparisonFailure::MismatchedElements(mismatch)
             }
-        })?;
+            failure => failure,
+        },
+    )?;
 
-    let mut mismatches = Vec::new();
+    if options.explicit_zeros_significant {
+        let left_only = explicit_zeros_missing_from(&left_hash, &right_hash);
+        let right_only = explicit_zeros_missing_from(&right_hash, &left_hash);
+        if !left_only.is_empty() || !right_only.is_empty() {
+            return Err(MatrixComparisonFailure::PatternMismatch(PatternMismatch {
+                left_only,
+                right_only,
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Collects the sorted coordinates of the explicitly stored zeros that are not stored
+/// in the other matrix.
+fn explicit_zeros_missing_from<T: Zero>(
+    hash: &HashMap<(usize, usize), T>,
+    other: &HashMap<(usize, usize), T>,
+) -> Vec<Coordinate> {
+    let mut coords: Vec<_> = hash
+        .iter()
+        .filter(|(coord, value)| value.is_zero() && !other.contains_key(*coord))
+        .map(|(coord, _)| *coord)
+        .collect();
+    coords.sort_unstable();
+    coords
+}
+
+/// Compares the entries of two sparse matrices stored in hash maps.
+///
+/// Coordinates that are only present in one of the hash maps are compared against zero.
+fn compare_hash_maps<T, C>(
+    left_hash: &HashMap<(usize, usize), T>,
+    right_hash: &HashMap<(usize, usize), T>,
+    comparator: &C,
+    options: &ComparisonOptions,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
     let left_keys: HashSet<_> = left_hash.keys().collect();
     let right_keys: HashSet<_> = right_hash.keys().collect();
     let zero = T::zero();
 
-    for coord in left_keys.union(&right_keys) {
-        let a = left_hash.get(coord).unwrap_or(&zero);
-        let b = right_hash.get(coord).unwrap_or(&zero);
-        if let Err(error) = comparator.compare(&a, &b) {
-            mismatches.push(MatrixElementComparisonFailure {
+    // Visiting the coordinates in (i, j) order gives us predictable output, independent of e.g.
+    // the order we compare the two matrices, or whether we stop early.
+    let mut coords: Vec<_> = left_keys.union(&right_keys).collect();
+    coords.sort();
+
+    let mut collector = MismatchCollector::new(options);
+    for coord in coords {
+        let a = left_hash.get(*coord).unwrap_or(&zero);
+        let b = right_hash.get(*coord).unwrap_or(&zero);
+        if let Err(error) = comparator.compare(a, b) {
+            let stop = collector.push(MatrixElementComparisonFailure {
                 left: a.clone(),
                 right: b.clone(),
                 error,
                 row: coord.0,
                 col: coord.1,
             });
+            if stop {
+                break;
+            }
         }
     }
 
-    // Sorting the mismatches by (i, j) gives us predictable output, independent of e.g.
-    // the order we compare the two matrices.
-    mismatches.sort_by_key(|mismatch| (mismatch.row, mismatch.col));
+    match collector.into_elements_mismatch(comparator) {
+        Some(mismatch) => Err(MatrixComparisonFailure::MismatchedElements(Box::new(
+            mismatch,
+        ))),
+        None => Ok(()),
+    }
+}
 
-    if mismatches.is_empty() {
-        Ok(())
+/// Attempts to find the mismatches between a dense and a sparse matrix by only visiting the
+/// explicitly stored entries of the sparse matrix.
+///
+/// This is only possible if the dense matrix provides a hint for its number of non-zeros, and
+/// the comparator considers two zeros equal. In that case, if every non-zero element of the dense
+/// matrix is located at one of the sparse coordinates, all remaining elements are zero in both
+/// matrices, and need not be visited. Since the number of visited dense elements is proportional
+/// to the number of non-zeros in the sparse matrix, this avoids a full scan of the dense matrix.
+///
+/// Returns `None` if the fast path is not applicable, in which case a full scan is necessary.
+/// Otherwise, returns all mismatches, sorted by their coordinates, which are then identical to
+/// the mismatches found by a full scan.
+fn try_find_dense_sparse_mismatches_fast<T, C>(
+    dense: &dyn DenseAccess<T>,
+    sparse: &HashMap<(usize, usize), T>,
+    comparator: &C,
+    swap_order: bool,
+) -> Option<Vec<MatrixElementComparisonFailure<T, C::Error>>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let dense_nnz = dense.nonzero_count_hint()?;
+    let zero = T::zero();
+    if comparator.compare(&zero, &zero).is_err() {
+        return None;
+    }
+
+    let mut coords: Vec<_> = sparse.keys().collect();
+    coords.sort();
+
+    let mut mismatches = Vec::new();
+    let mut visited_dense_nnz = 0;
+    for &(i, j) in coords {
+        let a = &dense.fetch_single(i, j);
+        let b = &sparse[&(i, j)];
+        if !a.is_zero() {
+            visited_dense_nnz += 1;
+        }
+        // If the order is swapped, the sparse matrix is the left matrix, so that after the
+        // swap, a and b are always the elements of the left and right matrices, respectively
+        let (a, b) = if swap_order { (b, a) } else { (a, b) };
+        if let Err(error) = comparator.compare(a, b) {
+            mismatches.push(MatrixElementComparisonFailure {
+                left: a.clone(),
+                right: b.clone(),
+                error,
+                row: i,
+                col: j,
+            });
+        }
+    }
+
+    // If some non-zeros of the dense matrix are not covered by the sparse matrix,
+    // we need to fall back to a full scan
+    if visited_dense_nnz == dense_nnz {
+        Some(mismatches)
     } else {
-        Err(MatrixComparisonFailure::MismatchedElements(
-            ElementsMismatch {
-                comparator_description: comparator.description(),
-                mismatches,
-            },
-        ))
+        None
     }
 }
 
@@ -106,6 +494,7 @@ fn find_dense_sparse_mismatches<T, C>(
     sparse: &HashMap<(usize, usize), T>,
     comparator: &C,
     swap_order: bool,
+    options: &ComparisonOptions,
 ) -> Option<ElementsMismatch<T, C::Error>>
 where
     T: Zero + Clone,
@@ -113,41 +502,51 @@ where
 {
     // We assume the compatibility of dimensions have been checked by the outer calling function
 
-    let mut mismatches = Vec::new();
+    let mut collector = MismatchCollector::new(options);
+
+    if let Some(mismatches) =
+        try_find_dense_sparse_mismatches_fast(dense, sparse, comparator, swap_order)
+    {
+        for mismatch in mismatches {
+            if collector.push(mismatch) {
+                break;
+            }
+        }
+        return collector.into_elements_mismatch(comparator);
+    }
+
     let zero = T::zero();
 
-    for i in 0..dense.rows() {
+    'outer: for i in 0..dense.rows() {
         for j in 0..dense.cols() {
             let a = &dense.fetch_single(i, j);
             let b = sparse.get(&(i, j)).unwrap_or(&zero);
+            // As above, a and b are the left and right elements after the swap
             let (a, b) = if swap_order { (b, a) } else { (a, b) };
             if let Err(error) = comparator.compare(a, b) {
-                mismatches.push(MatrixElementComparisonFailure {
+                let stop = collector.push(MatrixElementComparisonFailure {
                     left: a.clone(),
                     right: b.clone(),
                     error,
                     row: i,
                     col: j,
                 });
+                if stop {
+                    break 'outer;
+                }
             }
         }
     }
 
-    if mismatches.is_empty() {
-        None
-    } else {
-        Some(ElementsMismatch {
-            comparator_description: comparator.description(),
-            mismatches,
-        })
-    }
+    collector.into_elements_mismatch(comparator)
 }
 
 fn compare_dense_sparse<T, C>(
     dense: &dyn DenseAccess<T>,
-    sparse: &dyn SparseAccess<T>,
+    sparse: SparseStorage<T>,
     comparator: &C,
     swap_order: bool,
+    options: &ComparisonOptions,
 ) -> Result<(), MatrixComparisonFailure<T, C::Error>>
 where
     T: Zero + Clone,
@@ -156,15 +555,16 @@ where
     // We assume the compatibility of dimensions have been checked by the outer calling function
     assert!(dense.rows() == sparse.rows() && dense.cols() == sparse.cols());
 
-    let triplets = sparse.fetch_triplets();
-
-    let sparse_hash = try_build_sparse_hash_map(sparse.rows(), sparse.cols(), &triplets);
+    let sparse_hash = sparse.try_build_hash_map();
 
     match sparse_hash {
         Ok(y_hash) => {
-            let mismatches = find_dense_sparse_mismatches(dense, &y_hash, comparator, swap_order);
+            let mismatches =
+                find_dense_sparse_mismatches(dense, &y_hash, comparator, swap_order, options);
             if let Some(mismatches) = mismatches {
-                Err(MatrixComparisonFailure::MismatchedElements(mismatches))
+                Err(MatrixComparisonFailure::MismatchedElements(Box::new(
+                    mismatches,
+                )))
             } else {
                 Ok(())
             }
@@ -178,23 +578,32 @@ where
                 }
             };
 
-            use MatrixComparisonFailure::*;
-            match build_error {
-                HashMapBuildError::OutOfBoundsCoord(coord) => {
-                    Err(SparseEntryOutOfBounds(make_entry(coord)))
-                }
-                HashMapBuildError::DuplicateCoord(coord) => {
-                    Err(DuplicateSparseEntry(make_entry(coord)))
-                }
-            }
+            Err(build_error.into_failure(make_entry))
         }
     }
 }
 
+/// Borrows the element if the matrix supports it, so that we only need to clone
+/// elements that are part of a mismatch.
+fn fetch_dense_element<T: Clone>(matrix: &dyn DenseAccess<T>, i: usize, j: usize) -> Cow<'_, T> {
+    match matrix.fetch_ref(i, j) {
+        Some(element) => Cow::Borrowed(element),
+        None => Cow::Owned(matrix.fetch_single(i, j)),
+    }
+}
+
+/// Returns the elements of the matrix as a row-major slice, if the matrix provides one
+/// of the correct length.
+fn row_major_slice<T>(matrix: &dyn DenseAccess<T>) -> Option<&[T]> {
+    let len = matrix.rows().checked_mul(matrix.cols())?;
+    matrix.as_slice().filter(|slice| slice.len() == len)
+}
+
 fn compare_dense_dense<T, C>(
     left: &dyn DenseAccess<T>,
     right: &dyn DenseAccess<T>,
     comparator: &C,
+    options: &ComparisonOptions,
 ) -> Result<(), MatrixComparisonFailure<T, C::Error>>
 where
     T: Clone,
@@ -203,32 +612,160 @@ where
     // We assume the compatibility of dimensions have been checked by the outer calling function
     assert!(left.rows() == right.rows() && left.cols() == right.cols());
 
-    let mut mismatches = Vec::new();
-    for i in 0..left.rows() {
-        for j in 0..left.cols() {
-            let a = left.fetch_single(i, j);
-            let b = right.fetch_single(i, j);
-            if let Err(error) = comparator.compare(&a, &b) {
-                mismatches.push(MatrixElementComparisonFailure {
+    let mut collector = MismatchCollector::new(options);
+    if let (Some(left_data), Some(right_data)) = (row_major_slice(left), row_major_slice(right)) {
+        // Fast path for contiguous storage, which avoids accessing the elements one at a time
+        let cols = left.cols();
+        for (k, (a, b)) in left_data.iter().zip(right_data).enumerate() {
+            if let Err(error) = comparator.compare(a, b) {
+                let stop = collector.push(MatrixElementComparisonFailure {
                     left: a.clone(),
                     right: b.clone(),
                     error,
-                    row: i,
-                    col: j,
+                    row: k / cols,
+                    col: k % cols,
                 });
+                if stop {
+                    break;
+                }
+            }
+        }
+    } else {
+        'outer: for i in 0..left.rows() {
+            for j in 0..left.cols() {
+                let a = fetch_dense_element(left, i, j);
+                let b = fetch_dense_element(right, i, j);
+                if let Err(error) = comparator.compare(&a, &b) {
+                    let stop = collector.push(MatrixElementComparisonFailure {
+                        left: a.into_owned(),
+                        right: b.into_owned(),
+                        error,
+                        row: i,
+                        col: j,
+                    });
+                    if stop {
+                        break 'outer;
+                    }
+                }
             }
         }
     }
 
-    if mismatches.is_empty() {
+    match collector.into_elements_mismatch(comparator) {
+        Some(mismatch) => Err(MatrixComparisonFailure::MismatchedElements(Box::new(
+            mismatch,
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Dense or sparse storage, with all sparse formats collapsed into a single variant.
+pub(crate) enum Storage<'a, T> {
+    Dense(&'a dyn DenseAccess<T>),
+    Sparse(SparseStorage<'a, T>),
+}
+
+impl<'a, T> From<Access<'a, T>> for Storage<'a, T> {
+    fn from(access: Access<'a, T>) -> Self {
+        match access {
+            Access::Dense(access) => Self::Dense(access),
+            Access::Sparse(access) => Self::Sparse(SparseStorage::Coo(access)),
+            Access::Csr(access) => Self::Sparse(SparseStorage::Csr(access)),
+            Access::Csc(access) => Self::Sparse(SparseStorage::Csc(access)),
+        }
+    }
+}
+
+impl<'a, T> Storage<'a, T>
+where
+    T: Clone,
+{
+    /// Checks the structure of compressed sparse storage, so that its entries can be iterated.
+    fn validate(&self, is_left: bool) -> Result<(), MalformedCompressedStorage> {
+        match self {
+            Storage::Dense(_) => Ok(()),
+            Storage::Sparse(access) => access
+                .validate()
+                .map_err(|error| MalformedCompressedStorage { is_left, error }),
+        }
+    }
+}
+
+impl<'a, T> Storage<'a, T> {
+    /// Whether the storage is sparse with at least one explicitly stored entry.
+    ///
+    /// This is determined without fetching the entries.
+    fn has_sparse_entries(&self) -> bool {
+        match *self {
+            Storage::Dense(_) => false,
+            Storage::Sparse(SparseStorage::Coo(access)) => access.nnz() > 0,
+            Storage::Sparse(SparseStorage::Csr(access)) => {
+                !access.values().is_empty() || !access.column_indices().is_empty()
+            }
+            Storage::Sparse(SparseStorage::Csc(access)) => {
+                !access.values().is_empty() || !access.row_indices().is_empty()
+            }
+        }
+    }
+}
+
+/// Checks that two matrices have the same dimensions.
+fn check_dimensions<T>(
+    left: &impl Matrix<T>,
+    right: &impl Matrix<T>,
+) -> Result<(), DimensionMismatch> {
+    let mismatch = DimensionMismatch {
+        dim_left: (left.rows(), left.cols()),
+        dim_right: (right.rows(), right.cols()),
+    };
+    if mismatch.dim_left == mismatch.dim_right {
         Ok(())
     } else {
-        Err(MatrixComparisonFailure::MismatchedElements(
-            ElementsMismatch {
-                comparator_description: comparator.description(),
-                mismatches,
-            },
-        ))
+        Err(mismatch)
+    }
+}
+
+/// Checks the storage of a matrix that is wrapped before comparison.
+///
+/// The wrappers only expose the entries of compressed storage that can be located, so malformed
+/// storage must be reported before wrapping.
+fn validate_wrapped<T: Clone>(
+    matrix: &impl Matrix<T>,
+    is_left: bool,
+) -> Result<(), MalformedCompressedStorage> {
+    Storage::from(matrix.access()).validate(is_left)
+}
+
+/// Returns the smallest coordinate of a NaN element, as determined by the comparator.
+///
+/// Sparse entries outside the bounds of the matrix are ignored, since they are reported
+/// separately by the comparison itself.
+fn find_nan<T, C>(storage: &Storage<T>, comparator: &C) -> Option<Coordinate>
+where
+    T: Clone,
+    C: ElementwiseComparator<T>,
+{
+    match *storage {
+        Storage::Dense(access) => {
+            let cols = access.cols();
+            if let Some(data) = row_major_slice(access) {
+                data.iter()
+                    .position(|x| comparator.is_nan(x))
+                    .map(|k| (k / cols, k % cols))
+            } else {
+                (0..access.rows())
+                    .flat_map(|i| (0..cols).map(move |j| (i, j)))
+                    .find(|&(i, j)| comparator.is_nan(&fetch_dense_element(access, i, j)))
+            }
+        }
+        Storage::Sparse(ref access) => {
+            let (rows, cols) = (access.rows(), access.cols());
+            access
+                .entries()
+                .filter(|(i, j, x)| *i < rows && *j < cols && comparator.is_nan(x))
+                .map(|(i, j, _)| (i, j))
+                .min()
+        }
     }
 }
 
@@ -236,6 +773,26 @@ where
 ///
 /// Most users will only need to use the comparison macro. This function is mainly of use to
 /// users who want to build their own macros.
+///
+/// The matrices are taken by value. Since [Matrix] is also implemented for references to
+/// matrices, they may either be borrowed or passed as temporaries that are consumed by the
+/// comparison. The returned failure owns copies of the mismatched elements, and so does not
+/// borrow from either matrix. Hence temporaries need not be bound to local variables first:
+///
+/// ```
+/// # use matrixcompare::compare_matrices;
+/// # use matrixcompare::comparators::ExactElementwiseComparator;
+/// # use matrixcompare_mock::mock_matrix;
+/// let failure = compare_matrices(mock_matrix![1, 2], mock_matrix![1, 3], &ExactElementwiseComparator)
+///     .unwrap_err();
+/// println!("{}", failure);
+/// ```
+///
+/// With the `self-comparison-warning` feature enabled, debug builds write a warning to the
+/// error output whenever a matrix is compared with itself, as in `assert_matrix_eq!(x, x)`.
+/// Such comparisons always succeed, and are usually a mistake in the test. See the
+/// [crate documentation](crate#detecting-self-comparison) for details.
+#[track_caller]
 pub fn compare_matrices<T, C>(
     left: impl Matrix<T>,
     right: impl Matrix<T>,
@@ -245,31 +802,1508 @@ where
     T: Zero + Clone,
     C: ElementwiseComparator<T>,
 {
-    let shapes_match = left.rows() == right.rows() && left.cols() == right.cols();
-    if shapes_match {
-        use Access::{Dense, Sparse};
-        match (left.access(), right.access()) {
-            (Dense(left_access), Dense(right_access)) => {
-                compare_dense_dense(left_access, right_access, comparator)
-            }
-            (Dense(left_access), Sparse(right_access)) => {
-                let swap = false;
-                compare_dense_sparse(left_access, right_access, comparator, swap)
-            }
-            (Sparse(left_access), Dense(right_access)) => {
-                let swap = true;
-                compare_dense_sparse(right_access, left_access, comparator, swap)
+    compare_matrices_with_options(left, right, comparator, &ComparisonOptions::default())
+}
+
+/// Comparison of two streams of `(row, col, value)` entries.
+///
+/// This is the kernel used to compare sparse matrices, exposed for users who want to compare
+/// entries from custom sources without implementing [Matrix]. Both streams describe matrices of
+/// dimensions `dims`, and coordinates that are only present in one of the streams are compared
+/// against zero. The entries need not be sorted, but each coordinate may only appear once in
+/// each stream, and must be within the given dimensions. Otherwise, the failure is reported as
+/// [MatrixComparisonFailure::SparseEntryOutOfBounds],
+/// [MatrixComparisonFailure::SparseEntriesOutOfBounds] or
+/// [MatrixComparisonFailure::DuplicateSparseEntry].
+///
+/// Mismatches are reported in lexicographical order of their coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::compare_entries;
+/// # use matrixcompare::comparators::ExactElementwiseComparator;
+/// let events = vec![(0, 1, 3), (2, 0, 5)];
+/// let expected = vec![(2, 0, 5), (0, 1, 3), (1, 1, 0)];
+/// assert!(compare_entries(events, expected, (3, 2), &ExactElementwiseComparator).is_ok());
+/// ```
+pub fn compare_entries<T, C>(
+    left: impl IntoIterator<Item = (usize, usize, T)>,
+    right: impl IntoIterator<Item = (usize, usize, T)>,
+    dims: (usize, usize),
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let (rows, cols) = dims;
+    let (left_hash, right_hash) = combine_hash_maps(
+        try_build_sparse_hash_map(rows, cols, left),
+        try_build_sparse_hash_map(rows, cols, right),
+    )?;
+
+    let options = ComparisonOptions::default();
+    compare_hash_maps(&left_hash, &right_hash, comparator, &options).map_err(
+        |failure| match failure {
+            MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+                mismatch.dims = Some(dims);
+                mismatch.nnz = Some((left_hash.len(), right_hash.len()));
+                MatrixComparisonFailure::MismatchedElements(mismatch)
             }
-            (Sparse(left_access), Sparse(right_access)) => {
-                compare_sparse_sparse(left_access, right_access, comparator)
+            failure => failure,
+        },
+    )
+}
+
+/// Fetches an element, reporting a failed access as a failure of the comparison.
+fn try_fetch_element<T, E>(
+    matrix: &dyn TryDenseAccess<T>,
+    i: usize,
+    j: usize,
+    make_entry: fn(Coordinate) -> Entry,
+) -> Result<T, MatrixComparisonFailure<T, E>> {
+    matrix.try_fetch_single(i, j).map_err(|error| {
+        MatrixComparisonFailure::AccessError(AccessFailure {
+            entry: make_entry((i, j)),
+            error,
+        })
+    })
+}
+
+/// Comparison of two dense matrices with fallible access to their elements.
+///
+/// Works as [compare_matrices] for dense matrices, except that elements are fetched through
+/// [TryDenseAccess]. This supports matrices whose elements may fail to load, such as matrices
+/// backed by memory-mapped files. The comparison stops at the first element that cannot be
+/// fetched, and fails with [MatrixComparisonFailure::AccessError]. Since every dense matrix
+/// implements [TryDenseAccess], fallible matrices can be compared with ordinary dense matrices.
+///
+/// The elements are visited in row-major order.
+pub fn compare_matrices_fallible<T, C>(
+    left: &impl TryDenseAccess<T>,
+    right: &impl TryDenseAccess<T>,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    C: ElementwiseComparator<T>,
+{
+    let (rows, cols) = left.dimensions();
+    if (rows, cols) != right.dimensions() {
+        return Err(MatrixComparisonFailure::MismatchedDimensions(
+            DimensionMismatch {
+                dim_left: (rows, cols),
+                dim_right: right.dimensions(),
+            },
+        ));
+    }
+
+    let mut collector = MismatchCollector::new(&ComparisonOptions::default());
+    for i in 0..rows {
+        for j in 0..cols {
+            let a = try_fetch_element(left, i, j, Entry::Left)?;
+            let b = try_fetch_element(right, i, j, Entry::Right)?;
+            if let Err(error) = comparator.compare(&a, &b) {
+                collector.push(MatrixElementComparisonFailure {
+                    left: a,
+                    right: b,
+                    error,
+                    row: i,
+                    col: j,
+                });
             }
         }
-    } else {
-        Err(MatrixComparisonFailure::MismatchedDimensions(
-            DimensionMismatch {
-                dim_left: (left.rows(), left.cols()),
-                dim_right: (right.rows(), right.cols()),
+    }
+
+    match collector.into_elements_mismatch(comparator) {
+        Some(mismatch) => Err(MatrixComparisonFailure::MismatchedElements(Box::new(
+            ElementsMismatch {
+                dims: Some((rows, cols)),
+                ..mismatch
             },
-        ))
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Comparison of two matrices given as trait objects.
+///
+/// Works exactly as [compare_matrices]. This is convenient when matrices of different types
+/// are stored behind trait objects, e.g. in a `Vec<Box<dyn Matrix<T>>>`.
+pub fn compare_matrices_dyn<T, C>(
+    left: &dyn Matrix<T>,
+    right: &dyn Matrix<T>,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    compare_matrices(left, right, comparator)
+}
+
+/// Comparison of two matrices with a comparator given as a trait object.
+///
+/// Works exactly as [compare_matrices], except that the comparator can be selected at runtime.
+/// The errors of the comparator are wrapped in [DynError].
+///
+/// ```
+/// # use matrixcompare::comparators::{
+/// #     AbsoluteElementwiseComparator, DynComparator, UlpElementwiseComparator,
+/// # };
+/// # use matrixcompare::compare_matrices_dyn_comparator; use matrixcompare_mock::mock_matrix;
+/// let kind = "abs"; // E.g. read from a test specification
+/// let comparator: Box<dyn DynComparator<f64>> = match kind {
+///     "abs" => Box::new(AbsoluteElementwiseComparator { tol: 1e-6 }),
+///     _ => Box::new(UlpElementwiseComparator { tol: 4 }),
+/// };
+///
+/// let a = mock_matrix![1.0, 2.0];
+/// let b = mock_matrix![1.0, 2.1];
+/// assert!(compare_matrices_dyn_comparator(&a, &a, comparator.as_ref()).is_ok());
+/// assert!(compare_matrices_dyn_comparator(&a, &b, comparator.as_ref()).is_err());
+/// ```
+pub fn compare_matrices_dyn_comparator<T>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &dyn DynComparator<T>,
+) -> Result<(), MatrixComparisonFailure<T, DynError>>
+where
+    T: Zero + Clone,
+{
+    compare_matrices(left, right, &DynComparatorAdaptor(comparator))
+}
+
+/// Comparison of two matrices, referring to the matrices by the given labels in the output.
+///
+/// Works exactly as [compare_matrices], except that the left and right matrices are referred to
+/// by `labels.0` and `labels.1` instead of X and Y when the failure is displayed.
+#[track_caller]
+pub fn compare_matrices_labeled<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+    labels: (&str, &str),
+) -> Result<(), LabeledComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    compare_matrices(left, right, comparator)
+        .map_err(|failure| failure.with_labels(labels.0, labels.1))
+}
+
+/// Lazy iterator over the mismatched elements of two matrices, see [iter_mismatches].
+enum MismatchIter<'a, L, R, C, T, E> {
+    Dense {
+        left: L,
+        right: R,
+        comparator: &'a C,
+        cols: usize,
+        index: usize,
+        len: usize,
+    },
+    Collected(vec::IntoIter<MatrixElementComparisonFailure<T, E>>),
+}
+
+impl<'a, L, R, C, T> Iterator for MismatchIter<'a, L, R, C, T, C::Error>
+where
+    L: Matrix<T>,
+    R: Matrix<T>,
+    T: Clone,
+    C: ElementwiseComparator<T>,
+{
+    type Item = MatrixElementComparisonFailure<T, C::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            MismatchIter::Dense {
+                left,
+                right,
+                comparator,
+                cols,
+                index,
+                len,
+            } => {
+                let (left, right) = match (left.access(), right.access()) {
+                    (Access::Dense(left), Access::Dense(right)) => (left, right),
+                    _ => panic!("Matrices must keep providing dense access during iteration."),
+                };
+                while *index < *len {
+                    let (i, j) = (*index / *cols, *index % *cols);
+                    *index += 1;
+                    let a = fetch_dense_element(left, i, j);
+                    let b = fetch_dense_element(right, i, j);
+                    if let Err(error) = comparator.compare(&a, &b) {
+                        return Some(MatrixElementComparisonFailure {
+                            left: a.into_owned(),
+                            right: b.into_owned(),
+                            error,
+                            row: i,
+                            col: j,
+                        });
+                    }
+                }
+                None
+            }
+            MismatchIter::Collected(mismatches) => mismatches.next(),
+        }
+    }
+}
+
+/// Iterates over the mismatched elements of two matrices.
+///
+/// If both matrices are dense, the elements are compared lazily in row-major order as the
+/// iterator advances, so that mismatches can be processed without collecting all of them,
+/// for example with `.take(100)`. Otherwise, all mismatches are found up front by
+/// [compare_matrices], and then yielded in the same order as in its failure.
+///
+/// Fails if the dimensions of the matrices do not match, or if a sparse matrix has
+/// out-of-bounds or duplicate entries.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::iter_mismatches;
+/// # use matrixcompare::comparators::ExactElementwiseComparator;
+/// # use matrixcompare_mock::mock_matrix;
+/// let a = mock_matrix![1, 2, 3;
+///                      4, 5, 6];
+/// let b = mock_matrix![1, 0, 3;
+///                      0, 5, 0];
+///
+/// let mismatches = iter_mismatches(&a, &b, &ExactElementwiseComparator).unwrap();
+/// let coords: Vec<_> = mismatches.take(2).map(|m| (m.row, m.col)).collect();
+/// assert_eq!(coords, vec![(0, 1), (1, 0)]);
+/// ```
+pub fn iter_mismatches<'a, T, C>(
+    left: impl Matrix<T> + 'a,
+    right: impl Matrix<T> + 'a,
+    comparator: &'a C,
+) -> Result<
+    impl Iterator<Item = MatrixElementComparisonFailure<T, C::Error>> + 'a,
+    MatrixComparisonFailure<T, C::Error>,
+>
+where
+    T: Zero + Clone + 'a,
+    C: ElementwiseComparator<T>,
+{
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let is_dense = matches!(
+        (left.access(), right.access()),
+        (Access::Dense(_), Access::Dense(_))
+    );
+    if is_dense {
+        let (rows, cols) = (left.rows(), left.cols());
+        Ok(MismatchIter::Dense {
+            left,
+            right,
+            comparator,
+            cols,
+            index: 0,
+            len: rows * cols,
+        })
+    } else {
+        let mismatches = match compare_matrices(&left, &right, comparator) {
+            Ok(()) => Vec::new(),
+            Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch.mismatches,
+            Err(failure) => return Err(failure),
+        };
+        Ok(MismatchIter::Collected(mismatches.into_iter()))
+    }
+}
+
+/// Counts the mismatched elements of two matrices.
+///
+/// Returns zero if the matrices compare equal. If both matrices are dense, this is cheaper than
+/// [compare_matrices] when only the number of mismatches is of interest, for example in a
+/// progress log, since the elements are compared lazily through [iter_mismatches] and no
+/// failure is built. Otherwise, the mismatches are collected by [compare_matrices] and then
+/// counted. Failures that are not about the elements themselves, such as mismatched dimensions
+/// or out-of-bounds sparse entries, are returned as errors, since they have no meaningful count.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::count_mismatches;
+/// # use matrixcompare::comparators::AbsoluteElementwiseComparator;
+/// # use matrixcompare_mock::mock_matrix;
+/// let a = mock_matrix![1.0, 2.0, 3.0];
+/// let b = mock_matrix![1.0, 2.5, 3.5];
+/// let comparator = AbsoluteElementwiseComparator { tol: 0.1 };
+/// assert_eq!(count_mismatches(&a, &b, &comparator), Ok(2));
+/// assert_eq!(count_mismatches(&a, &a, &comparator), Ok(0));
+/// ```
+pub fn count_mismatches<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+) -> Result<usize, MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    iter_mismatches(left, right, comparator).map(Iterator::count)
+}
+
+/// Removes mismatches in the lower triangle whose mirrored counterpart is also a mismatch.
+fn deduplicate_symmetric_mismatches<T, E>(mismatch: &mut ElementsMismatch<T, E>) {
+    let coords: HashSet<_> = mismatch
+        .mismatches
+        .iter()
+        .map(|element| (element.row, element.col))
+        .collect();
+    mismatch.mismatches.retain(|element| {
+        element.row <= element.col || !coords.contains(&(element.col, element.row))
+    });
+    mismatch.symmetric = true;
+}
+
+/// Whether the two matrices are the same object, as determined by the address of the
+/// matrix that provides access to the elements.
+#[cfg(any(test, all(feature = "self-comparison-warning", debug_assertions)))]
+fn is_same_matrix<T>(left: &impl Matrix<T>, right: &impl Matrix<T>) -> bool {
+    fn address<T>(access: Access<'_, T>) -> *const () {
+        match access {
+            Access::Dense(access) => access as *const _ as *const (),
+            Access::Sparse(access) => access as *const _ as *const (),
+            Access::Csr(access) => access as *const _ as *const (),
+            Access::Csc(access) => access as *const _ as *const (),
+        }
+    }
+    address(left.access()) == address(right.access())
+}
+
+/// Writes a warning to the error output if a matrix is compared with itself, which is
+/// usually a mistake in the test, since the comparison cannot fail.
+#[cfg(all(feature = "self-comparison-warning", debug_assertions))]
+#[track_caller]
+fn warn_if_same_matrix<T>(left: &impl Matrix<T>, right: &impl Matrix<T>) {
+    if is_same_matrix(left, right) {
+        eprintln!(
+            "warning: matrix compared with itself at {}. \
+             The comparison always succeeds, so it cannot catch any errors.",
+            core::panic::Location::caller()
+        );
+    }
+}
+
+/// Comparison of two matrices, with additional options.
+///
+/// See [ComparisonOptions] for the available options. With the default options,
+/// this is equivalent to [compare_matrices].
+///
+/// If `options.symmetric` is set and the matrices have matching, but non-square, dimensions,
+/// the comparison fails with [MatrixComparisonFailure::NonSquareDimensions].
+#[track_caller]
+pub fn compare_matrices_with_options<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+    options: &ComparisonOptions,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    #[cfg(all(feature = "self-comparison-warning", debug_assertions))]
+    warn_if_same_matrix(&left, &right);
+
+    if options.symmetric && left.rows() != left.cols() {
+        return Err(MatrixComparisonFailure::NonSquareDimensions(
+            DimensionMismatch {
+                dim_left: (left.rows(), left.cols()),
+                dim_right: (right.rows(), right.cols()),
+            },
+        ));
+    }
+
+    let left_storage = Storage::from(left.access());
+    let right_storage = Storage::from(right.access());
+    left_storage
+        .validate(true)
+        .and_then(|()| right_storage.validate(false))
+        .map_err(MatrixComparisonFailure::MalformedCompressedStorage)?;
+
+    // Matrices without elements are trivially equal, which we can determine without fetching
+    // any elements, unless a sparse matrix stores entries that must be reported as out of bounds
+    let is_empty = left.rows() == 0 || left.cols() == 0;
+    if is_empty && !left_storage.has_sparse_entries() && !right_storage.has_sparse_entries() {
+        return Ok(());
+    }
+
+    #[cfg(feature = "std")]
+    if options.validate_dimensions {
+        let dims = (left.rows(), left.cols());
+        let entry = find_malformed_corner(&left_storage, dims, Entry::Left)
+            .or_else(|| find_malformed_corner(&right_storage, dims, Entry::Right));
+        if let Some(entry) = entry {
+            return Err(MatrixComparisonFailure::MalformedMatrix(MalformedMatrix {
+                entry,
+                dims,
+            }));
+        }
+    }
+
+    if options.reject_nan {
+        let left_nan = find_nan(&left_storage, comparator);
+        let right_nan = find_nan(&right_storage, comparator);
+        let entry = match (left_nan, right_nan) {
+            (Some(l), Some(r)) if r < l => Some(Entry::Right(r)),
+            (Some(l), _) => Some(Entry::Left(l)),
+            (None, Some(r)) => Some(Entry::Right(r)),
+            (None, None) => None,
+        };
+        if let Some(entry) = entry {
+            return Err(MatrixComparisonFailure::NanEncountered(entry));
+        }
+    }
+
+    let result = match (left_storage, right_storage) {
+        (Storage::Dense(left_access), Storage::Dense(right_access)) => {
+            compare_dense_dense(left_access, right_access, comparator, options)
+        }
+        (Storage::Dense(left_access), Storage::Sparse(right_access)) => {
+            let swap = false;
+            compare_dense_sparse(left_access, right_access, comparator, swap, options)
+        }
+        (Storage::Sparse(left_access), Storage::Dense(right_access)) => {
+            let swap = true;
+            compare_dense_sparse(right_access, left_access, comparator, swap, options)
+        }
+        (Storage::Sparse(left_access), Storage::Sparse(right_access)) => {
+            compare_sparse_sparse(left_access, right_access, comparator, options)
+        }
+    };
+
+    result.map_err(|failure| match failure {
+        MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+            if options.symmetric {
+                deduplicate_symmetric_mismatches(&mut mismatch);
+            }
+            mismatch.dims = Some((left.rows(), left.cols()));
+            mismatch.display = DisplayOptions {
+                decimal_places: options.decimal_places,
+                max_reports: options.max_reports,
+                group_by_row: options.group_by_row,
+            };
+            MatrixComparisonFailure::MismatchedElements(mismatch)
+        }
+        failure => failure,
+    })
+}
+
+/// Finds a corner element of a dense matrix that cannot be fetched without panicking.
+///
+/// Sparse matrices are not checked, since their entries are validated during comparison.
+#[cfg(feature = "std")]
+fn find_malformed_corner<T>(
+    storage: &Storage<T>,
+    (rows, cols): (usize, usize),
+    make_entry: fn(Coordinate) -> Entry,
+) -> Option<Entry> {
+    let access = match storage {
+        Storage::Dense(access) => *access,
+        Storage::Sparse(_) => return None,
+    };
+    if rows == 0 || cols == 0 {
+        return None;
+    }
+
+    let corners = [(0, 0), (0, cols - 1), (rows - 1, 0), (rows - 1, cols - 1)];
+    corners
+        .iter()
+        .copied()
+        .find(|&(i, j)| {
+            let fetch = std::panic::AssertUnwindSafe(|| access.fetch_single(i, j));
+            std::panic::catch_unwind(fetch).is_err()
+        })
+        .map(make_entry)
+}
+
+/// Collects the coordinates explicitly stored in a matrix.
+///
+/// Dense matrices are considered to explicitly store every entry.
+fn stored_coordinates<T>(
+    storage: Storage<T>,
+    make_entry: fn(Coordinate) -> Entry,
+) -> Result<HashSet<Coordinate>, MatrixComparisonFailure<T, Infallible>>
+where
+    T: Clone,
+{
+    match storage {
+        Storage::Dense(access) => {
+            let (rows, cols) = (access.rows(), access.cols());
+            Ok((0..rows)
+                .flat_map(|i| (0..cols).map(move |j| (i, j)))
+                .collect())
+        }
+        Storage::Sparse(access) => access
+            .try_build_hash_map()
+            .map(|hash_map| hash_map.into_keys().collect())
+            .map_err(|build_error| build_error.into_failure(make_entry)),
+    }
+}
+
+/// Comparison of the sparsity patterns of two matrices.
+///
+/// Only the coordinates of the explicitly stored entries are compared, and the values of the
+/// entries are ignored. In particular, an explicitly stored zero is part of the pattern, whereas
+/// [compare_matrices] considers it equal to an implicit zero. Dense matrices are considered to
+/// explicitly store every entry.
+///
+/// Returns a [PatternMismatch] failure listing the coordinates that are stored in only
+/// one of the two matrices.
+pub fn compare_sparsity_patterns<T>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+) -> Result<(), MatrixComparisonFailure<T, Infallible>>
+where
+    T: Clone,
+{
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let left_pattern = stored_coordinates(Storage::from(left.access()), Entry::Left)?;
+    let right_pattern = stored_coordinates(Storage::from(right.access()), Entry::Right)?;
+
+    let mut left_only: Vec<_> = left_pattern.difference(&right_pattern).cloned().collect();
+    let mut right_only: Vec<_> = right_pattern.difference(&left_pattern).cloned().collect();
+    left_only.sort_unstable();
+    right_only.sort_unstable();
+
+    if left_only.is_empty() && right_only.is_empty() {
+        Ok(())
+    } else {
+        Err(MatrixComparisonFailure::PatternMismatch(PatternMismatch {
+            left_only,
+            right_only,
+        }))
+    }
+}
+
+/// A `(row, col, value)` entry of a sparse matrix.
+pub type Triplet<T> = (usize, usize, T);
+
+/// How [canonical_triplets] treats entries that are stored more than once.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DuplicateEntries {
+    /// Fail with [MatrixComparisonFailure::DuplicateSparseEntry].
+    Reject,
+    /// Sum the values of all entries with the same coordinate, as is common when assembling
+    /// sparse matrices from triplets.
+    Sum,
+}
+
+/// Returns the triplets of a sparse matrix in canonical form.
+///
+/// The triplets are validated and sorted by `(row, col)`, so that each coordinate appears at
+/// most once. Out-of-bounds entries are reported as for a comparison, with all of them listed
+/// as entries of the left matrix. Duplicate entries are either rejected or summed, as given by
+/// `duplicates`. Explicitly stored zeros are kept, so the canonical triplets describe the
+/// sparsity pattern as well as the values.
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::{canonical_triplets, DuplicateEntries};
+/// # use matrixcompare_mock::MockSparseMatrix;
+/// let m = MockSparseMatrix::from_triplets(2, 2, vec![(1, 0, 3), (0, 1, 1), (1, 0, 2)]);
+/// assert!(canonical_triplets(&m, DuplicateEntries::Reject).is_err());
+/// assert_eq!(
+///     canonical_triplets(&m, DuplicateEntries::Sum),
+///     Ok(vec![(0, 1, 1), (1, 0, 5)])
+/// );
+/// ```
+pub fn canonical_triplets<T>(
+    matrix: &impl SparseAccess<T>,
+    duplicates: DuplicateEntries,
+) -> Result<Vec<Triplet<T>>, MatrixComparisonFailure<T, Infallible>>
+where
+    T: Zero + Clone,
+{
+    let (rows, cols) = (matrix.rows(), matrix.cols());
+    let triplets = matrix.fetch_triplets_cow();
+    let hash_map = match duplicates {
+        DuplicateEntries::Reject => try_build_sparse_hash_map(rows, cols, triplets.iter().cloned())
+            .map_err(|build_error| build_error.into_failure(Entry::Left))?,
+        DuplicateEntries::Sum => {
+            let mut out_of_bounds = Vec::new();
+            let mut hash_map: SparseHashMap<T> = HashMap::new();
+            for (i, j, v) in triplets.iter().cloned() {
+                if i >= rows || j >= cols {
+                    out_of_bounds.push((i, j));
+                } else {
+                    let sum = hash_map.remove(&(i, j)).unwrap_or_else(T::zero) + v;
+                    hash_map.insert((i, j), sum);
+                }
+            }
+            if !out_of_bounds.is_empty() {
+                return Err(OutOfBoundsEntries::from_entries(
+                    out_of_bounds.into_iter().map(Entry::Left),
+                )
+                .into_failure());
+            }
+            hash_map
+        }
+    };
+
+    let mut canonical: Vec<_> = hash_map.into_iter().map(|((i, j), v)| (i, j, v)).collect();
+    canonical.sort_unstable_by_key(|&(i, j, _)| (i, j));
+    Ok(canonical)
+}
+
+/// View of a matrix with a function applied to each of its elements.
+///
+/// Only the first `rows` rows of the wrapped matrix are exposed, which are all of them unless
+/// [with_rows](Self::with_rows) is used. Sparse entries in the remaining rows are dropped,
+/// except for entries with row indices beyond the wrapped matrix, so that they are still
+/// reported as out of bounds. The same holds for entries with out of bounds column indices,
+/// which are always kept.
+struct MappedMatrix<'a, S, M, F> {
+    inner: &'a M,
+    rows: usize,
+    map: F,
+    /// Whether `map` sends zeros to zero and non-zeros to non-zero values, in which case the
+    /// non-zero count hint of a wrapped dense matrix carries over to the view.
+    preserves_nonzeros: bool,
+    marker: PhantomData<fn(S)>,
+}
+
+impl<'a, S, M, F> MappedMatrix<'a, S, M, F>
+where
+    M: Matrix<S>,
+{
+    fn new(inner: &'a M, map: F) -> Self {
+        Self {
+            inner,
+            rows: inner.rows(),
+            map,
+            preserves_nonzeros: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Restricts the view to the first `rows` rows of the wrapped matrix.
+    fn with_rows(self, rows: usize) -> Self {
+        Self {
+            rows,
+            preserves_nonzeros: false,
+            ..self
+        }
+    }
+}
+
+impl<'a, S, T, M, F> Matrix<T> for MappedMatrix<'a, S, M, F>
+where
+    S: Clone,
+    M: Matrix<S>,
+    F: Fn(S) -> T,
+{
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.inner.cols()
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        match Storage::from(self.inner.access()) {
+            Storage::Dense(_) => Access::Dense(self),
+            Storage::Sparse(_) => Access::Sparse(self),
+        }
+    }
+}
+
+impl<'a, S, T, M, F> DenseAccess<T> for MappedMatrix<'a, S, M, F>
+where
+    S: Clone,
+    M: Matrix<S>,
+    F: Fn(S) -> T,
+{
+    fn fetch_single(&self, row: usize, col: usize) -> T {
+        match self.inner.access() {
+            Access::Dense(access) => (self.map)(access.fetch_single(row, col)),
+            _ => unreachable!("Mapped matrix only exposes dense access for dense matrices"),
+        }
+    }
+
+    fn nonzero_count_hint(&self) -> Option<usize> {
+        match self.inner.access() {
+            Access::Dense(access) if self.preserves_nonzeros => access.nonzero_count_hint(),
+            Access::Dense(_) => None,
+            _ => unreachable!("Mapped matrix only exposes dense access for dense matrices"),
+        }
+    }
+}
+
+impl<'a, S, T, M, F> SparseAccess<T> for MappedMatrix<'a, S, M, F>
+where
+    S: Clone,
+    M: Matrix<S>,
+    F: Fn(S) -> T,
+{
+    fn nnz(&self) -> usize {
+        self.fetch_triplets().len()
+    }
+
+    fn fetch_triplets(&self) -> Vec<(usize, usize, T)> {
+        match Storage::from(self.inner.access()) {
+            Storage::Sparse(storage) => {
+                let inner_rows = self.inner.rows();
+                storage
+                    .entries()
+                    .filter(|(i, _, _)| *i < self.rows || *i >= inner_rows)
+                    .map(|(i, j, v)| (i, j, (self.map)(v)))
+                    .collect()
+            }
+            Storage::Dense(_) => {
+                unreachable!("Mapped matrix only exposes sparse access for sparse matrices")
+            }
+        }
+    }
+}
+
+/// Comparison of a single-precision result against a double-precision reference.
+///
+/// Each entry of the reference is rounded to `f32`, after which it is compared with the
+/// corresponding computed entry by their `f32` ULP difference. This is the appropriate way to
+/// validate `f32` computations against `f64` reference data, since the tolerance is interpreted
+/// in terms of the precision of the computed result.
+///
+/// The reported mismatches contain the computed values (losslessly widened to `f64`) and the
+/// original `f64` reference values, while the error contains the rounded reference.
+/// See [F32ReferenceUlpComparator] for details.
+pub fn compare_f32_against_f64_reference(
+    computed: impl Matrix<f32>,
+    reference: impl Matrix<f64>,
+    ulp_tol: u64,
+) -> Result<(), MatrixComparisonFailure<f64, F32ReferenceError>> {
+    let comparator = F32ReferenceUlpComparator { tol: ulp_tol };
+    validate_wrapped(&computed, true)
+        .map_err(MatrixComparisonFailure::MalformedCompressedStorage)?;
+    // Every `f32` is exactly representable as an `f64`, so the conversion is lossless
+    let widened = MappedMatrix {
+        preserves_nonzeros: true,
+        ..MappedMatrix::new(&computed, f64::from)
+    };
+    compare_matrices(widened, reference, &comparator)
+}
+
+/// Comparison of the first rows of two matrices.
+///
+/// Only rows `0 .. min(n_rows, rows)` are compared, and the remaining rows are ignored.
+/// The number of columns must match, and both matrices must have at least as many rows as
+/// the number of rows to compare. If there are mismatched elements, the failure notes that only
+/// a prefix of the rows were compared.
+///
+/// This is intended as a quick smoke test for very large matrices.
+pub fn compare_head<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    n_rows: usize,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let compared_rows = n_rows.min(left.rows());
+    let shapes_match = compared_rows == n_rows.min(right.rows()) && left.cols() == right.cols();
+    if !shapes_match {
+        // The compared rows differ, so the full dimensions do too, which are the ones reported
+        check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+    }
+
+    validate_wrapped(&left, true)
+        .and_then(|()| validate_wrapped(&right, false))
+        .map_err(MatrixComparisonFailure::MalformedCompressedStorage)?;
+
+    let left_prefix = MappedMatrix::new(&left, |x: T| x).with_rows(compared_rows);
+    let right_prefix = MappedMatrix::new(&right, |x: T| x).with_rows(compared_rows);
+
+    compare_matrices(left_prefix, right_prefix, comparator).map_err(|failure| match failure {
+        MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+            mismatch.compared_rows = Some(compared_rows);
+            MatrixComparisonFailure::MismatchedElements(mismatch)
+        }
+        failure => failure,
+    })
+}
+
+/// A triangular part of a square matrix.
+///
+/// Used with [compare_triangular].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Triangle {
+    /// The elements below the diagonal, `i > j`.
+    Lower,
+    /// The elements above the diagonal, `i < j`.
+    Upper,
+}
+
+impl Triangle {
+    fn contains(self, i: usize, j: usize, include_diagonal: bool) -> bool {
+        match self {
+            _ if i == j => include_diagonal,
+            Triangle::Lower => i > j,
+            Triangle::Upper => i < j,
+        }
+    }
+}
+
+impl Display for Triangle {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Triangle::Lower => write!(f, "lower"),
+            Triangle::Upper => write!(f, "upper"),
+        }
+    }
+}
+
+/// Comparison of the lower or upper triangular parts of two square matrices.
+///
+/// Only the elements in the given triangle, and optionally on the diagonal, are compared.
+/// The remaining elements are ignored. This is useful when testing e.g. Cholesky or LU factors,
+/// where only a triangular part of the matrix is meaningful. Mismatches are reported with
+/// their coordinates in the full matrix, and the comparison criterion notes which part of the
+/// matrices was compared.
+///
+/// Sparse matrices are converted to dense storage, so invalid sparse entries are reported even
+/// if they lie outside the compared part. Fails with
+/// [MatrixComparisonFailure::NonSquareDimensions] if the matrices are not square.
+pub fn compare_triangular<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    triangle: Triangle,
+    include_diagonal: bool,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let (rows, cols) = (left.rows(), left.cols());
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+    if rows != cols {
+        return Err(MatrixComparisonFailure::NonSquareDimensions(
+            DimensionMismatch {
+                dim_left: (rows, cols),
+                dim_right: (right.rows(), right.cols()),
+            },
+        ));
+    }
+
+    let left_rows = collect_dense_rows(rows, cols, Storage::from(left.access()), Entry::Left)?;
+    let right_rows = collect_dense_rows(rows, cols, Storage::from(right.access()), Entry::Right)?;
+
+    let mut mismatches = Vec::new();
+    for (i, (left_row, right_row)) in left_rows.into_iter().zip(right_rows).enumerate() {
+        for (j, (a, b)) in left_row.into_iter().zip(right_row).enumerate() {
+            if triangle.contains(i, j, include_diagonal) {
+                if let Err(error) = comparator.compare(&a, &b) {
+                    mismatches.push(MatrixElementComparisonFailure {
+                        left: a,
+                        right: b,
+                        error,
+                        row: i,
+                        col: j,
+                    });
+                }
+            }
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        let diagonal = if include_diagonal {
+            "including"
+        } else {
+            "excluding"
+        };
+        Err(MatrixComparisonFailure::MismatchedElements(Box::new(
+            ElementsMismatch {
+                comparator_description: format!(
+                    "{description}\nOnly the {triangle} triangle, {diagonal} the diagonal, \
+                     was compared.",
+                    description = comparator.description(),
+                    triangle = triangle,
+                    diagonal = diagonal
+                ),
+                mismatches,
+                compared_rows: None,
+                statistics: None,
+                symmetric: false,
+                stopped_early: false,
+                dims: Some((rows, cols)),
+                display: DisplayOptions::default(),
+                nnz: None,
+            },
+        )))
+    }
+}
+
+/// Collects the rows of a matrix into dense vectors.
+fn collect_dense_rows<T, E>(
+    rows: usize,
+    cols: usize,
+    storage: Storage<T>,
+    make_entry: fn(Coordinate) -> Entry,
+) -> Result<Vec<Vec<T>>, MatrixComparisonFailure<T, E>>
+where
+    T: Zero + Clone,
+{
+    match storage {
+        Storage::Dense(access) => Ok((0..rows)
+            .map(|i| (0..cols).map(|j| access.fetch_single(i, j)).collect())
+            .collect()),
+        Storage::Sparse(access) => {
+            let hash_map = access
+                .try_build_hash_map()
+                .map_err(|build_error| build_error.into_failure(make_entry))?;
+            let mut dense_rows = vec![vec![T::zero(); cols]; rows];
+            for ((i, j), v) in hash_map {
+                dense_rows[i][j] = v;
+            }
+            Ok(dense_rows)
+        }
+    }
+}
+
+/// Attempts to find a match for the given left row by recursively searching for
+/// an augmenting path, as in Kuhn's algorithm for maximum bipartite matching.
+fn try_match_row(
+    left_row: usize,
+    candidates: &[Vec<usize>],
+    visited: &mut [bool],
+    matched_right: &mut [Option<usize>],
+) -> bool {
+    for &right_row in &candidates[left_row] {
+        if !visited[right_row] {
+            visited[right_row] = true;
+            let is_free_or_reassignable = match matched_right[right_row] {
+                None => true,
+                Some(other_left) => try_match_row(other_left, candidates, visited, matched_right),
+            };
+            if is_free_or_reassignable {
+                matched_right[right_row] = Some(left_row);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Comparison of two matrices, up to a permutation of the rows.
+///
+/// The comparison succeeds if there exists a permutation of the rows of `right` such that
+/// every row of `left` compares equal to the corresponding row of the permuted matrix.
+/// This is useful when the order of the rows is arbitrary, such as for a set of eigenvectors.
+///
+/// The rows are matched by computing a maximum matching between the rows of `left` and the rows
+/// of `right`, where two rows may be matched if all their elements compare equal. If there is no
+/// perfect matching, a [RowPermutationMismatch] failure lists the rows that could not be matched.
+/// Note that the specific rows reported as unmatched depend on the matching found,
+/// as there might be several maximum matchings.
+///
+/// # Complexity
+///
+/// Every pair of rows is compared, and the matching itself has cubic worst-case complexity
+/// in the number of rows. For an `m x n` matrix, the cost is therefore `O(m^2 n + m^3)`.
+/// Moreover, sparse matrices are converted to dense storage. This function is only intended
+/// for matrices of modest size.
+pub fn compare_matrices_up_to_row_permutation<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let (rows, cols) = (left.rows(), left.cols());
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let left_rows = collect_dense_rows(rows, cols, Storage::from(left.access()), Entry::Left)?;
+    let right_rows = collect_dense_rows(rows, cols, Storage::from(right.access()), Entry::Right)?;
+
+    // For each row in the left matrix, the rows in the right matrix it compares equal to
+    let candidates: Vec<Vec<usize>> = left_rows
+        .iter()
+        .map(|left_row| {
+            (0..rows)
+                .filter(|&j| {
+                    left_row
+                        .iter()
+                        .zip(&right_rows[j])
+                        .all(|(a, b)| comparator.compare(a, b).is_ok())
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut matched_right = vec![None; rows];
+    let mut unmatched_left = Vec::new();
+    for i in 0..rows {
+        let mut visited = vec![false; rows];
+        if !try_match_row(i, &candidates, &mut visited, &mut matched_right) {
+            unmatched_left.push(i);
+        }
+    }
+
+    if unmatched_left.is_empty() {
+        Ok(())
+    } else {
+        let unmatched_right = (0..rows).filter(|&j| matched_right[j].is_none()).collect();
+        Err(MatrixComparisonFailure::RowPermutationMismatch(Box::new(
+            RowPermutationMismatch {
+                comparator_description: comparator.description(),
+                unmatched_left,
+                unmatched_right,
+            },
+        )))
+    }
+}
+
+/// Comparison of a matrix against a scaled matrix.
+///
+/// Compares `left` elementwise against `factor * right`, where `factor` is a known conversion
+/// factor. This is useful when the two matrices are expressed in different units, for example
+/// when comparing a result in meters against a reference in millimeters (with `factor = 1e-3`).
+///
+/// The reported mismatches contain the scaled values of `right`, and the comparison criterion
+/// notes the factor that was used.
+pub fn compare_matrices_scaled<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    factor: T,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone + Display + Mul<Output = T>,
+    C: ElementwiseComparator<T>,
+{
+    compare_against_scaled(left, right, factor, comparator, "a factor of")
+}
+
+/// Compares `left` against `factor * right`, describing the factor with `factor_kind`
+/// in the comparison criterion.
+fn compare_against_scaled<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    factor: T,
+    comparator: &C,
+    factor_kind: &str,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone + Display + Mul<Output = T>,
+    C: ElementwiseComparator<T>,
+{
+    validate_wrapped(&left, true)
+        .and_then(|()| validate_wrapped(&right, false))
+        .map_err(MatrixComparisonFailure::MalformedCompressedStorage)?;
+
+    let scaled_right = MappedMatrix::new(&right, |x: T| factor.clone() * x);
+
+    compare_matrices(left, scaled_right, comparator).map_err(|failure| match failure {
+        MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+            mismatch.comparator_description = format!(
+                "{description}\nY (right) was scaled by {kind} {factor} before comparison.",
+                description = mismatch.comparator_description,
+                kind = factor_kind,
+                factor = factor
+            );
+            MatrixComparisonFailure::MismatchedElements(mismatch)
+        }
+        failure => failure,
+    })
+}
+
+/// Comparison of two matrices, up to a global scale factor.
+///
+/// Estimates the factor `alpha` that minimizes the Frobenius norm `||left - alpha * right||`,
+/// which is given by `alpha = <left, right> / <right, right>`, and then compares `left`
+/// elementwise against `alpha * right`. The factor may be negative, so this is useful for
+/// comparing e.g. eigenvectors, which are only determined up to a scalar multiple.
+/// If `right` is zero, every factor is equally good, and `alpha = 1` is used.
+///
+/// As with [compare_matrices_scaled], the reported mismatches contain the scaled values of `right`,
+/// and the comparison criterion notes the estimated factor. Sparse matrices are converted to
+/// dense storage in order to estimate the factor.
+pub fn compare_matrices_up_to_scale<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: FloatCore + Display,
+    C: ElementwiseComparator<T>,
+{
+    let (rows, cols) = (left.rows(), left.cols());
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let left_rows = collect_dense_rows(rows, cols, Storage::from(left.access()), Entry::Left)?;
+    let right_rows = collect_dense_rows(rows, cols, Storage::from(right.access()), Entry::Right)?;
+
+    let (mut dot, mut norm_squared) = (T::zero(), T::zero());
+    for (&x, &y) in left_rows.iter().flatten().zip(right_rows.iter().flatten()) {
+        dot = dot + x * y;
+        norm_squared = norm_squared + y * y;
+    }
+    let alpha = if norm_squared > T::zero() {
+        dot / norm_squared
+    } else {
+        T::one()
+    };
+
+    compare_against_scaled(
+        left,
+        right,
+        alpha,
+        comparator,
+        "an estimated best-fit factor of",
+    )
+}
+
+/// Replaces the value by zero if it is below the threshold in absolute value.
+fn snap_to_zero<T>(value: T, threshold: &T) -> T
+where
+    T: Zero + Clone + PartialOrd + Sub<Output = T>,
+{
+    let abs = if value < T::zero() {
+        T::zero() - value.clone()
+    } else {
+        value.clone()
+    };
+    if abs < *threshold {
+        T::zero()
+    } else {
+        value
+    }
+}
+
+/// Comparison of two matrices, treating small elements as zero.
+///
+/// Every element `x` of either matrix with `|x| < threshold` is replaced by zero before the
+/// elements are compared. In contrast to the tolerance of a comparator, this changes the values
+/// themselves, and so it also applies to exact comparison. This is useful when comparing e.g.
+/// a denoised result against a sparse reference. NaN elements are never replaced.
+///
+/// The reported mismatches contain the values after replacement, and the comparison criterion
+/// notes the threshold that was used. Sparse entries that are replaced by zero remain explicitly
+/// stored. Panics if the threshold is negative.
+pub fn compare_matrices_with_zero_threshold<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    threshold: T,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone + Display + PartialOrd + Sub<Output = T>,
+    C: ElementwiseComparator<T>,
+{
+    assert!(
+        threshold >= T::zero(),
+        "Zero threshold must be non-negative."
+    );
+
+    validate_wrapped(&left, true)
+        .and_then(|()| validate_wrapped(&right, false))
+        .map_err(MatrixComparisonFailure::MalformedCompressedStorage)?;
+
+    let snap = |x: T| snap_to_zero(x, &threshold);
+    let thresholded_left = MappedMatrix::new(&left, snap);
+    let thresholded_right = MappedMatrix::new(&right, snap);
+
+    compare_matrices(thresholded_left, thresholded_right, comparator).map_err(|failure| {
+        match failure {
+            MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+                mismatch.comparator_description = format!(
+                    "{description}\nElements x with |x| < {threshold} were treated as zero \
+                     before comparison.",
+                    description = mismatch.comparator_description,
+                    threshold = threshold
+                );
+                MatrixComparisonFailure::MismatchedElements(mismatch)
+            }
+            failure => failure,
+        }
+    })
+}
+
+/// Comparison of two matrices with a tolerance relative to the norm of the right matrix.
+///
+/// The comparison succeeds if
+///
+/// ```text
+///     ||left - right|| <= rel_tol * ||right||,
+/// ```
+///
+/// where `||.||` is the given [Norm]. In contrast to elementwise comparison, this is a global
+/// criterion, which is typically used to compare the solution of a numerical method, such as
+/// a PDE solver, against a reference solution. If the criterion is not satisfied, the failure
+/// reports the relative residual `||left - right|| / ||right||`. In particular, if `right` is zero,
+/// the comparison only succeeds if `left` is also zero, unless the tolerance is infinite.
+/// NaN elements always cause the comparison to fail.
+///
+/// Sparse matrices are converted to dense storage in order to compute the norms.
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn compare_matrices_relative_norm<T>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    norm: Norm,
+    rel_tol: T,
+) -> Result<(), MatrixComparisonFailure<T, Infallible>>
+where
+    T: Float + Display,
+{
+    let (rows, cols) = (left.rows(), left.cols());
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let left_rows = collect_dense_rows(rows, cols, Storage::from(left.access()), Entry::Left)?;
+    let right_rows = collect_dense_rows(rows, cols, Storage::from(right.access()), Entry::Right)?;
+    let difference_rows: Vec<Vec<T>> = left_rows
+        .iter()
+        .zip(&right_rows)
+        .map(|(x_row, y_row)| x_row.iter().zip(y_row).map(|(&x, &y)| x - y).collect())
+        .collect();
+
+    let residual_norm = norm.of_rows(&difference_rows);
+    let reference_norm = norm.of_rows(&right_rows);
+    // An infinite tolerance accepts any residual that is not NaN. Checking this explicitly
+    // avoids the product with a zero reference norm, which would be NaN
+    let accepted = if rel_tol.is_infinite() {
+        !residual_norm.is_nan()
+    } else {
+        residual_norm <= rel_tol * reference_norm
+    };
+    if accepted {
+        Ok(())
+    } else {
+        Err(MatrixComparisonFailure::NormMismatch(NormMismatch {
+            comparator_description: format!(
+                "relative {norm}, ||X - Y|| <= {tol} * ||reference||.",
+                norm = norm,
+                tol = rel_tol
+            ),
+            residual_norm,
+            reference_norm,
+            relative_residual: residual_norm / reference_norm,
+            reference_is_left: false,
+        }))
+    }
+}
+
+/// Comparison of a matrix against the transpose of another matrix.
+///
+/// Compares `left[[i, j]]` against `right[[j, i]]`, without storing the transpose of `right`.
+/// This is useful for checking that an operation and its adjoint agree.
+///
+/// Mismatched elements are reported at their coordinates in `left`, and the comparison criterion
+/// notes that `right` was transposed. If the dimensions do not match, the dimensions of the
+/// transpose of `right` are reported. Out-of-bounds or duplicate entries of a sparse `right`
+/// are reported at their coordinates in `right`.
+///
+/// To compare against a transposed matrix with the assertion macros, wrap the matrix in
+/// [Transpose](crate::Transpose) instead, e.g. `assert_matrix_eq!(a, Transpose(&b))`.
+pub fn compare_matrix_to_transpose_of<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+) -> Result<(), MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let transposed_right = Transpose(&right);
+
+    use MatrixComparisonFailure::*;
+    compare_matrices(left, transposed_right, comparator).map_err(|failure| match failure {
+        MismatchedElements(mut mismatch) => {
+            mismatch.comparator_description = format!(
+                "{}\nY (right) was transposed before comparison.",
+                mismatch.comparator_description
+            );
+            MismatchedElements(mismatch)
+        }
+        SparseEntryOutOfBounds(Entry::Right((i, j))) => {
+            SparseEntryOutOfBounds(Entry::Right((j, i)))
+        }
+        SparseEntriesOutOfBounds(entries) => {
+            SparseEntriesOutOfBounds(OutOfBoundsEntries::from_entries(
+                entries
+                    .left
+                    .into_iter()
+                    .map(Entry::Left)
+                    .chain(entries.right.into_iter().map(|(i, j)| Entry::Right((j, i)))),
+            ))
+        }
+        DuplicateSparseEntry(Entry::Right((i, j))) => DuplicateSparseEntry(Entry::Right((j, i))),
+        failure => failure,
+    })
+}
+
+/// Analyzes how close two matrices are, without comparing them against a tolerance.
+///
+/// Every element pair `(x, y)` is visited, and the largest absolute difference `|x - y|`
+/// is recorded in the returned [ComparisonReport]. Sparse matrices are converted to
+/// dense storage. Pairs whose difference cannot be ordered, such as those involving NaN,
+/// are ignored. As with the `abs` comparator, the difference is computed by subtracting
+/// the smaller number from the larger number, so unsigned integers are supported.
+///
+/// Fails if the dimensions of the matrices do not match, or if a sparse matrix
+/// has out-of-bounds or duplicate entries.
+pub fn analyze_matrices<T>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+) -> Result<ComparisonReport<T>, MatrixComparisonFailure<T, Infallible>>
+where
+    T: Zero + Clone + PartialOrd + Sub<Output = T>,
+{
+    let (rows, cols) = (left.rows(), left.cols());
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let left_rows = collect_dense_rows(rows, cols, Storage::from(left.access()), Entry::Left)?;
+    let right_rows = collect_dense_rows(rows, cols, Storage::from(right.access()), Entry::Right)?;
+
+    let num_compared = rows * cols;
+    let mut report = ComparisonReport {
+        num_compared,
+        max_abs_diff: T::zero(),
+        max_abs_diff_coord: if num_compared > 0 { Some((0, 0)) } else { None },
+    };
+    for (i, (left_row, right_row)) in left_rows.into_iter().zip(right_rows).enumerate() {
+        for (j, (x, y)) in left_row.into_iter().zip(right_row).enumerate() {
+            let abs_diff = if x > y { x - y } else { y - x };
+            if abs_diff > report.max_abs_diff {
+                report.max_abs_diff = abs_diff;
+                report.max_abs_diff_coord = Some((i, j));
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// A dense grid of elementwise comparison results, indexed as `grid[row][col]`.
+///
+/// See [error_matrix].
+pub type ErrorGrid<E> = Vec<Vec<Option<E>>>;
+
+/// Computes the elementwise comparison result for every pair of elements.
+///
+/// Returns a dense grid of results, indexed as `grid[row][col]`, where `None` indicates that
+/// the element pair compared equal and `Some(error)` holds the error reported by the comparator.
+/// Unlike [compare_matrices], which only reports the mismatched elements, this keeps the
+/// result for every element, which is useful e.g. for visualizing spatial patterns in the error.
+///
+/// Sparse matrices are converted to dense storage, so every element pair is compared,
+/// including pairs of implicit zeros. Fails if the dimensions of the matrices do not match,
+/// or if a sparse matrix has out-of-bounds or duplicate entries.
+pub fn error_matrix<T, C>(
+    left: impl Matrix<T>,
+    right: impl Matrix<T>,
+    comparator: &C,
+) -> Result<ErrorGrid<C::Error>, MatrixComparisonFailure<T, C::Error>>
+where
+    T: Zero + Clone,
+    C: ElementwiseComparator<T>,
+{
+    let (rows, cols) = (left.rows(), left.cols());
+    check_dimensions(&left, &right).map_err(MatrixComparisonFailure::MismatchedDimensions)?;
+
+    let left_rows = collect_dense_rows(rows, cols, Storage::from(left.access()), Entry::Left)?;
+    let right_rows = collect_dense_rows(rows, cols, Storage::from(right.access()), Entry::Right)?;
+
+    Ok(left_rows
+        .iter()
+        .zip(&right_rows)
+        .map(|(left_row, right_row)| {
+            left_row
+                .iter()
+                .zip(right_row)
+                .map(|(a, b)| comparator.compare(a, b).err())
+                .collect()
+        })
+        .collect())
+}
+
+/// Checks that every element of a floating-point matrix is finite.
+///
+/// Every element is visited, and all non-finite elements are returned together with their kind,
+/// sorted in lexicographical order of their coordinates. For sparse matrices, only the explicitly
+/// stored entries are checked, since implicit zeros are always finite. The coordinates of sparse
+/// entries are not validated against the dimensions of the matrix.
+///
+/// See also [assert_matrix_finite!](crate::assert_matrix_finite).
+///
+/// # Examples
+///
+/// ```
+/// # use matrixcompare::{compare_matrix_finite, SpecialValueKind};
+/// # use matrixcompare_mock::mock_matrix;
+/// let m = mock_matrix![1.0, f64::NAN;
+///                      f64::NEG_INFINITY, 4.0];
+/// assert_eq!(
+///     compare_matrix_finite(&m),
+///     Err(vec![((0, 1), SpecialValueKind::Nan), ((1, 0), SpecialValueKind::NegativeInfinity)])
+/// );
+/// ```
+pub fn compare_matrix_finite<T>(
+    matrix: impl Matrix<T>,
+) -> Result<(), Vec<(Coordinate, SpecialValueKind)>>
+where
+    T: FloatCore,
+{
+    let classify = |i, j, x: T| SpecialValueKind::classify(x).map(|kind| ((i, j), kind));
+    let mut non_finite: Vec<_> = match Storage::from(matrix.access()) {
+        Storage::Dense(access) => {
+            let (rows, cols) = (access.rows(), access.cols());
+            if let Some(data) = row_major_slice(access) {
+                data.iter()
+                    .enumerate()
+                    .filter_map(|(k, &x)| classify(k / cols, k % cols, x))
+                    .collect()
+            } else {
+                (0..rows)
+                    .flat_map(|i| (0..cols).map(move |j| (i, j)))
+                    .filter_map(|(i, j)| classify(i, j, *fetch_dense_element(access, i, j)))
+                    .collect()
+            }
+        }
+        Storage::Sparse(ref access) => access
+            .entries()
+            .filter_map(|(i, j, x)| classify(i, j, x))
+            .collect(),
+    };
+
+    if non_finite.is_empty() {
+        Ok(())
+    } else {
+        non_finite.sort_by_key(|&(coord, _)| coord);
+        Err(non_finite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_same_matrix;
+    use matrixcompare_mock::{mock_matrix, MockSparseMatrix};
+
+    #[test]
+    fn is_same_matrix_compares_addresses() {
+        let a = mock_matrix![1.0, 2.0];
+        let b = a.clone();
+        assert!(is_same_matrix(&a, &a));
+        assert!(is_same_matrix(&&a, &a));
+        assert!(!is_same_matrix(&a, &b));
+
+        let sparse = MockSparseMatrix::from_triplets(1, 2, vec![(0, 0, 1.0)]);
+        assert!(is_same_matrix(&sparse, &&sparse));
+        assert!(!is_same_matrix(&sparse, &sparse.clone()));
     }
 }
@@ -0,0 +1,68 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use num_traits::Float;
+
+/// A matrix norm.
+///
+/// Used with [compare_matrices_relative_norm](crate::compare_matrices_relative_norm).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Norm {
+    /// The square root of the sum of the squares of all elements.
+    Frobenius,
+    /// The largest absolute value of any element.
+    Max,
+    /// The largest sum of absolute values in any column.
+    One,
+    /// The largest sum of absolute values in any row.
+    Infinity,
+}
+
+impl Display for Norm {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Norm::Frobenius => "Frobenius norm",
+            Norm::Max => "max norm",
+            Norm::One => "1-norm",
+            Norm::Infinity => "infinity norm",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Norm {
+    /// Computes the norm of a dense matrix, given as a list of rows of equal length.
+    pub(crate) fn of_rows<T: Float>(self, rows: &[Vec<T>]) -> T {
+        let abs_values = || rows.iter().flatten().map(|x| x.abs());
+        match self {
+            Norm::Frobenius => abs_values().fold(T::zero(), |acc, x| acc + x * x).sqrt(),
+            Norm::Max => abs_values().fold(T::zero(), max_propagating_nan),
+            Norm::One => {
+                let cols = rows.first().map_or(0, |row| row.len());
+                (0..cols)
+                    .map(|j| rows.iter().fold(T::zero(), |acc, row| acc + row[j].abs()))
+                    .fold(T::zero(), max_propagating_nan)
+            }
+            Norm::Infinity => rows
+                .iter()
+                .map(|row| row.iter().fold(T::zero(), |acc, x| acc + x.abs()))
+                .fold(T::zero(), max_propagating_nan),
+        }
+    }
+}
+
+/// The maximum of two numbers, which is NaN if either of them is NaN.
+///
+/// In contrast, `Float::max` ignores NaN, which would let a NaN element go unnoticed.
+#[cfg(feature = "std")]
+fn max_propagating_nan<T: Float>(a: T, b: T) -> T {
+    if b.is_nan() || b > a {
+        b
+    } else {
+        a
+    }
+}
@@ -0,0 +1,27 @@
+//! Tools for comparing optional values, such as measurements that may be missing.
+
+/// Types that represent a value that may be missing.
+///
+/// Implementing this trait enables the usage of the
+/// [OptionComparator](crate::comparators::OptionComparator) for the given type.
+///
+/// An implementation for [Option] is provided, so that slices of [Option] can be compared
+/// directly. However, matrix comparison requires the elements to implement `num_traits::Zero`,
+/// and formatting a failure requires the elements to implement `Display`, neither of which can
+/// be implemented for [Option] outside of the crates that define them. Matrices of optional
+/// values therefore typically use a newtype that implements this trait.
+pub trait Optional {
+    /// The type of the value, if present.
+    type Value;
+
+    /// Returns a reference to the value, or `None` if the value is missing.
+    fn as_option(&self) -> Option<&Self::Value>;
+}
+
+impl<T> Optional for Option<T> {
+    type Value = T;
+
+    fn as_option(&self) -> Option<&T> {
+        self.as_ref()
+    }
+}
@@ -0,0 +1,134 @@
+/// Options that modify how matrices are compared.
+///
+/// Used with [compare_matrices_with_options](crate::compare_matrices_with_options).
+/// The default options give the same behavior as [compare_matrices](crate::compare_matrices).
+/// Options are usually set through the builder methods, starting from [ComparisonOptions::new].
+///
+/// Names for the matrices in the output are not part of the options, since they are attached
+/// to the failure rather than affecting the comparison. See
+/// [MatrixComparisonFailure::with_labels](crate::MatrixComparisonFailure::with_labels).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ComparisonOptions {
+    /// Treat the matrices as symmetric, and deduplicate mirrored mismatches.
+    ///
+    /// If a mismatch at `(i, j)` in the lower triangle has a corresponding mismatch at `(j, i)`,
+    /// only the latter is reported. Mismatches that do not have a mirrored counterpart
+    /// are always reported. Both matrices must be square.
+    pub symmetric: bool,
+    /// Stop comparing after the given number of mismatched elements have been found.
+    ///
+    /// This avoids the cost of examining every element of very large matrices when only a few
+    /// examples of mismatches are needed. If the comparison stops early, the number of reported
+    /// mismatches is only a lower bound. A limit of zero is treated as a limit of one.
+    /// The default, `None`, compares all elements.
+    pub early_exit_after: Option<usize>,
+    /// The number of decimal places used when displaying the mismatched elements and their errors.
+    ///
+    /// This is passed on to the `Display` implementations of the elements and errors as the
+    /// precision, as with `{:.N}`, which for floating-point numbers is the number of digits
+    /// after the decimal point. It is not the number of significant digits, so small numbers may
+    /// be displayed as zero. The default, `None`, displays the numbers at full precision.
+    pub decimal_places: Option<usize>,
+    /// The maximum number of mismatched elements listed when the failure is displayed.
+    ///
+    /// Mismatches beyond the limit are still collected, and their number is noted in the output.
+    /// The default, `None`, lists at most 12 mismatches.
+    pub max_reports: Option<usize>,
+    /// Fail immediately with [MatrixComparisonFailure::NanEncountered] if either matrix
+    /// contains NaN.
+    ///
+    /// Whether an element is NaN is determined by
+    /// [ElementwiseComparator::is_nan](crate::comparators::ElementwiseComparator::is_nan), so
+    /// NaN is only detected by comparators that accept floating-point elements. If there are
+    /// several NaN elements, the one with the smallest coordinate is reported.
+    ///
+    /// [MatrixComparisonFailure::NanEncountered]: crate::MatrixComparisonFailure::NanEncountered
+    pub reject_nan: bool,
+    /// Treat explicitly stored zeros in sparse matrices as significant.
+    ///
+    /// By default, an explicitly stored zero compares equal to an implicit zero. If set, a
+    /// comparison between two sparse matrices whose elements all compare equal instead fails
+    /// with [MatrixComparisonFailure::PatternMismatch] if an explicit zero in one matrix
+    /// corresponds to an entry that is not stored in the other. Mismatched elements take
+    /// precedence, and comparisons involving dense matrices are not affected.
+    ///
+    /// [MatrixComparisonFailure::PatternMismatch]: crate::MatrixComparisonFailure::PatternMismatch
+    pub explicit_zeros_significant: bool,
+    /// Display mismatched elements as the mismatched columns of each row.
+    ///
+    /// Instead of listing every mismatched element with its values, the output lists each row
+    /// that contains mismatches along with its mismatched columns, as in
+    /// `Row 3: cols [0, 5, 7] mismatched`. This makes failures that affect whole rows easy
+    /// to spot. The limit set by [max_reports](Self::max_reports) then applies to the number
+    /// of rows listed. The default, `false`, lists the individual elements.
+    pub group_by_row: bool,
+    /// Check that dense matrices can provide the elements at the corners of their reported
+    /// dimensions before comparing them.
+    ///
+    /// A faulty implementation of the matrix traits may report dimensions that do not match
+    /// the data it can serve, which would otherwise cause a panic somewhere in the middle of
+    /// the comparison. If set, a panic while fetching a corner element is caught, and the
+    /// comparison fails with [MatrixComparisonFailure::MalformedMatrix] instead. This is useful
+    /// when testing implementations of the matrix traits. The message of the caught panic is
+    /// still written to the error output by the panic hook.
+    ///
+    /// Catching panics requires the `std` feature, without which this option has no effect.
+    ///
+    /// [MatrixComparisonFailure::MalformedMatrix]: crate::MatrixComparisonFailure::MalformedMatrix
+    pub validate_dimensions: bool,
+}
+
+impl ComparisonOptions {
+    /// Creates the default options, for use with the builder methods.
+    ///
+    /// ```
+    /// # use matrixcompare::ComparisonOptions;
+    /// let options = ComparisonOptions::new()
+    ///     .max_reports(50)
+    ///     .decimal_places(4);
+    /// assert_eq!(options.max_reports, Some(50));
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn symmetric(mut self, symmetric: bool) -> Self {
+        self.symmetric = symmetric;
+        self
+    }
+
+    pub fn early_exit_after(mut self, max_mismatches: usize) -> Self {
+        self.early_exit_after = Some(max_mismatches);
+        self
+    }
+
+    pub fn decimal_places(mut self, decimal_places: usize) -> Self {
+        self.decimal_places = Some(decimal_places);
+        self
+    }
+
+    pub fn max_reports(mut self, max_reports: usize) -> Self {
+        self.max_reports = Some(max_reports);
+        self
+    }
+
+    pub fn reject_nan(mut self, reject_nan: bool) -> Self {
+        self.reject_nan = reject_nan;
+        self
+    }
+
+    pub fn explicit_zeros_significant(mut self, significant: bool) -> Self {
+        self.explicit_zeros_significant = significant;
+        self
+    }
+
+    pub fn group_by_row(mut self, group_by_row: bool) -> Self {
+        self.group_by_row = group_by_row;
+        self
+    }
+
+    pub fn validate_dimensions(mut self, validate: bool) -> Self {
+        self.validate_dimensions = validate;
+        self
+    }
+}
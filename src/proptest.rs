@@ -7,13 +7,25 @@
 #[doc(hidden)]
 macro_rules! build_proptest_message {
     ($failure:expr) => {
-        format!(
+        $crate::__private::format!(
             "Comparison failure at {}:{}. Error:\n {}",
             file!(),
             line!(),
             $failure
         );
     };
+    ($failure:expr, $x:expr, $y:expr) => {
+        $crate::__private::format!(
+            "Comparison failure at {}:{}. Dimensions: X is {} x {}, Y is {} x {}. Error:\n {}",
+            file!(),
+            line!(),
+            $crate::Matrix::rows($x),
+            $crate::Matrix::cols($x),
+            $crate::Matrix::rows($y),
+            $crate::Matrix::cols($y),
+            $failure
+        );
+    };
 }
 
 /// A version of `assert_matrix_eq` suitable for use in `proptest` property-based tests.
@@ -21,18 +33,26 @@ macro_rules! build_proptest_message {
 /// Works exactly as `assert_matrix_eq`, except that instead of causing a panic,
 /// it returns an error compatible with property-based tests from the `proptest` crate.
 ///
+/// Since the generated inputs are not otherwise visible in the output, the failure message
+/// also states the dimensions of both matrices. This is particularly helpful when the
+/// matrices have compatible dimensions, in which case the comparison failure itself only
+/// lists the coordinates of the mismatched elements.
+///
 /// Requires the `proptest-support` feature to be enabled.
 #[macro_export]
 macro_rules! prop_assert_matrix_eq {
-    ($($args:tt)*) => {
+    ($x:expr, $y:expr $(, $($args:tt)*)?) => {
+        // Evaluate the matrices only once, since they are needed for both the comparison
+        // and the failure message
+        let (x, y) = (&$x, &$y);
         let failure_handler = |msg| {
-            // Add filename and line numbers to message (since we don't panic, it's useful
-            // to have this information in the output).
-            let amended_message = $crate::build_proptest_message!(msg);
+            // Add filename, line numbers and dimensions to message (since we don't panic,
+            // it's useful to have this information in the output).
+            let amended_message = $crate::build_proptest_message!(msg, x, y);
             return ::core::result::Result::Err(
                 ::proptest::test_runner::TestCaseError::fail(amended_message));
         };
-        $crate::base_matrix_eq!(failure_handler, $($args)*);
+        $crate::base_matrix_eq_args!(failure_handler, x, y $(, $($args)*)?);
     }
 }
 
@@ -0,0 +1,159 @@
+use crate::{Coordinate, MatrixComparisonFailure};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// A summary of how close two matrices are, regardless of whether they compare equal.
+///
+/// Produced by [analyze_matrices](crate::analyze_matrices) and
+/// [assert_matrix_approx_eq_with_report!](crate::assert_matrix_approx_eq_with_report).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport<T> {
+    /// The number of element pairs that were compared.
+    pub num_compared: usize,
+    /// The largest absolute difference `|x - y|` over all element pairs.
+    ///
+    /// Zero if no element pairs were compared.
+    pub max_abs_diff: T,
+    /// The coordinate of the element pair with the largest absolute difference.
+    ///
+    /// `None` if no element pairs were compared.
+    pub max_abs_diff_coord: Option<Coordinate>,
+}
+
+impl<T> Display for ComparisonReport<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.max_abs_diff_coord {
+            Some((i, j)) => write!(
+                f,
+                "Max absolute difference |x - y| over {n} element pairs: {diff} at ({i}, {j}).",
+                n = self.num_compared,
+                diff = self.max_abs_diff,
+                i = i,
+                j = j
+            ),
+            None => write!(f, "No element pairs were compared."),
+        }
+    }
+}
+
+/// A collection of the results of several matrix comparisons, reported together.
+///
+/// This is useful for table-driven tests, where many pairs of matrices are compared and all
+/// failures should be reported at once, rather than only the first. Each result is added
+/// together with a name that identifies the comparison in the output.
+///
+/// # Examples
+///
+/// ```rust,should_panic
+/// # use matrixcompare::{compare_matrices, MultiComparisonReport};
+/// # use matrixcompare::comparators::AbsoluteElementwiseComparator;
+/// # use matrixcompare_mock::mock_matrix;
+/// let comp = AbsoluteElementwiseComparator { tol: 1e-6 };
+/// let cases = vec![
+///     ("identity", mock_matrix![1.0, 0.0; 0.0, 1.0], mock_matrix![1.0, 0.0; 0.0, 1.0]),
+///     ("scaled", mock_matrix![2.0, 0.0; 0.0, 2.0], mock_matrix![2.0, 0.0; 0.0, 2.5]),
+/// ];
+///
+/// let mut report = MultiComparisonReport::new();
+/// for (name, computed, expected) in &cases {
+///     report.add(*name, compare_matrices(computed, expected, &comp));
+/// }
+/// // Panics once, listing every failed comparison
+/// report.assert_all_ok();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiComparisonReport<T, Error> {
+    num_results: usize,
+    failures: Vec<(String, MatrixComparisonFailure<T, Error>)>,
+}
+
+impl<T, Error> Default for MultiComparisonReport<T, Error> {
+    fn default() -> Self {
+        MultiComparisonReport {
+            num_results: 0,
+            failures: Vec::new(),
+        }
+    }
+}
+
+impl<T, Error> MultiComparisonReport<T, Error> {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the result of a comparison, identified by the given name.
+    pub fn add(
+        &mut self,
+        name: impl Into<String>,
+        result: Result<(), MatrixComparisonFailure<T, Error>>,
+    ) {
+        self.num_results += 1;
+        if let Err(failure) = result {
+            self.failures.push((name.into(), failure));
+        }
+    }
+
+    /// The number of results that have been added, including successful ones.
+    pub fn num_results(&self) -> usize {
+        self.num_results
+    }
+
+    /// The names and failures of the failed comparisons, in the order they were added.
+    pub fn failures(&self) -> &[(String, MatrixComparisonFailure<T, Error>)] {
+        &self.failures
+    }
+
+    /// Whether all of the comparisons succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics with a message listing all failed comparisons, if any comparison failed.
+    #[track_caller]
+    pub fn assert_all_ok(&self)
+    where
+        T: Display,
+        Error: Display,
+    {
+        if !self.is_ok() {
+            panic!("{}", self);
+        }
+    }
+}
+
+impl<T, Error> Display for MultiComparisonReport<T, Error>
+where
+    T: Display,
+    Error: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_ok() {
+            return write!(f, "All {} matrix comparisons succeeded.", self.num_results);
+        }
+
+        write!(
+            f,
+            "{} of {} matrix comparisons failed:",
+            self.failures.len(),
+            self.num_results
+        )?;
+        for (index, (name, _)) in self.failures.iter().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, name)?;
+        }
+        writeln!(f, ".")?;
+
+        for (name, failure) in &self.failures {
+            writeln!(f)?;
+            writeln!(f, "Comparison {} failed:", name)?;
+            writeln!(f, "{}", failure)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
-use std::fmt;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::comparators::ElementwiseComparator;
+#[cfg(feature = "std")]
+use crate::comparison_failure::WithSource;
+use crate::comparison_failure::MAX_MISMATCH_REPORTS;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ScalarComparisonFailure<T, E> {
@@ -26,10 +31,13 @@ where
 }
 
 /// Comparison of two scalars.
+///
+/// As with [compare_matrices](crate::compare_matrices), the comparator is borrowed, so that
+/// the same comparator can be reused across scalar and matrix comparisons.
 pub fn compare_scalars<T, C>(
     left: &T,
     right: &T,
-    comparator: C,
+    comparator: &C,
 ) -> Result<(), ScalarComparisonFailure<T, C::Error>>
 where
     T: Clone,
@@ -44,3 +52,207 @@ where
             error,
         })
 }
+
+/// A pair of elements of two slices that did not compare equal.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SliceElementComparisonFailure<T, E> {
+    /// The element `x[index]` of the left slice x.
+    pub left: T,
+    /// The element `y[index]` of the right slice y.
+    pub right: T,
+    pub error: E,
+    pub index: usize,
+}
+
+impl<T, E> fmt::Display for SliceElementComparisonFailure<T, E>
+where
+    T: fmt::Display,
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{i}]: x = {x}, y = {y}.",
+            i = self.index,
+            x = self.left,
+            y = self.right
+        )?;
+
+        // Only add a space between the elements and the error if there is something to report
+        let error = alloc::format!("{}", self.error);
+        if !error.is_empty() {
+            write!(f, " {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Mismatch between the lengths of two slices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LengthMismatch {
+    pub len_left: usize,
+    pub len_right: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r"Lengths of slices x (left) and y (right) do not match.
+ len(x) = {x_len}
+ len(y) = {y_len}",
+            x_len = self.len_left,
+            y_len = self.len_right
+        )
+    }
+}
+
+/// Mismatched elements of two slices of equal length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SliceElementsMismatch<T, E> {
+    pub comparator_description: String,
+    /// The mismatched element pairs, in increasing order of their indices.
+    pub mismatches: Vec<SliceElementComparisonFailure<T, E>>,
+    /// The length of both slices.
+    pub len: usize,
+}
+
+impl<T, E> fmt::Display for SliceElementsMismatch<T, E>
+where
+    T: fmt::Display,
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Slices x (left) and y (right) have {num} mismatched element pairs out of {len}.",
+            num = self.mismatches.len(),
+            len = self.len
+        )?;
+        writeln!(f, "The mismatched elements are listed below, in the format")?;
+        writeln!(f, "[index]: x = x[index], y = y[index].")?;
+        writeln!(f)?;
+        for mismatch in self.mismatches.iter().take(MAX_MISMATCH_REPORTS) {
+            writeln!(f, " {}", mismatch)?;
+        }
+        if self.mismatches.len() > MAX_MISMATCH_REPORTS {
+            let num_hidden_entries = self.mismatches.len() - MAX_MISMATCH_REPORTS;
+            writeln!(
+                f,
+                " ... ({} mismatching elements not shown)",
+                num_hidden_entries
+            )?;
+        }
+        writeln!(f)?;
+        write!(f, "Comparison criterion: {}", self.comparator_description)
+    }
+}
+
+/// The error type associated with slice comparison.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SliceComparisonFailure<T, E> {
+    MismatchedLengths(LengthMismatch),
+    MismatchedElements(SliceElementsMismatch<T, E>),
+}
+
+impl<T, E> fmt::Display for SliceComparisonFailure<T, E>
+where
+    T: fmt::Display,
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SliceComparisonFailure::MismatchedLengths(mismatch) => mismatch.fmt(f),
+            SliceComparisonFailure::MismatchedElements(mismatch) => mismatch.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> std::error::Error for SliceComparisonFailure<T, E>
+where
+    T: fmt::Debug + fmt::Display,
+    E: fmt::Debug + fmt::Display,
+{
+}
+
+#[cfg(feature = "std")]
+impl<T, E> SliceComparisonFailure<T, E> {
+    /// Wraps the failure so that the comparator error of the first mismatch is its source.
+    ///
+    /// See [WithSource] for details.
+    pub fn with_source(self) -> WithSource<Self> {
+        WithSource(self)
+    }
+}
+
+/// As for matrices, the source of a failure with mismatched elements is the comparator error
+/// of the first mismatch.
+#[cfg(feature = "std")]
+impl<T, E> std::error::Error for WithSource<SliceComparisonFailure<T, E>>
+where
+    T: fmt::Debug + fmt::Display,
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.0 {
+            SliceComparisonFailure::MismatchedElements(mismatch) => mismatch
+                .mismatches
+                .first()
+                .map(|first| &first.error as &(dyn std::error::Error + 'static)),
+            SliceComparisonFailure::MismatchedLengths(_) => None,
+        }
+    }
+}
+
+/// Elementwise comparison of two slices of scalars.
+///
+/// This is a lightweight alternative to [compare_matrices](crate::compare_matrices) for
+/// one-dimensional data, such as a `Vec<f64>`. Fails with
+/// [SliceComparisonFailure::MismatchedLengths] if the slices have different lengths, and
+/// otherwise lists the indices of all mismatched element pairs.
+pub fn compare_slices<T, C>(
+    left: &[T],
+    right: &[T],
+    comparator: &C,
+) -> Result<(), SliceComparisonFailure<T, C::Error>>
+where
+    T: Clone,
+    C: ElementwiseComparator<T>,
+{
+    if left.len() != right.len() {
+        return Err(SliceComparisonFailure::MismatchedLengths(LengthMismatch {
+            len_left: left.len(),
+            len_right: right.len(),
+        }));
+    }
+
+    let mismatches: Vec<_> = left
+        .iter()
+        .zip(right)
+        .enumerate()
+        .filter_map(|(index, (x, y))| {
+            comparator
+                .compare(x, y)
+                .err()
+                .map(|error| SliceElementComparisonFailure {
+                    left: x.clone(),
+                    right: y.clone(),
+                    error,
+                    index,
+                })
+        })
+        .collect();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(SliceComparisonFailure::MismatchedElements(
+            SliceElementsMismatch {
+                comparator_description: comparator.description(),
+                mismatches,
+                len: left.len(),
+            },
+        ))
+    }
+}
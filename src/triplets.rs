@@ -0,0 +1,57 @@
+use crate::{Access, Matrix, SparseAccess};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+/// A sparse matrix given by a list of triplets, used by
+/// [assert_matrix_eq_triplets!](crate::assert_matrix_eq_triplets).
+///
+/// The triplets are not validated, so that duplicate or out-of-bounds triplets are reported
+/// by the comparison like for any other sparse matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripletMatrix<T> {
+    rows: usize,
+    cols: usize,
+    triplets: Vec<(usize, usize, T)>,
+}
+
+impl<T> TripletMatrix<T> {
+    /// Creates a matrix with the same dimensions as `matrix` from the given triplets.
+    pub fn with_dims_of<M>(matrix: &M, triplets: Vec<(usize, usize, T)>) -> Self
+    where
+        M: Matrix<T>,
+    {
+        Self {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+            triplets,
+        }
+    }
+}
+
+impl<T: Clone> Matrix<T> for TripletMatrix<T> {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn access(&self) -> Access<'_, T> {
+        Access::Sparse(self)
+    }
+}
+
+impl<T: Clone> SparseAccess<T> for TripletMatrix<T> {
+    fn nnz(&self) -> usize {
+        self.triplets.len()
+    }
+
+    fn fetch_triplets(&self) -> Vec<(usize, usize, T)> {
+        self.triplets.clone()
+    }
+
+    fn fetch_triplets_cow(&self) -> Cow<'_, [(usize, usize, T)]> {
+        Cow::Borrowed(&self.triplets)
+    }
+}
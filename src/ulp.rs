@@ -1,5 +1,4 @@
 //! Tools for ULP-based comparison of floating point numbers.
-use std::mem;
 
 /// Represents the result of an ULP-based comparison between two floating point numbers.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -28,7 +27,9 @@ pub enum UlpComparisonResult {
 /// [Comparing Floating Point Numbers, 2012 Edition](https://randomascii.wordpress.com/2012/02/25/comparing-floating-point-numbers-2012-edition/).
 ///
 /// Implementations for `f32` and `f64` are already available, and so users should not normally
-/// need to implement this. In the case when a custom implementation is necessary,
+/// need to implement this. For newtypes wrapping `f32` or `f64`, such as unit types,
+/// the trait can be implemented with [impl_ulp_for_newtype!](crate::impl_ulp_for_newtype).
+/// In the case when a custom implementation is necessary,
 /// please see the possible return values for [UlpComparisonResult].
 /// Otherwise, we can recommend to read the source code of the included `f32` and `f64` implementations.
 pub trait Ulp {
@@ -39,7 +40,6 @@ pub trait Ulp {
 macro_rules! impl_float_ulp {
     ($ftype:ty, $itype:ty) => {
         impl Ulp for $ftype {
-            #[allow(clippy::transmute_float_to_int)]
             fn ulp_diff(a: &Self, b: &Self) -> UlpComparisonResult {
                 if a == b {
                     UlpComparisonResult::ExactMatch
@@ -51,9 +51,9 @@ macro_rules! impl_float_ulp {
                     UlpComparisonResult::IncompatibleSigns
                 } else {
                     // Otherwise, we compute the ULP diff as the difference of the signed integer representations
-                    let a_int = unsafe { mem::transmute::<$ftype, $itype>(a.to_owned()) };
-                    let b_int = unsafe { mem::transmute::<$ftype, $itype>(b.to_owned()) };
-                    UlpComparisonResult::Difference((b_int - a_int).abs() as u64)
+                    let a_int = a.to_bits() as $itype;
+                    let b_int = b.to_bits() as $itype;
+                    UlpComparisonResult::Difference(u64::from((b_int - a_int).unsigned_abs()))
                 }
             }
         }
@@ -63,12 +63,43 @@ macro_rules! impl_float_ulp {
 impl_float_ulp!(f32, i32);
 impl_float_ulp!(f64, i64);
 
+/// Implements [Ulp](crate::ulp::Ulp) for newtypes wrapping a type that implements `Ulp`.
+///
+/// The ULP difference of two instances of the newtype is the ULP difference of the wrapped
+/// values, which must be accessible as the first field `.0` of the newtype.
+///
+/// # Examples
+///
+/// ```
+/// use matrixcompare::impl_ulp_for_newtype;
+/// use matrixcompare::ulp::{Ulp, UlpComparisonResult};
+///
+/// struct Meters(f64);
+/// struct Seconds(f32);
+/// impl_ulp_for_newtype!(Meters, Seconds);
+///
+/// let a = Meters(1.0);
+/// let b = Meters(f64::from_bits(1.0f64.to_bits() + 2));
+/// assert_eq!(Meters::ulp_diff(&a, &b), UlpComparisonResult::Difference(2));
+/// ```
+#[macro_export]
+macro_rules! impl_ulp_for_newtype {
+    ($($newtype:ty),+ $(,)?) => {
+        $(
+            impl $crate::ulp::Ulp for $newtype {
+                fn ulp_diff(a: &Self, b: &Self) -> $crate::ulp::UlpComparisonResult {
+                    $crate::ulp::Ulp::ulp_diff(&a.0, &b.0)
+                }
+            }
+        )+
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::Ulp;
     use super::UlpComparisonResult;
     use quickcheck::TestResult;
-    use std::mem;
     use std::{f32, f64};
 
     #[test]
@@ -87,6 +118,27 @@ mod tests {
         assert!(f64::ulp_diff(&-0.0, &0.0) == UlpComparisonResult::ExactMatch);
     }
 
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    struct Meters(f64);
+    crate::impl_ulp_for_newtype!(Meters);
+
+    #[test]
+    fn newtype_ulp_diff_matches_wrapped_value() {
+        let pairs = [
+            (1.0, 1.0),
+            (1.0, 1.0 + f64::EPSILON),
+            (-0.0, 0.0),
+            (1.0, -1.0),
+            (f64::NAN, 1.0),
+        ];
+        for &(a, b) in &pairs {
+            assert_eq!(
+                Meters::ulp_diff(&Meters(a), &Meters(b)),
+                f64::ulp_diff(&a, &b)
+            );
+        }
+    }
+
     #[test]
     fn f32_double_nan() {
         assert!(f32::ulp_diff(&f32::NAN, &f32::NAN) == UlpComparisonResult::Nan);
@@ -125,12 +177,12 @@ mod tests {
                 return TestResult::discard();
             }
 
-            let x = unsafe { mem::transmute::<i32, f32>(a) };
-            let y = unsafe { mem::transmute::<i32, f32>(b) };
+            let x = f32::from_bits(a as u32);
+            let y = f32::from_bits(b as u32);
 
             // Discard the input if it's non-finite or has different signs
             if x.is_finite() && y.is_finite() && x.signum() == y.signum() {
-                TestResult::from_bool(f32::ulp_diff(&x, &y) == UlpComparisonResult::Difference((b - a).abs() as u64))
+                TestResult::from_bool(f32::ulp_diff(&x, &y) == UlpComparisonResult::Difference(u64::from((b - a).unsigned_abs())))
             } else {
                 TestResult::discard()
             }
@@ -145,12 +197,12 @@ mod tests {
                 return TestResult::discard();
             }
 
-            let x = unsafe { mem::transmute::<i64, f64>(a) };
-            let y = unsafe { mem::transmute::<i64, f64>(b) };
+            let x = f64::from_bits(a as u64);
+            let y = f64::from_bits(b as u64);
 
             // Discard the input if it's non-finite or has different signs
             if x.is_finite() && y.is_finite() && x.signum() == y.signum() {
-                TestResult::from_bool(f64::ulp_diff(&x, &y) == UlpComparisonResult::Difference((b - a).abs() as u64))
+                TestResult::from_bool(f64::ulp_diff(&x, &y) == UlpComparisonResult::Difference((b - a).unsigned_abs()))
             } else {
                 TestResult::discard()
             }
@@ -159,9 +211,7 @@ mod tests {
 
     quickcheck! {
         fn property_f32_incompatible_signs_yield_corresponding_enum_value(x: f32, y: f32) -> TestResult {
-            if x.signum() == y.signum() {
-                TestResult::discard()
-            } else if x.is_nan() || y.is_nan() {
+            if x.signum() == y.signum() || x.is_nan() || y.is_nan() {
                 TestResult::discard()
             } else {
                 TestResult::from_bool(f32::ulp_diff(&x, &y) == UlpComparisonResult::IncompatibleSigns)
@@ -171,9 +221,7 @@ mod tests {
 
     quickcheck! {
         fn property_f64_incompatible_signs_yield_corresponding_enum_value(x: f64, y: f64) -> TestResult {
-            if x.signum() == y.signum() {
-                TestResult::discard()
-            } else if x.is_nan() || y.is_nan() {
+            if x.signum() == y.signum() || x.is_nan() || y.is_nan() {
                 TestResult::discard()
             } else {
                 TestResult::from_bool(f64::ulp_diff(&x, &y) == UlpComparisonResult::IncompatibleSigns)
@@ -0,0 +1,343 @@
+use matrixcompare::comparators::{ExactElementwiseComparator, Not};
+use matrixcompare::{
+    compare_matrices, compare_matrices_with_options, ComparisonOptions, MatrixComparisonFailure,
+};
+use matrixcompare_core::{Access, DenseAccess, Matrix, SparseAccess};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, AccessCounts,
+    AccessRecorder, MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+/// Returns a copy of the matrix with an exact non-zero count hint.
+fn with_exact_hint(matrix: &MockDenseMatrix<i64>) -> MockDenseMatrix<i64> {
+    let nnz = (0..matrix.rows())
+        .flat_map(|i| (0..matrix.cols()).map(move |j| (i, j)))
+        .filter(|&(i, j)| matrix.get(i, j) != Some(&0))
+        .count();
+    matrix.clone().with_nonzero_count_hint(nnz)
+}
+
+#[test]
+fn dense_dense_iterates_contiguous_slices() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b = mock_matrix![1, 2, 3;
+                         4, 5, 7];
+    let a_recorder = AccessRecorder::new(&a);
+    let b_recorder = AccessRecorder::new(&b);
+
+    let result = compare_matrices(&a_recorder, &b_recorder, &ExactElementwiseComparator);
+    let mismatch = match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch,
+        _ => panic!("Expected mismatched elements"),
+    };
+    assert_eq!(
+        (mismatch.mismatches[0].row, mismatch.mismatches[0].col),
+        (1, 2)
+    );
+
+    // The mock matrix stores its elements contiguously, so no elements need to be fetched
+    for counts in [a_recorder.counts(), b_recorder.counts()] {
+        assert_eq!(counts.as_slice, 1);
+        assert_eq!(counts.fetch_ref, 0);
+        assert_eq!(counts.fetch_single, 0);
+        assert_eq!(counts.fetch_triplets, 0);
+    }
+}
+
+/// A dense matrix that returns a slice of the wrong length.
+struct TruncatedSlice(MockDenseMatrix<i64>);
+
+impl Matrix<i64> for TruncatedSlice {
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn access(&self) -> Access<'_, i64> {
+        Access::Dense(self)
+    }
+}
+
+impl DenseAccess<i64> for TruncatedSlice {
+    fn fetch_single(&self, row: usize, col: usize) -> i64 {
+        self.0.fetch_single(row, col)
+    }
+
+    fn as_slice(&self) -> Option<&[i64]> {
+        self.0.as_slice().map(|slice| &slice[..slice.len() - 1])
+    }
+}
+
+#[test]
+fn dense_dense_ignores_slices_of_wrong_length() {
+    let a = TruncatedSlice(mock_matrix![1, 2, 3;
+                                        4, 5, 6]);
+    let b = mock_matrix![1, 2, 3;
+                         4, 5, 7];
+
+    // If the truncated slice were used, the mismatch in the last element would go unnoticed
+    assert!(compare_matrices(&a, &b, &ExactElementwiseComparator).is_err());
+}
+
+/// A dense matrix that only supports access by value.
+struct ByValue(MockDenseMatrix<i64>);
+
+impl Matrix<i64> for ByValue {
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn access(&self) -> Access<'_, i64> {
+        Access::Dense(self)
+    }
+}
+
+impl DenseAccess<i64> for ByValue {
+    fn fetch_single(&self, row: usize, col: usize) -> i64 {
+        self.0.fetch_single(row, col)
+    }
+}
+
+#[test]
+fn dense_dense_falls_back_to_fetch_single() {
+    let a = ByValue(mock_matrix![1, 2, 3;
+                                 4, 5, 6]);
+    let b = mock_matrix![1, 2, 3;
+                         4, 5, 7];
+    let a_recorder = AccessRecorder::new(&a);
+    let b_recorder = AccessRecorder::new(&b);
+
+    let result = compare_matrices(&a_recorder, &b_recorder, &ExactElementwiseComparator);
+    let mismatch = match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch,
+        _ => panic!("Expected mismatched elements"),
+    };
+    assert_eq!(mismatch.mismatches.len(), 1);
+    assert_eq!(
+        (mismatch.mismatches[0].left, mismatch.mismatches[0].right),
+        (6, 7)
+    );
+
+    // Without a contiguous slice for both matrices, every element is first requested
+    // by reference, and only fetched by value if unavailable
+    assert_eq!(a_recorder.counts().fetch_ref, 6);
+    assert_eq!(a_recorder.counts().fetch_single, 6);
+    assert_eq!(b_recorder.counts().fetch_ref, 6);
+    assert_eq!(b_recorder.counts().fetch_single, 0);
+}
+
+#[test]
+fn sparse_sparse_borrows_triplets_once() {
+    let a = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3)]);
+    let b = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 2)]);
+    let a_recorder = AccessRecorder::new(&a);
+    let b_recorder = AccessRecorder::new(&b);
+
+    let result = compare_matrices(&a_recorder, &b_recorder, &ExactElementwiseComparator);
+    assert!(result.is_err());
+
+    // The mock matrix stores its triplets contiguously, so they need not be fetched by value
+    for counts in [a_recorder.counts(), b_recorder.counts()] {
+        assert_eq!(counts.fetch_single, 0);
+        assert_eq!(counts.fetch_triplets_cow, 1);
+        assert_eq!(counts.fetch_triplets, 0);
+    }
+}
+
+/// A sparse matrix that only supports fetching its triplets by value.
+struct TripletsByValue(MockSparseMatrix<i64>);
+
+impl Matrix<i64> for TripletsByValue {
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn access(&self) -> Access<'_, i64> {
+        Access::Sparse(self)
+    }
+}
+
+impl SparseAccess<i64> for TripletsByValue {
+    fn nnz(&self) -> usize {
+        self.0.nnz()
+    }
+
+    fn fetch_triplets(&self) -> Vec<(usize, usize, i64)> {
+        self.0.fetch_triplets()
+    }
+}
+
+#[test]
+fn sparse_sparse_falls_back_to_fetch_triplets() {
+    let a = TripletsByValue(MockSparseMatrix::from_triplets(
+        2,
+        3,
+        vec![(0, 1, 2), (1, 2, 3)],
+    ));
+    let b = MockSparseMatrix::from_triplets(2, 3, vec![(1, 2, 4), (0, 1, 2)]);
+    let a_recorder = AccessRecorder::new(&a);
+
+    let result = compare_matrices(&a_recorder, &b, &ExactElementwiseComparator);
+    let mismatch = match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch,
+        _ => panic!("Expected mismatched elements"),
+    };
+    assert_eq!(mismatch.mismatches.len(), 1);
+    assert_eq!(
+        (mismatch.mismatches[0].left, mismatch.mismatches[0].right),
+        (3, 4)
+    );
+
+    // The default implementation of the borrowing access returns the triplets by value
+    assert_eq!(a_recorder.counts().fetch_triplets_cow, 1);
+    assert_eq!(a_recorder.counts().fetch_triplets, 0);
+}
+
+#[test]
+fn dense_sparse_access_counts() {
+    let dense = mock_matrix![0, 2, 0;
+                             0, 0, 3];
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3)]);
+    let dense_recorder = AccessRecorder::new(&dense);
+    let sparse_recorder = AccessRecorder::new(&sparse);
+
+    let result = compare_matrices(
+        &dense_recorder,
+        &sparse_recorder,
+        &ExactElementwiseComparator,
+    );
+    assert!(result.is_ok());
+
+    assert_eq!(dense_recorder.counts().fetch_single, 6);
+    assert_eq!(sparse_recorder.counts().fetch_triplets_cow, 1);
+    assert_eq!(sparse_recorder.counts().fetch_triplets, 0);
+}
+
+#[test]
+fn recorder_counts_dimension_queries() {
+    let a = mock_matrix![1, 2];
+    let recorder = AccessRecorder::new(&a);
+    assert_eq!(recorder.counts(), AccessCounts::default());
+
+    let _ = compare_matrices(&recorder, &a, &ExactElementwiseComparator);
+    let counts = recorder.counts();
+    assert!(counts.rows >= 1);
+    assert!(counts.cols >= 1);
+}
+
+#[test]
+fn empty_matrices_are_not_accessed() {
+    let dense_empty = vec![
+        MockDenseMatrix::<i64>::from_row_major(0, 0, vec![]),
+        MockDenseMatrix::from_row_major(0, 3, vec![]),
+        MockDenseMatrix::from_row_major(3, 0, vec![]),
+    ];
+    for a in &dense_empty {
+        let sparse = MockSparseMatrix::from_triplets(a.rows(), a.cols(), vec![]);
+        let a_recorder = AccessRecorder::new(a);
+        let sparse_recorder = AccessRecorder::new(&sparse);
+        let comp = ExactElementwiseComparator;
+        let options = ComparisonOptions::new().reject_nan(true);
+
+        assert!(compare_matrices(&a_recorder, &a_recorder, &comp).is_ok());
+        assert!(compare_matrices(&a_recorder, &sparse_recorder, &comp).is_ok());
+        assert!(compare_matrices(&sparse_recorder, &sparse_recorder, &comp).is_ok());
+        assert!(compare_matrices_with_options(&a_recorder, &a_recorder, &comp, &options).is_ok());
+
+        for counts in [a_recorder.counts(), sparse_recorder.counts()] {
+            assert_eq!(counts.as_slice, 0);
+            assert_eq!(counts.fetch_ref, 0);
+            assert_eq!(counts.fetch_single, 0);
+            assert_eq!(counts.fetch_triplets, 0);
+            assert_eq!(counts.fetch_triplets_cow, 0);
+        }
+    }
+
+    // Entries stored in an empty sparse matrix are still reported as out of bounds
+    let a = MockDenseMatrix::<i64>::from_row_major(0, 2, vec![]);
+    let sparse = MockSparseMatrix::from_triplets(0, 2, vec![(0, 0, 1)]);
+    assert!(compare_matrices(&a, &sparse, &ExactElementwiseComparator).is_err());
+}
+
+#[test]
+fn dense_sparse_with_hint_only_fetches_sparse_coordinates() {
+    let dense = mock_matrix![0, 2, 0;
+                             0, 0, 3]
+    .with_nonzero_count_hint(2);
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 4)]);
+    let dense_recorder = AccessRecorder::new(&dense);
+
+    let result = compare_matrices(&dense_recorder, &sparse, &ExactElementwiseComparator);
+    assert!(result.is_err());
+    assert_eq!(dense_recorder.counts().fetch_single, 2);
+}
+
+#[test]
+fn dense_sparse_with_hint_falls_back_to_full_scan() {
+    // The non-zero at (1, 0) is not covered by the sparse matrix
+    let dense = mock_matrix![0, 2, 0;
+                             5, 0, 3]
+    .with_nonzero_count_hint(3);
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 2), (1, 2, 3)]);
+    let dense_recorder = AccessRecorder::new(&dense);
+
+    let result = compare_matrices(&dense_recorder, &sparse, &ExactElementwiseComparator);
+    assert_eq!(
+        result,
+        compare_matrices(&dense, &sparse, &ExactElementwiseComparator)
+    );
+    assert!(result.is_err());
+    assert_eq!(dense_recorder.counts().fetch_single, 2 + 6);
+}
+
+#[test]
+fn dense_sparse_with_hint_full_scan_if_zeros_mismatch() {
+    // The negated exact comparator considers two zeros a mismatch, so every element
+    // must be visited
+    let dense = mock_matrix![0, 2;
+                             0, 0]
+    .with_nonzero_count_hint(1);
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 3)]);
+    let dense_recorder = AccessRecorder::new(&dense);
+
+    let comparator = Not(ExactElementwiseComparator);
+    let result = compare_matrices(&dense_recorder, &sparse, &comparator);
+    assert_eq!(result, compare_matrices(&dense, &sparse, &comparator));
+    assert_eq!(dense_recorder.counts().fetch_single, 4);
+}
+
+proptest! {
+    #[test]
+    fn dense_sparse_with_hint_gives_identical_results(
+        dense in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        sparse in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        limit in proptest::option::of(1usize..4)
+    ) {
+        let hinted = with_exact_hint(&dense);
+        let c = ExactElementwiseComparator;
+        let options = ComparisonOptions {
+            early_exit_after: limit,
+            ..ComparisonOptions::default()
+        };
+        prop_assert_eq!(compare_matrices_with_options(&hinted, &sparse, &c, &options),
+                        compare_matrices_with_options(&dense, &sparse, &c, &options));
+        prop_assert_eq!(compare_matrices_with_options(&sparse, &hinted, &c, &options),
+                        compare_matrices_with_options(&sparse, &dense, &c, &options));
+    }
+}
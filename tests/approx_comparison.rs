@@ -0,0 +1,114 @@
+#![cfg(feature = "approx")]
+
+use approx::{AbsDiffEq, RelativeEq};
+use matrixcompare::comparators::ApproxComparator;
+use matrixcompare::{assert_matrix_eq, assert_scalar_eq, assert_slice_eq, compare_matrices};
+use matrixcompare_mock::mock_matrix;
+
+use pretty_assertions::assert_eq;
+
+/// A type that only supports approximate comparison through the `approx` traits.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Meters(f64);
+
+impl std::fmt::Display for Meters {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} m", self.0)
+    }
+}
+
+impl num::Zero for Meters {
+    fn zero() -> Self {
+        Meters(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
+
+impl std::ops::Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+impl AbsDiffEq for Meters {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        1e-9
+    }
+
+    fn abs_diff_eq(&self, other: &Meters, epsilon: f64) -> bool {
+        self.0.abs_diff_eq(&other.0, epsilon)
+    }
+}
+
+impl RelativeEq for Meters {
+    fn default_max_relative() -> f64 {
+        1e-6
+    }
+
+    fn relative_eq(&self, other: &Meters, epsilon: f64, max_relative: f64) -> bool {
+        self.0.relative_eq(&other.0, epsilon, max_relative)
+    }
+}
+
+#[test]
+fn approx_macro_arms() {
+    let x = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let y = mock_matrix![1.0 + f64::EPSILON, 2.0; 3.0, 4.0];
+    assert_matrix_eq!(x, y, comp = approx);
+    assert_matrix_eq!(x, y, comp = approx, epsilon = 1e-12);
+    assert_matrix_eq!(x, y, comp = approx, max_relative = 1e-12, epsilon = 0.0);
+    assert_matrix_eq!(x, y, comp = approx, epsilon = 0.0, max_relative = 1e-12);
+
+    assert_scalar_eq!(1.0, 1.0 + f64::EPSILON, comp = approx);
+    assert_scalar_eq!(1.0, 1.1, comp = approx, max_relative = 0.1);
+    assert_slice_eq!([1.0, 2.0], [1.0 + f64::EPSILON, 2.0], comp = approx);
+    assert_slice_eq!([1.0, 2.0], [1.0, 2.1], comp = approx, epsilon = 0.2);
+}
+
+#[test]
+#[should_panic]
+fn approx_macro_mismatch() {
+    let x = mock_matrix![1.0, 2.0];
+    let y = mock_matrix![1.0, 2.1];
+    assert_matrix_eq!(x, y, comp = approx, max_relative = 1e-3);
+}
+
+#[test]
+fn approx_comparator_uses_type_defaults() {
+    let x = mock_matrix![Meters(1.0), Meters(2.0)];
+    let y = mock_matrix![Meters(1.0), Meters(2.0 + 1e-7)];
+    let z = mock_matrix![Meters(1.0), Meters(2.1)];
+
+    let comp = ApproxComparator::<Meters>::default();
+    assert_eq!(comp.epsilon, 1e-9);
+    assert_eq!(comp.max_relative, 1e-6);
+    assert!(compare_matrices(&x, &y, &comp).is_ok());
+    assert!(compare_matrices(&x, &z, &comp).is_err());
+    assert!(compare_matrices(&x, &z, &comp.max_relative(0.1)).is_ok());
+    assert_matrix_eq!(x, y, comp = approx);
+}
+
+#[test]
+fn approx_mismatch_output() {
+    let x = mock_matrix![1.0, 2.0];
+    let y = mock_matrix![1.0, 2.5];
+    let comp = ApproxComparator::default().epsilon(0.0).max_relative(0.1);
+    let err = compare_matrices(&x, &y, &comp).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 2 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 1): x = 2, y = 2.5.
+
+Comparison criterion: approx::RelativeEq with epsilon = 0 and max_relative = 0.1."
+    );
+}
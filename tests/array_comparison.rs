@@ -0,0 +1,57 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{assert_matrix_eq, compare_matrices, MatrixComparisonFailure};
+use matrixcompare_core::Matrix;
+use matrixcompare_mock::mock_matrix;
+
+#[test]
+fn arrays_have_expected_dimensions() {
+    let a = [[1, 2, 3], [4, 5, 6]];
+    assert_eq!(a.rows(), 2);
+    assert_eq!(a.cols(), 3);
+
+    let no_rows: [[i64; 3]; 0] = [];
+    assert_eq!(no_rows.rows(), 0);
+    assert_eq!(no_rows.cols(), 3);
+
+    let no_cols: [[i64; 0]; 2] = [[], []];
+    assert_eq!(no_cols.rows(), 2);
+    assert_eq!(no_cols.cols(), 0);
+}
+
+#[test]
+fn arrays_compare_elementwise() {
+    let comp = AbsoluteElementwiseComparator { tol: 0.05 };
+    assert!(compare_matrices([[1.0, 2.0]], [[1.0, 2.01]], &comp).is_ok());
+
+    let result = compare_matrices([[1.0, 2.0]], [[1.0, 2.1]], &comp);
+    match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert_eq!(mismatch.mismatches.len(), 1);
+            assert_eq!(mismatch.mismatches[0].row, 0);
+            assert_eq!(mismatch.mismatches[0].col, 1);
+        }
+        _ => panic!("Expected mismatched elements, got {:?}", result),
+    }
+}
+
+#[test]
+fn arrays_compare_with_other_matrices() {
+    let a = [[1, 2], [3, 4]];
+    let b = mock_matrix![1, 2;
+                         3, 4];
+    assert_matrix_eq!(a, b);
+    assert_matrix_eq!(b, a, comp = exact);
+}
+
+#[test]
+fn empty_arrays_compare_by_dimensions() {
+    let no_rows: [[i64; 3]; 0] = [];
+    let no_cols: [[i64; 0]; 3] = [[], [], []];
+    let also_no_rows: [[i64; 3]; 0] = [];
+
+    assert!(compare_matrices(no_rows, also_no_rows, &ExactElementwiseComparator).is_ok());
+    assert!(matches!(
+        compare_matrices(no_rows, no_cols, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::MismatchedDimensions(_))
+    ));
+}
@@ -0,0 +1,55 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, AbsoluteError};
+use matrixcompare::{assert_matrix_eq, assert_scalar_eq, compare_matrices, compare_scalars};
+use matrixcompare_mock::MockDenseMatrix;
+use num::{BigInt, BigRational};
+
+fn big(value: i64) -> BigInt {
+    BigInt::from(value)
+}
+
+fn ratio(numer: i64, denom: i64) -> BigRational {
+    BigRational::new(big(numer), big(denom))
+}
+
+#[test]
+fn bigint_exact_and_abs() {
+    // Exceeds the range of i64
+    let huge = big(i64::MAX) * big(1000);
+    let x = MockDenseMatrix::from_row_major(1, 2, vec![huge.clone(), big(2)]);
+    let y = MockDenseMatrix::from_row_major(1, 2, vec![huge.clone() + big(3), big(2)]);
+
+    assert_matrix_eq!(x, x);
+    assert_matrix_eq!(x, y, comp = abs, tol = big(3));
+
+    let comp = AbsoluteElementwiseComparator { tol: big(2) };
+    let failure = compare_matrices(&x, &y, &comp).unwrap_err();
+    assert!(failure.to_string().contains("Absolute error: 3."));
+}
+
+#[test]
+fn bigrational_exact_and_abs() {
+    let x = MockDenseMatrix::from_row_major(2, 1, vec![ratio(1, 3), ratio(2, 3)]);
+    let y = MockDenseMatrix::from_row_major(2, 1, vec![ratio(1, 3), ratio(3, 4)]);
+
+    assert_matrix_eq!(y, y, comp = exact);
+    assert_matrix_eq!(x, y, comp = abs, tol = ratio(1, 12));
+
+    let comp = AbsoluteElementwiseComparator { tol: ratio(1, 13) };
+    assert!(compare_matrices(&x, &y, &comp).is_err());
+}
+
+#[test]
+fn bigint_scalars() {
+    assert_scalar_eq!(big(-5), big(-5));
+    assert_scalar_eq!(big(-5), big(-3), comp = abs, tol = big(2));
+
+    let comp = AbsoluteElementwiseComparator { tol: big(1) };
+    let failure = compare_scalars(&big(-5), &big(-3), &comp).unwrap_err();
+    assert_eq!(failure.error, AbsoluteError(big(2)));
+}
+
+#[test]
+fn bigrational_scalars() {
+    assert_scalar_eq!(ratio(1, 2), ratio(2, 4));
+    assert_scalar_eq!(ratio(1, 2), ratio(1, 3), comp = abs, tol = ratio(1, 6));
+}
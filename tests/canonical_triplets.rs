@@ -0,0 +1,69 @@
+use matrixcompare::{
+    canonical_triplets, DuplicateEntries, Entry, Matrix, MatrixComparisonFailure,
+    OutOfBoundsEntries,
+};
+use matrixcompare_mock::{sparse_matrix_strategy_i64, MockSparseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn canonical_triplets_are_sorted() {
+    let m = MockSparseMatrix::from_triplets(3, 2, vec![(2, 0, 1), (0, 1, 2), (1, 1, 0), (0, 0, 3)]);
+
+    let expected = vec![(0, 0, 3), (0, 1, 2), (1, 1, 0), (2, 0, 1)];
+    for &duplicates in &[DuplicateEntries::Reject, DuplicateEntries::Sum] {
+        assert_eq!(canonical_triplets(&m, duplicates), Ok(expected.clone()));
+    }
+}
+
+#[test]
+fn canonical_triplets_reject_duplicates() {
+    let m = MockSparseMatrix::from_triplets(2, 2, vec![(1, 1, 1), (0, 1, 2), (1, 1, 3)]);
+
+    let expected = MatrixComparisonFailure::DuplicateSparseEntry(Entry::Left((1, 1)));
+    assert_eq!(
+        canonical_triplets(&m, DuplicateEntries::Reject),
+        Err(expected)
+    );
+}
+
+#[test]
+fn canonical_triplets_sum_duplicates() {
+    let m = MockSparseMatrix::from_triplets(2, 2, vec![(1, 1, 1), (0, 1, 2), (1, 1, 3)]);
+
+    assert_eq!(
+        canonical_triplets(&m, DuplicateEntries::Sum),
+        Ok(vec![(0, 1, 2), (1, 1, 4)])
+    );
+}
+
+#[test]
+fn canonical_triplets_report_out_of_bounds_entries() {
+    let m = MockSparseMatrix::from_triplets(2, 2, vec![(2, 0, 1), (1, 1, 2), (1, 1, 3), (0, 5, 4)]);
+
+    let expected = MatrixComparisonFailure::SparseEntriesOutOfBounds(OutOfBoundsEntries {
+        left: vec![(0, 5), (2, 0)],
+        right: vec![],
+    });
+    for &duplicates in &[DuplicateEntries::Reject, DuplicateEntries::Sum] {
+        assert_eq!(canonical_triplets(&m, duplicates), Err(expected.clone()));
+    }
+}
+
+proptest! {
+    #[test]
+    fn canonical_triplets_are_independent_of_order(
+        m in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let (rows, cols) = (m.rows(), m.cols());
+        let mut reversed = m.clone().take_triplets();
+        reversed.reverse();
+        let reversed = MockSparseMatrix::from_triplets(rows, cols, reversed);
+
+        let canonical = canonical_triplets(&m, DuplicateEntries::Reject).unwrap();
+        prop_assert!(canonical.windows(2).all(|w| (w[0].0, w[0].1) < (w[1].0, w[1].1)));
+        prop_assert_eq!(canonical_triplets(&reversed, DuplicateEntries::Reject), Ok(canonical));
+    }
+}
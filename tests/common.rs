@@ -1,69 +1,57 @@
-use matrixcompare::{
-    DimensionMismatch, ElementsMismatch, Entry, MatrixComparisonFailure,
-    MatrixElementComparisonFailure,
-};
+// Each test file includes this module, but not every test file uses every helper
+#![allow(dead_code)]
+
+use matrixcompare::{Entry, MatrixComparisonFailure, OutOfBoundsEntries};
 use std::ops::Range;
 
 pub const MATRIX_DIM_RANGE: Range<usize> = 0..5;
 
-/// Reverses a comparison result.
+/// Reverses the role of left and right in a comparison result.
 ///
-/// See docs for `reverse_failure`.
+/// Only used for testing that comparison is symmetric.
+/// It is implicitly assumed that the error metric is symmetric.
 pub fn reverse_result<T, E>(
     result: Result<(), MatrixComparisonFailure<T, E>>,
 ) -> Result<(), MatrixComparisonFailure<T, E>> {
-    result.map_err(|err| reverse_failure(err))
+    result.map_err(MatrixComparisonFailure::reverse)
 }
 
-/// Reverses the role of left and right in the given failure object.
+/// Removes the number of stored entries from a comparison result.
 ///
-/// Only used for testing that comparison is symmetric.
-/// It is implicitly assumed that the error metric is symmetric.
-pub fn reverse_failure<T, E>(
-    failure: MatrixComparisonFailure<T, E>,
-) -> MatrixComparisonFailure<T, E> {
-    use MatrixComparisonFailure::*;
-    match failure {
-        MismatchedDimensions(dim) => MismatchedDimensions(reverse_dimension_mismatch(dim)),
-        MismatchedElements(elements) => MismatchedElements(reverse_elements_mismatch(elements)),
-        SparseEntryOutOfBounds(entry) => SparseEntryOutOfBounds(reverse_entry(entry)),
-        DuplicateSparseEntry(entry) => SparseEntryOutOfBounds(reverse_entry(entry)),
-    }
-}
-
-fn reverse_dimension_mismatch(dims: DimensionMismatch) -> DimensionMismatch {
-    DimensionMismatch {
-        dim_left: dims.dim_right,
-        dim_right: dims.dim_left,
-    }
-}
-
-fn reverse_elements_mismatch<T, E>(mismatch: ElementsMismatch<T, E>) -> ElementsMismatch<T, E> {
-    ElementsMismatch {
-        comparator_description: mismatch.comparator_description,
-        mismatches: mismatch
-            .mismatches
-            .into_iter()
-            .map(reverse_matrix_element_comparison_failure)
-            .collect(),
-    }
-}
-
-fn reverse_matrix_element_comparison_failure<T, E>(
-    failure: MatrixElementComparisonFailure<T, E>,
-) -> MatrixElementComparisonFailure<T, E> {
-    MatrixElementComparisonFailure {
-        left: failure.right,
-        right: failure.left,
-        error: failure.error,
-        row: failure.row,
-        col: failure.col,
-    }
+/// The number is only reported if both matrices are sparse, so this is used for checking that
+/// sparse matrices otherwise compare the same as their dense counterparts.
+pub fn without_nnz<T, E>(
+    result: Result<(), MatrixComparisonFailure<T, E>>,
+) -> Result<(), MatrixComparisonFailure<T, E>> {
+    result.map_err(|failure| match failure {
+        MatrixComparisonFailure::MismatchedElements(mut mismatch) => {
+            mismatch.nnz = None;
+            MatrixComparisonFailure::MismatchedElements(mismatch)
+        }
+        failure => failure,
+    })
 }
 
-fn reverse_entry(entry: Entry) -> Entry {
-    match entry {
-        Entry::Left(coord) => Entry::Right(coord),
-        Entry::Right(coord) => Entry::Left(coord),
+/// Collects the out-of-bounds entries of a failure, regardless of how many there are.
+///
+/// Returns `None` if the failure does not concern out-of-bounds entries.
+pub fn out_of_bounds_entries<T, E>(
+    failure: MatrixComparisonFailure<T, E>,
+) -> Option<OutOfBoundsEntries> {
+    match failure {
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Left(coord)) => {
+            Some(OutOfBoundsEntries {
+                left: vec![coord],
+                right: vec![],
+            })
+        }
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Right(coord)) => {
+            Some(OutOfBoundsEntries {
+                left: vec![],
+                right: vec![coord],
+            })
+        }
+        MatrixComparisonFailure::SparseEntriesOutOfBounds(entries) => Some(entries),
+        _ => None,
     }
 }
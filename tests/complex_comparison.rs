@@ -0,0 +1,38 @@
+#![cfg(feature = "num-complex")]
+
+use matrixcompare::comparators::ComplexUlpComparator;
+use matrixcompare::compare_matrices;
+use matrixcompare_mock::mock_matrix;
+use num::complex::Complex;
+
+use pretty_assertions::assert_eq;
+
+fn c(re: f64, im: f64) -> Complex<f64> {
+    Complex::new(re, im)
+}
+
+#[test]
+fn bitwise_identical_fft_output() {
+    let a = mock_matrix![c(1.0, 0.0), c(0.5, -0.5); c(0.0, 1e-300), c(-2.0, 3.0)];
+    let comp = ComplexUlpComparator { tol: 0 };
+    assert!(compare_matrices(&a, &a, &comp).is_ok());
+}
+
+#[test]
+fn mismatched_complex_elements() {
+    let next = |v: f64| f64::from_bits(v.to_bits() + 3);
+    let a = mock_matrix![c(1.0, 2.0), c(0.5, -0.5)];
+    let b = mock_matrix![c(1.0, next(2.0)), c(0.5, -0.5)];
+
+    let err = compare_matrices(&a, &b, &ComplexUlpComparator { tol: 2 }).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 2 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 0): x = 1+2i, y = 1+2.0000000000000013i. Imaginary part: Difference: 3 ULP. Absolute difference: 0.0000000000000013322676295501878.
+
+Comparison criterion: ULP difference of both the real and imaginary parts less than or equal to 2. See documentation for details."
+    );
+}
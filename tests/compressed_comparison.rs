@@ -0,0 +1,289 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_head, compare_matrices, compare_sparsity_patterns, Access, CompressedStorageError,
+    CscAccess, CsrAccess, Entry, MalformedCompressedStorage, Matrix, MatrixComparisonFailure,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockCscMatrix,
+    MockCsrMatrix, MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+fn to_csr<T: Clone>(sparse: &MockSparseMatrix<T>) -> MockCsrMatrix<T> {
+    let (r, c) = (sparse.rows(), sparse.cols());
+    MockCsrMatrix::from_triplets(r, c, sparse.clone().take_triplets())
+}
+
+fn to_csc<T: Clone>(sparse: &MockSparseMatrix<T>) -> MockCscMatrix<T> {
+    let (r, c) = (sparse.rows(), sparse.cols());
+    MockCscMatrix::from_triplets(r, c, sparse.clone().take_triplets())
+}
+
+#[test]
+fn csr_csc_dense_mismatches() {
+    use matrixcompare::MatrixComparisonFailure::MismatchedElements;
+
+    let dense = mock_matrix![1, 0, 3;
+                             0, 5, 0];
+    let triplets = vec![(0, 0, 1), (0, 2, 3), (1, 1, 4), (1, 2, 2)];
+    let csr = MockCsrMatrix::from_triplets(2, 3, triplets.clone());
+    let csc = MockCscMatrix::from_triplets(2, 3, triplets);
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices(&csr, &csc, &c).is_ok());
+
+    for result in [
+        compare_matrices(&dense, &csr, &c),
+        compare_matrices(&dense, &csc, &c),
+    ] {
+        match result {
+            Err(MismatchedElements(mismatch)) => {
+                let coords: Vec<_> = mismatch.mismatches.iter().map(|m| (m.row, m.col)).collect();
+                assert_eq!(coords, vec![(1, 1), (1, 2)]);
+            }
+            _ => panic!("Unexpected result"),
+        }
+    }
+}
+
+#[test]
+fn csr_column_out_of_bounds() {
+    let csr = MockCsrMatrix::from_triplets(2, 3, vec![(0, 1, 1), (1, 3, 2)]);
+    let dense = mock_matrix![0, 1, 0;
+                             0, 0, 0];
+
+    assert_eq!(
+        compare_matrices(&dense, &csr, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Right((1, 3))
+        ))
+    );
+}
+
+#[test]
+fn csc_row_out_of_bounds() {
+    let csc = MockCscMatrix::from_triplets(2, 3, vec![(0, 1, 1), (2, 2, 2)]);
+    let dense = mock_matrix![0, 1, 0;
+                             0, 0, 0];
+
+    assert_eq!(
+        compare_matrices(&csc, &dense, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Left((2, 2))
+        ))
+    );
+}
+
+#[test]
+fn compressed_huge_minor_index_out_of_bounds() {
+    let csr = MockCsrMatrix::from_triplets(2, 3, vec![(0, 1, 1), (1, usize::MAX, 2)]);
+    let csc = MockCscMatrix::from_triplets(2, 3, vec![(0, 1, 1), (usize::MAX, 2, 2)]);
+    let dense = mock_matrix![0, 1, 0;
+                             0, 0, 0];
+
+    assert_eq!(
+        compare_matrices(&dense, &csr, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Right((1, usize::MAX))
+        ))
+    );
+    assert_eq!(
+        compare_matrices(&csc, &dense, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Left((usize::MAX, 2))
+        ))
+    );
+}
+
+#[test]
+fn csr_duplicate_entry() {
+    let csr = MockCsrMatrix::from_triplets(2, 3, vec![(1, 1, 1), (1, 1, 2)]);
+    let coo = MockSparseMatrix::from_triplets(2, 3, vec![]);
+
+    assert_eq!(
+        compare_matrices(&coo, &csr, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::DuplicateSparseEntry(Entry::Right(
+            (1, 1)
+        )))
+    );
+}
+
+/// A compressed matrix with raw, possibly inconsistent, offsets and indices.
+struct RawCompressed {
+    rows: usize,
+    cols: usize,
+    offsets: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<i64>,
+    column_major: bool,
+}
+
+impl Matrix<i64> for RawCompressed {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn access(&self) -> Access<'_, i64> {
+        if self.column_major {
+            Access::Csc(self)
+        } else {
+            Access::Csr(self)
+        }
+    }
+}
+
+impl CsrAccess<i64> for RawCompressed {
+    fn row_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    fn column_indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    fn values(&self) -> &[i64] {
+        &self.values
+    }
+}
+
+impl CscAccess<i64> for RawCompressed {
+    fn col_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    fn row_indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    fn values(&self) -> &[i64] {
+        &self.values
+    }
+}
+
+fn raw_compressed(offsets: Vec<usize>, indices: Vec<usize>, values: Vec<i64>) -> RawCompressed {
+    RawCompressed {
+        rows: 2,
+        cols: 3,
+        offsets,
+        indices,
+        values,
+        column_major: false,
+    }
+}
+
+#[test]
+fn malformed_compressed_storage_is_reported() {
+    let dense = mock_matrix![0, 1, 0;
+                             0, 0, 0];
+    let c = ExactElementwiseComparator;
+    let malformed = |is_left, error| {
+        MatrixComparisonFailure::MalformedCompressedStorage(MalformedCompressedStorage {
+            is_left,
+            error,
+        })
+    };
+
+    let too_few_offsets = raw_compressed(vec![0, 1], vec![1], vec![1]);
+    let error = CompressedStorageError::OffsetCount {
+        expected: 3,
+        actual: 2,
+    };
+    assert_eq!(
+        compare_matrices(&too_few_offsets, &dense, &c),
+        Err(malformed(true, error))
+    );
+    assert_eq!(
+        compare_matrices(&dense, &too_few_offsets, &c),
+        Err(malformed(false, error))
+    );
+
+    let decreasing = raw_compressed(vec![0, 2, 1], vec![1, 0], vec![1, 0]);
+    let error = CompressedStorageError::DecreasingOffsets { position: 2 };
+    assert_eq!(
+        compare_matrices(&decreasing, &dense, &c),
+        Err(malformed(true, error))
+    );
+
+    let mut too_many_values = raw_compressed(vec![0, 1, 1, 1], vec![1], vec![1, 2]);
+    too_many_values.column_major = true;
+    let error = CompressedStorageError::LengthMismatch {
+        nnz: 1,
+        indices: 1,
+        values: 2,
+    };
+    assert_eq!(
+        compare_matrices(&dense, &too_many_values, &c),
+        Err(malformed(false, error))
+    );
+    assert_eq!(
+        compare_head(&dense, &too_many_values, 1, &c),
+        Err(malformed(false, error))
+    );
+    assert_eq!(
+        compare_sparsity_patterns(&dense, &too_many_values),
+        Err(MatrixComparisonFailure::MalformedCompressedStorage(
+            MalformedCompressedStorage {
+                is_left: false,
+                error
+            }
+        ))
+    );
+}
+
+#[test]
+fn malformed_compressed_storage_is_displayed() {
+    let dense = mock_matrix![0, 1, 0;
+                             0, 0, 0];
+    let csr = raw_compressed(vec![0, 1, 3], vec![1, 2], vec![1, 2]);
+
+    let failure = compare_matrices(&dense, &csr, &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(
+        failure.to_string(),
+        "Matrix Y (right) has malformed compressed sparse storage: the last offset is 3, \
+         but there are 2 indices and 2 values."
+    );
+}
+
+fn same_size_dense_sparse_matrices(
+) -> impl Strategy<Value = (MockDenseMatrix<i64>, MockSparseMatrix<i64>)> {
+    (MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|(r, c)| {
+        (
+            dense_matrix_strategy_i64(Just(r), Just(c)),
+            sparse_matrix_strategy_i64(Just(r), Just(c)),
+        )
+    })
+}
+
+proptest! {
+    #[test]
+    fn compressed_formats_compare_the_same_as_coo_against_dense(
+        (dense, sparse) in same_size_dense_sparse_matrices()
+    ) {
+        let c = ExactElementwiseComparator;
+        let expected = compare_matrices(&dense, &sparse, &c);
+        prop_assert_eq!(compare_matrices(&dense, to_csr(&sparse), &c), expected.clone());
+        prop_assert_eq!(compare_matrices(&dense, to_csc(&sparse), &c), expected.clone());
+        prop_assert_eq!(reverse_result(compare_matrices(to_csr(&sparse), &dense, &c)),
+                        expected);
+    }
+
+    #[test]
+    fn compressed_formats_compare_the_same_as_coo_against_sparse(
+        sparse1 in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        sparse2 in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let expected = compare_matrices(&sparse1, &sparse2, &c);
+        prop_assert_eq!(compare_matrices(to_csr(&sparse1), to_csc(&sparse2), &c),
+                        expected.clone());
+        prop_assert_eq!(compare_matrices(to_csc(&sparse1), &sparse2, &c), expected.clone());
+        prop_assert_eq!(compare_matrices(&sparse1, to_csr(&sparse2), &c), expected);
+    }
+}
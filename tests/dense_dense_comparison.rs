@@ -1,5 +1,7 @@
-use matrixcompare::comparators::{ElementwiseComparator, ExactElementwiseComparator, ExactError};
-use matrixcompare::{assert_matrix_eq, ElementsMismatch};
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, ElementwiseComparator, ExactElementwiseComparator, ExactError,
+};
+use matrixcompare::{assert_matrix_eq, DisplayOptions, ElementsMismatch};
 use matrixcompare::{compare_matrices, DimensionMismatch, MatrixComparisonFailure};
 use matrixcompare_mock::{dense_matrix_strategy_i64, mock_matrix, MockDenseMatrix};
 use quickcheck::{quickcheck, TestResult};
@@ -21,8 +23,8 @@ quickcheck! {
 
         // It does not actually matter which comparator we use here, but we need to pick one
         let comp = ExactElementwiseComparator;
-        let ref x = MockDenseMatrix::from_row_major(m, n, vec![0; m * n]);
-        let ref y = MockDenseMatrix::from_row_major(p, q, vec![0; p * q]);
+        let x = &MockDenseMatrix::from_row_major(m, n, vec![0; m * n]);
+        let y = &MockDenseMatrix::from_row_major(p, q, vec![0; p * q]);
 
         let expected = MatrixComparisonFailure::MismatchedDimensions(
             DimensionMismatch { dim_left: (m, n), dim_right: (p, q) }
@@ -32,10 +34,32 @@ quickcheck! {
     }
 }
 
+#[test]
+fn dimension_mismatch_fields_refer_to_left_and_right() {
+    let x = mock_matrix![1, 2, 3];
+    let y = mock_matrix![1; 2];
+
+    let mismatch = DimensionMismatch {
+        dim_left: (1, 3),
+        dim_right: (2, 1),
+    };
+    let result = compare_matrices(&x, &y, &ExactElementwiseComparator);
+    assert_eq!(
+        result,
+        Err(MatrixComparisonFailure::MismatchedDimensions(mismatch))
+    );
+
+    let reversed = DimensionMismatch {
+        dim_left: (2, 1),
+        dim_right: (1, 3),
+    };
+    assert_eq!(mismatch.reverse(), reversed);
+}
+
 quickcheck! {
     fn property_elementwise_comparison_matrix_matches_self(m: usize, n: usize) -> bool {
         let comp = ExactElementwiseComparator;
-        let ref x = MockDenseMatrix::from_row_major(m, n, vec![0; m * n]);
+        let x = &MockDenseMatrix::from_row_major(m, n, vec![0; m * n]);
 
         compare_matrices(x, x, &comp).is_ok()
     }
@@ -52,10 +76,10 @@ fn compare_matrices_reports_correct_mismatches() {
 
     {
         // Single element matrices
-        let ref x = MockDenseMatrix::from_row_major(1, 1, vec![1]);
-        let ref y = MockDenseMatrix::from_row_major(1, 1, vec![2]);
+        let x = &MockDenseMatrix::from_row_major(1, 1, vec![1]);
+        let y = &MockDenseMatrix::from_row_major(1, 1, vec![2]);
 
-        let expected = MismatchedElements(ElementsMismatch {
+        let expected = MismatchedElements(Box::new(ElementsMismatch {
             comparator_description: description.clone(),
             mismatches: vec![MatrixElementComparisonFailure {
                 left: 1,
@@ -64,15 +88,22 @@ fn compare_matrices_reports_correct_mismatches() {
                 row: 0,
                 col: 0,
             }],
-        });
+            compared_rows: None,
+            statistics: None,
+            symmetric: false,
+            stopped_early: false,
+            dims: Some((1, 1)),
+            display: DisplayOptions::default(),
+            nnz: None,
+        }));
 
         assert_eq!(compare_matrices(x, y, &comp), Err(expected));
     }
 
     {
         // Mismatch in top-left and bottom-corner elements for a short matrix
-        let ref x = MockDenseMatrix::from_row_major(2, 3, vec![0, 1, 2, 3, 4, 5]);
-        let ref y = MockDenseMatrix::from_row_major(2, 3, vec![1, 1, 2, 3, 4, 6]);
+        let x = &MockDenseMatrix::from_row_major(2, 3, vec![0, 1, 2, 3, 4, 5]);
+        let y = &MockDenseMatrix::from_row_major(2, 3, vec![1, 1, 2, 3, 4, 6]);
         let mismatches = vec![
             MatrixElementComparisonFailure {
                 left: 0,
@@ -90,22 +121,29 @@ fn compare_matrices_reports_correct_mismatches() {
             },
         ];
 
-        let expected = MismatchedElements(ElementsMismatch {
+        let expected = MismatchedElements(Box::new(ElementsMismatch {
             comparator_description: description.clone(),
-            mismatches: mismatches,
-        });
+            mismatches,
+            compared_rows: None,
+            statistics: None,
+            symmetric: false,
+            stopped_early: false,
+            dims: Some((2, 3)),
+            display: DisplayOptions::default(),
+            nnz: None,
+        }));
 
         assert_eq!(compare_matrices(x, y, &comp), Err(expected));
     }
 
     {
         // Mismatch in top-left and bottom-corner elements for a tall matrix
-        let ref x = mock_matrix![ 0, 1;
-                                  2, 3;
-                                  4, 5 ];
-        let ref y = mock_matrix![ 1, 1;
-                                  2, 3;
-                                  4, 6 ];
+        let x = &mock_matrix![ 0, 1;
+                               2, 3;
+                               4, 5 ];
+        let y = &mock_matrix![ 1, 1;
+                               2, 3;
+                               4, 6 ];
         let mismatches = vec![
             MatrixElementComparisonFailure {
                 left: 0,
@@ -123,18 +161,25 @@ fn compare_matrices_reports_correct_mismatches() {
             },
         ];
 
-        let expected = MismatchedElements(ElementsMismatch {
+        let expected = MismatchedElements(Box::new(ElementsMismatch {
             comparator_description: description.clone(),
-            mismatches: mismatches,
-        });
+            mismatches,
+            compared_rows: None,
+            statistics: None,
+            symmetric: false,
+            stopped_early: false,
+            dims: Some((3, 2)),
+            display: DisplayOptions::default(),
+            nnz: None,
+        }));
 
         assert_eq!(compare_matrices(x, y, &comp), Err(expected));
     }
 
     {
         // Check some arbitrary elements
-        let ref x = MockDenseMatrix::from_row_major(2, 4, vec![0, 1, 2, 3, 4, 5, 6, 7]);
-        let ref y = MockDenseMatrix::from_row_major(2, 4, vec![0, 1, 3, 3, 4, 6, 6, 7]);
+        let x = &MockDenseMatrix::from_row_major(2, 4, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        let y = &MockDenseMatrix::from_row_major(2, 4, vec![0, 1, 3, 3, 4, 6, 6, 7]);
 
         let mismatches = vec![
             MatrixElementComparisonFailure {
@@ -153,15 +198,41 @@ fn compare_matrices_reports_correct_mismatches() {
             },
         ];
 
-        let expected = MismatchedElements(ElementsMismatch {
+        let expected = MismatchedElements(Box::new(ElementsMismatch {
             comparator_description: description.clone(),
-            mismatches: mismatches,
-        });
+            mismatches,
+            compared_rows: None,
+            statistics: None,
+            symmetric: false,
+            stopped_early: false,
+            dims: Some((2, 4)),
+            display: DisplayOptions::default(),
+            nnz: None,
+        }));
 
         assert_eq!(compare_matrices(x, y, &comp), Err(expected));
     }
 }
 
+#[test]
+fn compare_matrices_accepts_temporaries() {
+    let c = ExactElementwiseComparator;
+    let make_matrix = |last| MockDenseMatrix::from_row_major(2, 2, vec![1, 2, 3, last]);
+
+    assert!(compare_matrices(make_matrix(4), make_matrix(4), &c).is_ok());
+
+    // The failure does not borrow from the temporaries, so it can outlive the statement
+    let failure = compare_matrices(make_matrix(4), make_matrix(5), &c).unwrap_err();
+    match failure {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 1);
+        }
+        _ => panic!("Unexpected variant"),
+    }
+
+    assert_matrix_eq!(make_matrix(4), mock_matrix![1, 2; 3, 4]);
+}
+
 #[test]
 pub fn matrix_eq_absolute_compare_self_for_integer() {
     let x = MockDenseMatrix::from_row_major(2, 3, vec![1, 2, 3, 4, 5, 6]);
@@ -255,6 +326,23 @@ pub fn matrix_eq_float_compare_self_with_ulp() {
     assert_matrix_eq!(x, x, comp = float, ulp = 12);
 }
 
+#[test]
+pub fn matrix_eq_float_flush_subnormals() {
+    let tiny = f64::MIN_POSITIVE / 8.0;
+    let x = mock_matrix![1.0, tiny, -tiny];
+    let y = mock_matrix![1.0, 0.0, 2.0 * tiny];
+    assert_matrix_eq!(x, y, comp = float, eps = 0.0, flush_subnormals = true);
+}
+
+#[test]
+#[should_panic]
+pub fn matrix_eq_float_subnormals_not_flushed_by_default() {
+    let tiny = f64::MIN_POSITIVE / 8.0;
+    let x = mock_matrix![1.0, tiny, -tiny];
+    let y = mock_matrix![1.0, 0.0, 2.0 * tiny];
+    assert_matrix_eq!(x, y, comp = float, eps = 0.0);
+}
+
 #[test]
 pub fn matrix_eq_float_compare_self_with_eps_and_ulp() {
     let x = MockDenseMatrix::from_row_major(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
@@ -262,6 +350,70 @@ pub fn matrix_eq_float_compare_self_with_eps_and_ulp() {
     assert_matrix_eq!(x, x, comp = float, ulp = 12, eps = 1e-6);
 }
 
+#[test]
+pub fn matrix_eq_le_and_ge() {
+    let x = MockDenseMatrix::from_row_major(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let y = MockDenseMatrix::from_row_major(2, 2, vec![1.0, 2.5, 3.0, 4.5]);
+    assert_matrix_eq!(x, y, comp = le, tol = 0.0);
+    assert_matrix_eq!(y, x, comp = ge, tol = 0.0);
+}
+
+#[test]
+#[should_panic]
+pub fn matrix_eq_le_violated() {
+    let x = MockDenseMatrix::from_row_major(2, 2, vec![1, 2, 3, 4]);
+    let y = MockDenseMatrix::from_row_major(2, 2, vec![1, 2, 1, 4]);
+    assert_matrix_eq!(x, y, comp = le, tol = 1);
+}
+
+#[test]
+#[should_panic]
+pub fn matrix_eq_ge_violated() {
+    let x = MockDenseMatrix::from_row_major(2, 2, vec![1, 2, 1, 4]);
+    let y = MockDenseMatrix::from_row_major(2, 2, vec![1, 2, 3, 4]);
+    assert_matrix_eq!(x, y, comp = ge, tol = 1);
+}
+
+#[test]
+pub fn matrix_eq_percent() {
+    let x = MockDenseMatrix::from_row_major(2, 2, vec![100.0, -2.0, 0.0, 1e-20]);
+    let y = MockDenseMatrix::from_row_major(2, 2, vec![105.0, -1.95, 0.0, 0.96e-20]);
+    assert_matrix_eq!(x, y, comp = percent, tol = 5.0);
+    assert_matrix_eq!(y, x, comp = percent, tol = 5.0);
+}
+
+#[test]
+#[should_panic]
+pub fn matrix_eq_percent_violated() {
+    let x = MockDenseMatrix::from_row_major(1, 2, vec![100.0, 2.0]);
+    let y = MockDenseMatrix::from_row_major(1, 2, vec![100.0, 2.2]);
+    assert_matrix_eq!(x, y, comp = percent, tol = 5.0);
+}
+
+#[test]
+pub fn matrix_eq_comparator_value() {
+    let x = MockDenseMatrix::from_row_major(1, 2, vec![100.0, 200.0]);
+    let y = MockDenseMatrix::from_row_major(1, 2, vec![100.1, 199.9]);
+    let comparator = AbsoluteElementwiseComparator { tol: 0.2 };
+    assert_matrix_eq!(x, y, comp = comparator);
+    // The comparator is borrowed, and may be reused
+    assert_matrix_eq!(y, x, comp = comparator, labels = ("y", "x"));
+    assert_matrix_eq!(x, y, comp = AbsoluteElementwiseComparator { tol: 0.5 });
+
+    // Variables named after keywords can be used in parentheses
+    let exact = AbsoluteElementwiseComparator { tol: 0.2 };
+    assert_matrix_eq!(x, y, comp = (exact));
+}
+
+#[test]
+#[should_panic]
+pub fn matrix_eq_comparator_value_violated() {
+    let x = MockDenseMatrix::from_row_major(1, 2, vec![100.0, 200.0]);
+    let y = MockDenseMatrix::from_row_major(1, 2, vec![100.1, 199.9]);
+    let comparator = AbsoluteElementwiseComparator { tol: 0.05 };
+    assert_matrix_eq!(x, y, comp = comparator);
+}
+
 #[test]
 pub fn matrix_eq_pass_by_ref() {
     let x = MockDenseMatrix::from_row_major(1, 1, vec![0.0f64]);
@@ -274,6 +426,9 @@ pub fn matrix_eq_pass_by_ref() {
     assert_matrix_eq!(&x, &x, comp = ulp, tol = 0);
     assert_matrix_eq!(&x, &x, comp = float);
     assert_matrix_eq!(&x, &x, comp = float, eps = 0.0, ulp = 0);
+    assert_matrix_eq!(&x, &x, comp = le, tol = 0.0);
+    assert_matrix_eq!(&x, &x, comp = ge, tol = 0.0);
+    assert_matrix_eq!(&x, &x, comp = percent, tol = 0.0);
 }
 
 proptest! {
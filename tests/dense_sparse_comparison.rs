@@ -9,20 +9,21 @@ use matrixcompare_mock::{
 use proptest::prelude::*;
 
 mod common;
-use common::{reverse_result, MATRIX_DIM_RANGE};
+use common::{out_of_bounds_entries, reverse_result, MATRIX_DIM_RANGE};
 
 #[test]
 fn dense_sparse_index_out_of_bounds() {
-    use MatrixComparisonFailure::SparseEntryOutOfBounds;
-
     macro_rules! assert_out_of_bounds_detected {
         ($dense:expr, $sparse:expr, $oob:expr) => {
             // Dense-sparse
             {
                 let result = compare_matrices(&$dense, &$sparse, &ExactElementwiseComparator);
                 let err = result.unwrap_err();
-                match err {
-                    SparseEntryOutOfBounds(Entry::Right(coord)) => assert!($oob.contains(&coord)),
+                match out_of_bounds_entries(err) {
+                    Some(entries) => {
+                        assert!(entries.left.is_empty());
+                        assert_eq!(entries.right, $oob);
+                    }
                     _ => panic!("Unexpected variant"),
                 }
             }
@@ -31,8 +32,11 @@ fn dense_sparse_index_out_of_bounds() {
             {
                 let result = compare_matrices(&$sparse, &$dense, &ExactElementwiseComparator);
                 let err = result.unwrap_err();
-                match err {
-                    SparseEntryOutOfBounds(Entry::Left(coord)) => assert!($oob.contains(&coord)),
+                match out_of_bounds_entries(err) {
+                    Some(entries) => {
+                        assert_eq!(entries.left, $oob);
+                        assert!(entries.right.is_empty());
+                    }
                     _ => panic!("Unexpected variant"),
                 }
             }
@@ -45,7 +49,7 @@ fn dense_sparse_index_out_of_bounds() {
                                  4, 5, 6];
         let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, -3), (1, 2, 6), (2, 0, 1)]);
 
-        let oob = vec![(2, 0)];
+        let oob = [(2, 0)];
         assert_out_of_bounds_detected!(dense, sparse, oob);
     }
 
@@ -54,7 +58,7 @@ fn dense_sparse_index_out_of_bounds() {
         let dense = mock_matrix![1, 2, 3;
                                  4, 5, 6];
         let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, -3), (1, 3, 1), (1, 2, 6)]);
-        let oob = vec![(1, 3)];
+        let oob = [(1, 3)];
         assert_out_of_bounds_detected!(dense, sparse, oob);
     }
 
@@ -67,7 +71,7 @@ fn dense_sparse_index_out_of_bounds() {
             3,
             vec![(2, 3, 1), (0, 1, -3), (2, 0, 1), (1, 2, 6)],
         );
-        let oob = vec![(2, 0), (2, 3)];
+        let oob = [(2, 0), (2, 3)];
         assert_out_of_bounds_detected!(dense, sparse, oob);
     }
 }
@@ -102,6 +106,80 @@ fn dense_sparse_duplicate_entries() {
     }
 }
 
+#[test]
+fn dense_sparse_huge_coordinates_are_out_of_bounds() {
+    use MatrixComparisonFailure::SparseEntryOutOfBounds;
+
+    // The fast path for dense matrices with a non-zero count hint must also reject the entries
+    let plain = mock_matrix![1, 0, 0;
+                             0, 0, 0];
+    let hinted = plain.clone().with_nonzero_count_hint(1);
+    let huge_coords = vec![(usize::MAX, 0), (0, usize::MAX), (usize::MAX, usize::MAX)];
+    for dense in &[plain, hinted] {
+        for &(i, j) in &huge_coords {
+            let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 1), (i, j, 1)]);
+            assert_eq!(
+                compare_matrices(dense, &sparse, &ExactElementwiseComparator),
+                Err(SparseEntryOutOfBounds(Entry::Right((i, j))))
+            );
+            assert_eq!(
+                compare_matrices(&sparse, dense, &ExactElementwiseComparator),
+                Err(SparseEntryOutOfBounds(Entry::Left((i, j))))
+            );
+        }
+    }
+}
+
+#[test]
+fn sparse_dense_mismatch_values_refer_to_left_and_right() {
+    use matrixcompare::comparators::{AbsoluteElementwiseComparator, AbsoluteError};
+    use matrixcompare::MatrixElementComparisonFailure;
+
+    let comp = AbsoluteElementwiseComparator { tol: 0 };
+    let mismatches = |result| match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch.mismatches,
+        _ => panic!("Unexpected result"),
+    };
+    let element = |row, col, left, right| MatrixElementComparisonFailure {
+        left,
+        right,
+        error: AbsoluteError(i64::abs(left - right)),
+        row,
+        col,
+    };
+
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 5), (1, 0, 3)]);
+
+    // The non-zeros of the dense matrix are not covered by the sparse matrix,
+    // so every element is visited
+    let dense = mock_matrix![0, 7;
+                             3, 1];
+    for dense in &[dense.clone(), dense.with_nonzero_count_hint(3)] {
+        assert_eq!(
+            mismatches(compare_matrices(&sparse, dense, &comp)),
+            vec![element(0, 1, 5, 7), element(1, 1, 0, 1)]
+        );
+        assert_eq!(
+            mismatches(compare_matrices(dense, &sparse, &comp)),
+            vec![element(0, 1, 7, 5), element(1, 1, 1, 0)]
+        );
+    }
+
+    // With a non-zero count hint, only the sparse entries are visited
+    let dense = mock_matrix![0, 7;
+                             3, 0];
+    for dense in &[dense.clone(), dense.with_nonzero_count_hint(2)] {
+        assert_eq!(
+            mismatches(compare_matrices(&sparse, dense, &comp)),
+            vec![element(0, 1, 5, 7)]
+        );
+        assert_eq!(
+            mismatches(compare_matrices(dense, &sparse, &comp)),
+            vec![element(0, 1, 7, 5)]
+        );
+    }
+}
+
 /// A strategy producing pairs of dense and sparse matrices with the same dimensions.
 fn same_size_dense_sparse_matrices(
 ) -> impl Strategy<Value = (MockDenseMatrix<i64>, MockSparseMatrix<i64>)> {
@@ -175,13 +253,13 @@ fn sparse_matrix_out_of_bounds_strategy(
         })
 }
 
-fn dense_sparse_out_of_bounds_pair_strategy() -> impl Strategy<
-    Value = (
-        MockDenseMatrix<i64>,
-        MockSparseMatrix<i64>,
-        Vec<(usize, usize, i64)>,
-    ),
-> {
+type DenseSparseOutOfBoundsPair = (
+    MockDenseMatrix<i64>,
+    MockSparseMatrix<i64>,
+    Vec<(usize, usize, i64)>,
+);
+
+fn dense_sparse_out_of_bounds_pair_strategy() -> impl Strategy<Value = DenseSparseOutOfBoundsPair> {
     sparse_matrix_out_of_bounds_strategy()
         .prop_flat_map(|(sparse, triplets)| {
             let (r, c) = (sparse.rows(), sparse.cols());
@@ -200,14 +278,13 @@ proptest! {
         sparse in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
     ) {
         let c = ExactElementwiseComparator;
-        prop_assert!(compare_matrices(&sparse, &sparse.to_dense().unwrap(), &c).is_ok());
+        prop_assert!(compare_matrices(&sparse, sparse.to_dense()?, &c).is_ok());
     }
 
     #[test]
     fn sparse_and_dense_matrices_indices_out_of_bounds_are_detected(
         (dense, sparse, out_of_bounds_triplets) in dense_sparse_out_of_bounds_pair_strategy()
     ) {
-        use MatrixComparisonFailure::SparseEntryOutOfBounds;
         let c = ExactElementwiseComparator;
 
         let mut out_of_bounds_indices: Vec<_> = out_of_bounds_triplets
@@ -220,9 +297,11 @@ proptest! {
         {
             let result = compare_matrices(&dense, &sparse, &c);
             let err = result.unwrap_err();
-            match err {
-                SparseEntryOutOfBounds(Entry::Right(coord))
-                    => prop_assert!(out_of_bounds_indices.contains(&coord)),
+            match out_of_bounds_entries(err) {
+                Some(entries) => {
+                    prop_assert!(entries.left.is_empty());
+                    prop_assert_eq!(entries.right, out_of_bounds_indices.clone());
+                }
                 _ => prop_assert!(false)
             }
         }
@@ -231,9 +310,11 @@ proptest! {
         {
             let result = compare_matrices(&sparse, &dense, &c);
             let err = result.unwrap_err();
-            match err {
-                SparseEntryOutOfBounds(Entry::Left(coord))
-                    => prop_assert!(out_of_bounds_indices.contains(&coord)),
+            match out_of_bounds_entries(err) {
+                Some(entries) => {
+                    prop_assert_eq!(entries.left, out_of_bounds_indices.clone());
+                    prop_assert!(entries.right.is_empty());
+                }
                 _ => prop_assert!(false)
             }
         }
@@ -246,7 +327,7 @@ proptest! {
     ) {
         let c = ExactElementwiseComparator;
         prop_assert_eq!(compare_matrices(&dense, &sparse, &c),
-                        compare_matrices(&dense, sparse.to_dense().unwrap(), &c));
+                        compare_matrices(&dense, sparse.to_dense()?, &c));
     }
 
     #[test]
@@ -255,7 +336,7 @@ proptest! {
     ) {
         let c = ExactElementwiseComparator;
         prop_assert_eq!(compare_matrices(&dense, &sparse, &c),
-                        compare_matrices(&dense, sparse.to_dense().unwrap(), &c));
+                        compare_matrices(&dense, sparse.to_dense()?, &c));
     }
 
     #[test]
@@ -265,7 +346,7 @@ proptest! {
     ) {
         let c = ExactElementwiseComparator;
         prop_assert_eq!(compare_matrices(&dense, &sparse, &c),
-                        compare_matrices(&dense, sparse.to_dense().unwrap(), &c));
+                        compare_matrices(&dense, sparse.to_dense()?, &c));
     }
 
     #[test]
@@ -0,0 +1,90 @@
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, DynComparator, ExactElementwiseComparator,
+    UlpElementwiseComparator,
+};
+use matrixcompare::{
+    compare_matrices, compare_matrices_dyn, compare_matrices_dyn_comparator, Matrix,
+    MatrixComparisonFailure,
+};
+use matrixcompare_mock::{mock_matrix, MockCsrMatrix, MockSparseMatrix};
+
+fn heterogeneous_matrices() -> Vec<Box<dyn Matrix<i32>>> {
+    vec![
+        Box::new(mock_matrix![0, 2;
+                              3, 0]),
+        Box::new(MockSparseMatrix::from_triplets(
+            2,
+            2,
+            vec![(0, 1, 2), (1, 0, 3)],
+        )),
+        Box::new(MockCsrMatrix::from_triplets(
+            2,
+            2,
+            vec![(0, 1, 2), (1, 0, 3)],
+        )),
+        Box::new(mock_matrix![0, 2;
+                              4, 0]),
+    ]
+}
+
+#[test]
+fn compare_boxed_matrices_pairwise() {
+    let matrices = heterogeneous_matrices();
+    let c = ExactElementwiseComparator;
+
+    for (i, left) in matrices.iter().enumerate() {
+        for (j, right) in matrices.iter().enumerate() {
+            let expected_equal = (i == 3) == (j == 3);
+            let result = compare_matrices_dyn(left.as_ref(), right.as_ref(), &c);
+            assert_eq!(result.is_ok(), expected_equal, "Pair ({}, {})", i, j);
+        }
+    }
+}
+
+#[test]
+fn compare_matrices_accepts_trait_object_references() {
+    let matrices = heterogeneous_matrices();
+    let left: &dyn Matrix<i32> = matrices[0].as_ref();
+    let right: &dyn Matrix<i32> = matrices[2].as_ref();
+    assert!(compare_matrices(left, right, &ExactElementwiseComparator).is_ok());
+}
+
+fn comparator_from_spec(spec: &str) -> Box<dyn DynComparator<f64>> {
+    match spec {
+        "exact" => Box::new(ExactElementwiseComparator),
+        "abs" => Box::new(AbsoluteElementwiseComparator { tol: 0.5 }),
+        "ulp" => Box::new(UlpElementwiseComparator { tol: 4 }),
+        _ => panic!("Unknown comparator"),
+    }
+}
+
+#[test]
+fn comparator_selected_at_runtime() {
+    let a = mock_matrix![1.0, 2.0];
+    let b = mock_matrix![1.0, 2.25];
+
+    for &(spec, expected_equal) in &[("exact", false), ("abs", true), ("ulp", false)] {
+        let comparator = comparator_from_spec(spec);
+        let result = compare_matrices_dyn_comparator(&a, &b, comparator.as_ref());
+        assert_eq!(result.is_ok(), expected_equal, "Comparator {}", spec);
+    }
+}
+
+#[test]
+fn dyn_comparator_failure_matches_static_comparator() {
+    let a = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let b = mock_matrix![1.0, 3.0; 3.0, 5.0];
+    let c = AbsoluteElementwiseComparator { tol: 0.5 };
+
+    let dynamic = compare_matrices_dyn_comparator(&a, &b, &c).unwrap_err();
+    let expected = compare_matrices(&a, &b, &c).unwrap_err();
+    assert_eq!(dynamic.to_string(), expected.to_string());
+
+    match dynamic {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            let coords: Vec<_> = mismatch.mismatches.iter().map(|m| (m.row, m.col)).collect();
+            assert_eq!(coords, vec![(0, 1), (1, 1)]);
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
@@ -0,0 +1,133 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_matrices, compare_matrices_with_options, ComparisonOptions, MatrixComparisonFailure,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+#[test]
+fn comparison_stops_after_limit() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b = mock_matrix![0, 2, 0;
+                         0, 0, 6];
+
+    let c = ExactElementwiseComparator;
+    let options = ComparisonOptions::default().early_exit_after(2);
+    match compare_matrices_with_options(&a, &b, &c, &options) {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert!(mismatch.stopped_early);
+            let coords: Vec<_> = mismatch
+                .mismatches
+                .iter()
+                .map(|element| (element.row, element.col))
+                .collect();
+            assert_eq!(coords, vec![(0, 0), (0, 2)]);
+        }
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn comparison_below_limit_is_exhaustive() {
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![0, 2, 0];
+
+    let c = ExactElementwiseComparator;
+    let options = ComparisonOptions::default().early_exit_after(3);
+    let result = compare_matrices_with_options(&a, &b, &c, &options);
+    assert_eq!(result, compare_matrices(&a, &b, &c));
+}
+
+#[test]
+fn comparison_reaching_limit_exactly_did_not_stop_early() {
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![0, 2, 0];
+
+    let c = ExactElementwiseComparator;
+    let options = ComparisonOptions::default().early_exit_after(2);
+    match compare_matrices_with_options(&a, &b, &c, &options) {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert!(!mismatch.stopped_early);
+            assert_eq!(mismatch.mismatches.len(), 2);
+        }
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn zero_limit_still_reports_failure() {
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![0, 2, 0];
+
+    let c = ExactElementwiseComparator;
+    let options = ComparisonOptions::default().early_exit_after(0);
+    match compare_matrices_with_options(&a, &b, &c, &options) {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert!(mismatch.stopped_early);
+            assert_eq!(mismatch.mismatches.len(), 1);
+        }
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn sparse_comparison_stops_at_first_mismatches_in_order() {
+    let a = MockSparseMatrix::from_triplets(3, 3, vec![(2, 2, 1), (0, 1, 1), (1, 0, 1)]);
+    let b = MockSparseMatrix::from_triplets(3, 3, vec![(2, 1, 1)]);
+
+    let c = ExactElementwiseComparator;
+    let options = ComparisonOptions::default().early_exit_after(2);
+    match compare_matrices_with_options(&a, &b, &c, &options) {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            let coords: Vec<_> = mismatch
+                .mismatches
+                .iter()
+                .map(|element| (element.row, element.col))
+                .collect();
+            assert_eq!(coords, vec![(0, 1), (1, 0)]);
+        }
+        _ => panic!("Unexpected result"),
+    }
+}
+
+proptest! {
+    #[test]
+    fn early_exit_reports_prefix_of_all_mismatches(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        limit in 1usize..5
+    ) {
+        let c = ExactElementwiseComparator;
+        let options = ComparisonOptions::default().early_exit_after(limit);
+        let result = compare_matrices_with_options(&a, &b, &c, &options);
+        let full_result = compare_matrices(&a, &b, &c);
+
+        match (result, full_result) {
+            (Err(MatrixComparisonFailure::MismatchedElements(partial)),
+             Err(MatrixComparisonFailure::MismatchedElements(full))) => {
+                prop_assert_eq!(partial.stopped_early, full.mismatches.len() > limit);
+                prop_assert_eq!(&partial.mismatches[..], &full.mismatches[..partial.mismatches.len()]);
+            }
+            (result, full_result) => prop_assert_eq!(result, full_result),
+        }
+    }
+
+    #[test]
+    fn early_exit_comparison_is_symmetric(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        limit in 1usize..5
+    ) {
+        let c = ExactElementwiseComparator;
+        let options = ComparisonOptions::default().early_exit_after(limit);
+        let result1 = compare_matrices_with_options(&a, &b, &c, &options);
+        let result2 = compare_matrices_with_options(&b, &a, &c, &options);
+        prop_assert_eq!(result1, reverse_result(result2));
+    }
+}
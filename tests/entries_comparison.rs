@@ -0,0 +1,101 @@
+use matrixcompare::comparators::{ExactElementwiseComparator, ExactError};
+use matrixcompare::{
+    compare_entries, compare_matrices, Entry, Matrix, MatrixComparisonFailure,
+    MatrixElementComparisonFailure,
+};
+use matrixcompare_mock::{sparse_matrix_strategy_i64, MockSparseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn compare_entries_treats_missing_entries_as_zero() {
+    let c = ExactElementwiseComparator;
+    let left = vec![(0, 1, 3), (1, 0, 0)];
+    let right = vec![(0, 1, 3)];
+    assert!(compare_entries(left, right, (2, 2), &c).is_ok());
+
+    let left = vec![(0, 1, 3), (1, 1, 2)];
+    let right = vec![(0, 0, 1), (0, 1, 3)];
+    match compare_entries(left, right, (2, 2), &c).unwrap_err() {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            let coords: Vec<_> = mismatch
+                .mismatches
+                .iter()
+                .map(|element| (element.row, element.col))
+                .collect();
+            assert_eq!(coords, vec![(0, 0), (1, 1)]);
+            assert_eq!(
+                mismatch.mismatches[0],
+                MatrixElementComparisonFailure {
+                    left: 0,
+                    right: 1,
+                    error: ExactError,
+                    row: 0,
+                    col: 0
+                }
+            );
+            assert_eq!(mismatch.dims, Some((2, 2)));
+        }
+        failure => panic!("Unexpected failure: {:?}", failure),
+    }
+}
+
+#[test]
+fn compare_entries_reports_invalid_entries() {
+    let c = ExactElementwiseComparator;
+    let valid = vec![(0, 0, 1)];
+
+    let out_of_bounds = vec![(0, 0, 1), (2, 0, 1)];
+    assert_eq!(
+        compare_entries(out_of_bounds.clone(), valid.clone(), (2, 2), &c),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Left((2, 0))
+        ))
+    );
+    assert_eq!(
+        compare_entries(valid.clone(), out_of_bounds, (2, 2), &c),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Right((2, 0))
+        ))
+    );
+
+    let duplicate = vec![(0, 0, 1), (0, 0, 1)];
+    assert_eq!(
+        compare_entries(duplicate.clone(), valid.clone(), (2, 2), &c),
+        Err(MatrixComparisonFailure::DuplicateSparseEntry(Entry::Left(
+            (0, 0)
+        )))
+    );
+    assert_eq!(
+        compare_entries(valid, duplicate, (2, 2), &c),
+        Err(MatrixComparisonFailure::DuplicateSparseEntry(Entry::Right(
+            (0, 0)
+        )))
+    );
+}
+
+/// A strategy producing pairs of sparse matrices with the same dimensions.
+fn same_size_sparse_matrices(
+) -> impl Strategy<Value = (MockSparseMatrix<i64>, MockSparseMatrix<i64>)> {
+    (MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|(r, c)| {
+        let left = sparse_matrix_strategy_i64(Just(r), Just(c));
+        let right = sparse_matrix_strategy_i64(Just(r), Just(c));
+        (left, right)
+    })
+}
+
+proptest! {
+    #[test]
+    fn compare_entries_agrees_with_compare_matrices(
+        (left, right) in same_size_sparse_matrices()
+    ) {
+        let c = ExactElementwiseComparator;
+        let expected = compare_matrices(&left, &right, &c);
+
+        let dims = (left.rows(), left.cols());
+        let result = compare_entries(left.take_triplets(), right.take_triplets(), dims, &c);
+        prop_assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,84 @@
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, AbsoluteError, ExactElementwiseComparator,
+};
+use matrixcompare::{compare_matrices, error_matrix, DimensionMismatch, MatrixComparisonFailure};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn error_matrix_dense_dense() {
+    let a = mock_matrix![1.0, 2.0, 3.0;
+                         4.0, 5.0, 6.0];
+    let b = mock_matrix![1.0, 2.5, 3.0;
+                         4.0, 5.0, 7.0];
+
+    let comp = AbsoluteElementwiseComparator { tol: 0.1 };
+    let errors = error_matrix(&a, &b, &comp).unwrap();
+    assert_eq!(
+        errors,
+        vec![
+            vec![None, Some(AbsoluteError(0.5)), None],
+            vec![None, None, Some(AbsoluteError(1.0))]
+        ]
+    );
+}
+
+#[test]
+fn error_matrix_dense_sparse() {
+    let dense = mock_matrix![0, 3;
+                             1, 0];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 3), (1, 1, 2)]);
+
+    let errors = error_matrix(&dense, &sparse, &ExactElementwiseComparator).unwrap();
+    let failing: Vec<_> = errors
+        .iter()
+        .map(|row| row.iter().map(Option::is_some).collect::<Vec<_>>())
+        .collect();
+    assert_eq!(failing, vec![vec![false, false], vec![true, true]]);
+}
+
+#[test]
+fn error_matrix_mismatched_dimensions() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1; 2];
+
+    let expected = MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+        dim_left: (1, 2),
+        dim_right: (2, 1),
+    });
+    assert_eq!(
+        error_matrix(&a, &b, &ExactElementwiseComparator),
+        Err(expected)
+    );
+}
+
+proptest! {
+    #[test]
+    fn error_matrix_agrees_with_compare_matrices(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        match (error_matrix(&a, &b, &c), compare_matrices(&a, &b, &c)) {
+            (Ok(errors), Ok(())) => {
+                prop_assert!(errors.iter().flatten().all(Option::is_none));
+            }
+            (Ok(errors), Err(MatrixComparisonFailure::MismatchedElements(mismatch))) => {
+                let num_errors = errors.iter().flatten().filter(|e| e.is_some()).count();
+                prop_assert_eq!(num_errors, mismatch.mismatches.len());
+                for m in mismatch.mismatches {
+                    prop_assert!(errors[m.row][m.col].is_some());
+                }
+            }
+            (Err(error_matrix_failure), Err(failure)) => {
+                prop_assert_eq!(error_matrix_failure.kind(), failure.kind());
+            }
+            _ => prop_assert!(false, "error_matrix and compare_matrices disagree"),
+        }
+    }
+}
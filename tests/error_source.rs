@@ -0,0 +1,72 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{compare_matrices, compare_slices, compare_sparsity_patterns};
+use matrixcompare_mock::mock_matrix;
+use std::error::Error;
+
+#[test]
+fn source_of_mismatched_elements_is_first_comparator_error() {
+    let a = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let b = mock_matrix![1.0, 2.5; 3.0, 5.0];
+    let c = AbsoluteElementwiseComparator { tol: 0.1 };
+
+    let failure = compare_matrices(&a, &b, &c).unwrap_err();
+    let sourced = failure.clone().with_source();
+    let source = sourced.source().expect("Failure should have a source");
+    assert_eq!(source.to_string(), "Absolute error: 0.5.");
+    assert_eq!(sourced.to_string(), failure.to_string());
+
+    let labeled = failure.with_labels("computed", "expected").with_source();
+    let source = labeled
+        .source()
+        .expect("Labeled failure should have a source");
+    assert_eq!(source.to_string(), "Absolute error: 0.5.");
+}
+
+#[test]
+fn source_is_opt_in() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 3];
+
+    let failure = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    assert!(failure.source().is_none());
+    assert!(failure.with_labels("X", "Y").source().is_none());
+}
+
+#[test]
+fn other_failures_have_no_source() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 2, 3];
+
+    let failure = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    assert!(failure.with_source().source().is_none());
+
+    let failure = compare_sparsity_patterns(&a, &b).unwrap_err();
+    assert!(failure.with_source().source().is_none());
+}
+
+#[test]
+fn source_of_slice_failure_is_first_comparator_error() {
+    let c = AbsoluteElementwiseComparator { tol: 0.1 };
+
+    let failure = compare_slices(&[1.0, 2.0, 3.0], &[1.0, 2.5, 4.0], &c).unwrap_err();
+    assert!(failure.source().is_none());
+    let failure = failure.with_source();
+    let source = failure.source().expect("Failure should have a source");
+    assert_eq!(source.to_string(), "Absolute error: 0.5.");
+
+    let failure = compare_slices(&[1.0, 2.0], &[1.0], &c).unwrap_err();
+    assert!(failure.with_source().source().is_none());
+}
+
+#[test]
+fn failures_can_be_boxed_as_errors() {
+    fn check() -> Result<(), Box<dyn Error>> {
+        let a = mock_matrix![1, 2];
+        let b = mock_matrix![1, 3];
+        compare_matrices(&a, &b, &ExactElementwiseComparator).map_err(|f| f.with_source())?;
+        Ok(())
+    }
+
+    let error = check().unwrap_err();
+    assert!(error.source().is_some());
+}
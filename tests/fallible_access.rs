@@ -0,0 +1,98 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{
+    compare_matrices, compare_matrices_fallible, AccessError, AccessFailure, DimensionMismatch,
+    Entry, FailureKind, Matrix, MatrixComparisonFailure, TryDenseAccess,
+};
+use matrixcompare_mock::{dense_matrix_strategy_i64, mock_matrix, MockDenseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+/// A dense matrix whose element at a given coordinate cannot be fetched.
+struct FailingMatrix<'a> {
+    inner: &'a MockDenseMatrix<i64>,
+    failing: (usize, usize),
+}
+
+impl<'a> TryDenseAccess<i64> for FailingMatrix<'a> {
+    fn dimensions(&self) -> (usize, usize) {
+        (self.inner.rows(), self.inner.cols())
+    }
+
+    fn try_fetch_single(&self, row: usize, col: usize) -> Result<i64, AccessError> {
+        if (row, col) == self.failing {
+            Err(AccessError::new("I/O error"))
+        } else {
+            self.inner.try_fetch_single(row, col)
+        }
+    }
+}
+
+#[test]
+fn fallible_access_reports_access_errors() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let failing = FailingMatrix {
+        inner: &a,
+        failing: (1, 0),
+    };
+
+    let expected = |entry| {
+        MatrixComparisonFailure::AccessError(AccessFailure {
+            entry,
+            error: AccessError::new("I/O error"),
+        })
+    };
+    let comp = ExactElementwiseComparator;
+    assert_eq!(
+        compare_matrices_fallible(&failing, &a, &comp),
+        Err(expected(Entry::Left((1, 0))))
+    );
+    assert_eq!(
+        compare_matrices_fallible(&a, &failing, &comp),
+        Err(expected(Entry::Right((1, 0))))
+    );
+}
+
+#[test]
+fn fallible_access_error_is_displayed() {
+    let a = mock_matrix![1, 2];
+    let failing = FailingMatrix {
+        inner: &a,
+        failing: (0, 1),
+    };
+
+    let failure = compare_matrices_fallible(&a, &failing, &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(failure.kind(), FailureKind::AccessError);
+    assert_eq!(
+        failure.to_string(),
+        "Failed to fetch the element at (0, 1) of matrix Y (right): I/O error"
+    );
+}
+
+#[test]
+fn fallible_access_mismatched_dimensions() {
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![1, 2];
+    let expected = MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+        dim_left: (1, 3),
+        dim_right: (1, 2),
+    });
+    let comp = AbsoluteElementwiseComparator { tol: 0 };
+    assert_eq!(compare_matrices_fallible(&a, &b, &comp), Err(expected));
+}
+
+proptest! {
+    #[test]
+    fn fallible_access_matches_compare_matrices_for_dense_matrices(
+        (a, b) in (MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|(rows, cols)| {
+            let a = dense_matrix_strategy_i64(Just(rows), Just(cols));
+            let b = dense_matrix_strategy_i64(Just(rows), Just(cols));
+            (a, b)
+        })
+    ) {
+        let comp = ExactElementwiseComparator;
+        prop_assert_eq!(compare_matrices_fallible(&a, &b, &comp), compare_matrices(&a, &b, &comp));
+    }
+}
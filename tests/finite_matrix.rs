@@ -0,0 +1,101 @@
+use matrixcompare::{assert_matrix_finite, compare_matrix_finite, SpecialValueKind};
+use matrixcompare_mock::{
+    dense_matrix_strategy_normal_f64, mock_matrix, sparse_matrix_strategy_normal_f64,
+    MockCsrMatrix, MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+use SpecialValueKind::{Nan, NegativeInfinity, PositiveInfinity};
+
+#[test]
+fn finite_dense_matrix_passes() {
+    let m = mock_matrix![1.0, -2.0, f64::MAX;
+                         f64::MIN_POSITIVE, 0.0, -0.0];
+    assert_eq!(compare_matrix_finite(&m), Ok(()));
+    assert_matrix_finite!(m);
+}
+
+#[test]
+fn every_non_finite_dense_element_is_reported() {
+    let m = mock_matrix![f64::INFINITY, 1.0, f64::NAN;
+                         2.0, f64::NEG_INFINITY, f64::NAN];
+    assert_eq!(
+        compare_matrix_finite(&m),
+        Err(vec![
+            ((0, 0), PositiveInfinity),
+            ((0, 2), Nan),
+            ((1, 1), NegativeInfinity),
+            ((1, 2), Nan)
+        ])
+    );
+
+    // Arrays do not expose a contiguous slice, so their elements are accessed one at a time
+    let m = [
+        [f64::INFINITY, 1.0, f64::NAN],
+        [2.0, f64::NEG_INFINITY, f64::NAN],
+    ];
+    assert_eq!(
+        compare_matrix_finite(m),
+        Err(vec![
+            ((0, 0), PositiveInfinity),
+            ((0, 2), Nan),
+            ((1, 1), NegativeInfinity),
+            ((1, 2), Nan)
+        ])
+    );
+}
+
+#[test]
+fn non_finite_sparse_entries_are_reported_in_order() {
+    let m = MockSparseMatrix::from_triplets(
+        3,
+        3,
+        vec![(2, 0, f32::NAN), (0, 1, 1.0), (1, 2, f32::NEG_INFINITY)],
+    );
+    assert_eq!(
+        compare_matrix_finite(&m),
+        Err(vec![((1, 2), NegativeInfinity), ((2, 0), Nan)])
+    );
+
+    let csr = MockCsrMatrix::from_triplets(2, 2, vec![(1, 1, f64::INFINITY), (0, 0, 1.0)]);
+    assert_eq!(
+        compare_matrix_finite(&csr),
+        Err(vec![((1, 1), PositiveInfinity)])
+    );
+}
+
+#[test]
+fn empty_matrices_are_finite() {
+    let m = MockDenseMatrix::<f64>::from_row_major(0, 3, vec![]);
+    assert_eq!(compare_matrix_finite(&m), Ok(()));
+    assert_eq!(compare_matrix_finite([[0.0f64; 0]; 2]), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "Matrix `m` has 2 non-finite elements.\n (0, 1): +inf\n (1, 1): NaN\n")]
+fn assert_matrix_finite_lists_non_finite_elements() {
+    let m = mock_matrix![1.0, f64::INFINITY;
+                         3.0, f64::NAN];
+    assert_matrix_finite!(m);
+}
+
+#[test]
+#[should_panic(expected = "has 20 non-finite elements.")]
+fn assert_matrix_finite_truncates_long_lists() {
+    let m = MockDenseMatrix::from_row_major(4, 5, vec![f64::NAN; 20]);
+    assert_matrix_finite!(m);
+}
+
+proptest! {
+    #[test]
+    fn matrices_of_normal_values_are_finite(
+        dense in dense_matrix_strategy_normal_f64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        sparse in sparse_matrix_strategy_normal_f64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        prop_assert_eq!(compare_matrix_finite(&dense), Ok(()));
+        prop_assert_eq!(compare_matrix_finite(&sparse), Ok(()));
+    }
+}
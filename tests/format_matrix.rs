@@ -0,0 +1,81 @@
+use matrixcompare::{format_matrix, Matrix};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, MockCsrMatrix, MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn dense_matrix_columns_are_aligned() {
+    let a = mock_matrix![1, -20, 3;
+                         400, 5, 6];
+    assert_eq!(format_matrix(&a), "  1  -20  3\n400    5  6");
+}
+
+#[test]
+fn sparse_matrix_is_zero_filled() {
+    let a = MockSparseMatrix::from_triplets(2, 3, vec![(0, 2, 7), (1, 0, -1)]);
+    assert_eq!(format_matrix(&a), " 0  0  7\n-1  0  0");
+
+    let csr = MockCsrMatrix::from_triplets(2, 3, vec![(0, 2, 7), (1, 0, -1)]);
+    assert_eq!(format_matrix(&csr), format_matrix(&a));
+}
+
+#[test]
+fn sparse_entries_out_of_bounds_are_ignored() {
+    let a = MockSparseMatrix::from_triplets(1, 2, vec![(0, 1, 3), (1, 0, 5), (0, 2, 5)]);
+    assert_eq!(format_matrix(&a), "0  3");
+}
+
+#[test]
+fn empty_matrices() {
+    assert_eq!(
+        format_matrix(&MockDenseMatrix::<i32>::from_row_major(0, 3, vec![])),
+        ""
+    );
+    assert_eq!(
+        format_matrix(&MockDenseMatrix::<i32>::from_row_major(2, 0, vec![])),
+        "\n"
+    );
+}
+
+#[test]
+fn large_matrices_are_abbreviated() {
+    let a = MockDenseMatrix::from_row_major(12, 11, (0..132).collect());
+    let expected = r"  0    1    2    3    4  ...    6    7    8    9   10
+ 11   12   13   14   15  ...   17   18   19   20   21
+ 22   23   24   25   26  ...   28   29   30   31   32
+ 33   34   35   36   37  ...   39   40   41   42   43
+ 44   45   46   47   48  ...   50   51   52   53   54
+...  ...  ...  ...  ...  ...  ...  ...  ...  ...  ...
+ 77   78   79   80   81  ...   83   84   85   86   87
+ 88   89   90   91   92  ...   94   95   96   97   98
+ 99  100  101  102  103  ...  105  106  107  108  109
+110  111  112  113  114  ...  116  117  118  119  120
+121  122  123  124  125  ...  127  128  129  130  131";
+    assert_eq!(format_matrix(&a), expected);
+}
+
+#[test]
+fn large_sparse_matrices_are_abbreviated() {
+    let a = MockSparseMatrix::from_triplets(100, 3, vec![(0, 0, 1), (50, 1, 2), (99, 2, 3)]);
+    let lines: Vec<_> = format_matrix(&a).lines().map(str::to_string).collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "  1    0    0");
+    assert_eq!(lines[5], "...  ...  ...");
+    assert_eq!(lines[10], "  0    0    3");
+}
+
+proptest! {
+    #[test]
+    fn every_row_has_the_same_width(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let formatted = format_matrix(&a);
+        let widths: Vec<_> = formatted.split('\n').map(|line| line.chars().count()).collect();
+        prop_assert_eq!(widths.len(), a.rows().clamp(1, 11));
+        prop_assert!(widths.windows(2).all(|w| w[0] == w[1]));
+    }
+}
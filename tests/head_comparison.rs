@@ -0,0 +1,165 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_head, compare_matrices, DimensionMismatch, Entry, MatrixComparisonFailure,
+};
+use matrixcompare_core::Matrix;
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+#[test]
+fn head_ignores_trailing_rows() {
+    let a = mock_matrix![1, 2; 3, 4; 5, 6];
+    let b = mock_matrix![1, 2; 3, 4; 0, 0];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_head(&a, &b, 2, &c).is_ok());
+    assert!(compare_head(&a, &b, 3, &c).is_err());
+}
+
+#[test]
+fn head_reports_number_of_compared_rows() {
+    let a = mock_matrix![1, 2; 3, 4; 5, 6];
+    let b = mock_matrix![1, 2; 3, 5; 0, 0];
+
+    let err = compare_head(&a, &b, 2, &ExactElementwiseComparator).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.compared_rows, Some(2));
+            assert_eq!(mismatch.mismatches.len(), 1);
+            assert_eq!(
+                (mismatch.mismatches[0].row, mismatch.mismatches[0].col),
+                (1, 1)
+            );
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn head_larger_than_matrix_compares_all_rows() {
+    let a = mock_matrix![1, 2; 3, 4];
+    let b = mock_matrix![1, 2; 3, 5];
+
+    let err = compare_head(&a, &b, 10, &ExactElementwiseComparator).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.compared_rows, Some(2))
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn head_checks_dimensions() {
+    let c = ExactElementwiseComparator;
+
+    // Mismatched column counts are always reported
+    {
+        let a = mock_matrix![1, 2; 3, 4];
+        let b = mock_matrix![1, 2, 0; 3, 4, 0];
+        let err = compare_head(&a, &b, 1, &c).unwrap_err();
+        let expected = DimensionMismatch {
+            dim_left: (2, 2),
+            dim_right: (2, 3),
+        };
+        assert_eq!(err, MatrixComparisonFailure::MismatchedDimensions(expected));
+    }
+
+    // One of the matrices has fewer rows than requested
+    {
+        let a = mock_matrix![1, 2; 3, 4; 5, 6];
+        let b = mock_matrix![1, 2];
+        let err = compare_head(&a, &b, 2, &c).unwrap_err();
+        let expected = DimensionMismatch {
+            dim_left: (3, 2),
+            dim_right: (1, 2),
+        };
+        assert_eq!(err, MatrixComparisonFailure::MismatchedDimensions(expected));
+    }
+
+    // Row counts may differ as long as both have enough rows
+    {
+        let a = mock_matrix![1, 2; 3, 4; 5, 6];
+        let b = mock_matrix![1, 2];
+        assert!(compare_head(&a, &b, 1, &c).is_ok());
+    }
+}
+
+#[test]
+fn head_sparse_ignores_entries_beyond_prefix() {
+    let dense = mock_matrix![1, 0; 0, 4; 0, 0];
+    let sparse = MockSparseMatrix::from_triplets(3, 2, vec![(0, 0, 1), (1, 1, 4), (2, 0, 7)]);
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_head(&dense, &sparse, 2, &c).is_ok());
+    assert!(compare_head(&sparse, &dense, 2, &c).is_ok());
+    assert!(compare_head(&sparse, &dense, 3, &c).is_err());
+}
+
+#[test]
+fn head_sparse_out_of_bounds_in_prefix_is_detected() {
+    let dense = mock_matrix![1, 0; 0, 4; 0, 0];
+    let sparse = MockSparseMatrix::from_triplets(3, 2, vec![(0, 0, 1), (1, 2, 4)]);
+
+    let err = compare_head(&dense, &sparse, 2, &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Right((1, 2)))
+    );
+}
+
+#[test]
+fn head_sparse_out_of_bounds_beyond_matrix_is_detected() {
+    let dense = mock_matrix![1, 0; 0, 4; 0, 0];
+    let sparse = MockSparseMatrix::from_triplets(3, 2, vec![(0, 0, 1), (usize::MAX, 0, 4)]);
+
+    let err = compare_head(&dense, &sparse, 2, &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Right((usize::MAX, 0)))
+    );
+}
+
+proptest! {
+    #[test]
+    fn head_of_all_rows_agrees_with_full_comparison_dense(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let n = a.rows().max(b.rows());
+        let head_ok = compare_head(&a, &b, n, &c).is_ok();
+        let full_ok = compare_matrices(&a, &b, &c).is_ok();
+        prop_assert_eq!(head_ok, full_ok);
+    }
+
+    #[test]
+    fn head_of_all_rows_agrees_with_full_comparison_sparse(
+        a in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let n = a.rows().max(b.rows());
+        let head_ok = compare_head(&a, &b, n, &c).is_ok();
+        let full_ok = compare_matrices(&a, &b, &c).is_ok();
+        prop_assert_eq!(head_ok, full_ok);
+    }
+
+    #[test]
+    fn head_comparison_is_symmetric(
+        a in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        n in MATRIX_DIM_RANGE
+    ) {
+        let c = ExactElementwiseComparator;
+        let result1 = compare_head(&a, &b, n, &c);
+        let result2 = compare_head(&b, &a, n, &c);
+
+        prop_assert_eq!(result1, reverse_result(result2));
+    }
+}
@@ -0,0 +1,81 @@
+use matrixcompare::comparators::{IntervalElementwiseComparator, IntervalError};
+use matrixcompare::interval::Interval;
+use matrixcompare::{compare_matrices, MatrixComparisonFailure};
+use matrixcompare_mock::mock_matrix;
+use num_traits::Zero;
+use std::fmt;
+use std::ops::Add;
+
+/// A minimal interval type, as might be provided by an interval arithmetic library.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Iv(f64, f64);
+
+impl Interval for Iv {
+    type Bound = f64;
+
+    fn lower(&self) -> f64 {
+        self.0
+    }
+
+    fn upper(&self) -> f64 {
+        self.1
+    }
+}
+
+impl Add for Iv {
+    type Output = Iv;
+
+    fn add(self, rhs: Iv) -> Iv {
+        Iv(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+// Required for the implicit zeros of sparse matrices
+impl Zero for Iv {
+    fn zero() -> Self {
+        Iv(0.0, 0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0 && self.1 == 0.0
+    }
+}
+
+impl fmt::Display for Iv {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}]", self.0, self.1)
+    }
+}
+
+#[test]
+fn overlapping_interval_matrices_compare_equal() {
+    let a = mock_matrix![Iv(0.0, 1.0), Iv(2.0, 3.0);
+                         Iv(-1.0, 1.0), Iv(5.0, 5.0)];
+    let b = mock_matrix![Iv(0.5, 1.5), Iv(3.0, 4.0);
+                         Iv(0.0, 0.0), Iv(4.0, 6.0)];
+
+    let comp = IntervalElementwiseComparator { tol: 0.0 };
+    assert!(compare_matrices(&a, &b, &comp).is_ok());
+}
+
+#[test]
+fn disjoint_intervals_are_reported() {
+    let a = mock_matrix![Iv(0.0, 1.0), Iv(2.0, 3.0)];
+    let b = mock_matrix![Iv(0.5, 1.5), Iv(3.5, 4.0)];
+
+    let comp = IntervalElementwiseComparator { tol: 0.25 };
+    let err = compare_matrices(&a, &b, &comp).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(ref mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 1);
+            let element = &mismatch.mismatches[0];
+            assert_eq!((element.row, element.col), (0, 1));
+            assert_eq!(element.error, IntervalError::Gap(0.5));
+        }
+        _ => panic!("Unexpected variant"),
+    }
+
+    assert!(err.to_string().contains(
+        "(0, 1): x = [2, 3], y = [3.5, 4]. Intervals do not overlap, separated by a gap of 0.5."
+    ));
+}
@@ -0,0 +1,190 @@
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, ExactElementwiseComparator, ExactError,
+};
+use matrixcompare::{
+    compare_matrices, count_mismatches, iter_mismatches, DimensionMismatch, Entry,
+    MatrixComparisonFailure,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, AccessRecorder,
+    MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+/// Collects the mismatches reported by `compare_matrices`, for reference.
+fn collect_mismatches<T>(
+    result: Result<(), MatrixComparisonFailure<T, ExactError>>,
+) -> Vec<(usize, usize, T)> {
+    match result {
+        Ok(()) => Vec::new(),
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch
+            .mismatches
+            .into_iter()
+            .map(|m| (m.row, m.col, m.left))
+            .collect(),
+        Err(_) => panic!("Unexpected failure"),
+    }
+}
+
+#[test]
+fn iter_mismatches_dense_is_lazy() {
+    let n = 100;
+    let a = MockDenseMatrix::from_row_major(n, n, vec![0; n * n]);
+    let mut data = vec![0; n * n];
+    data[1] = 1;
+    data[n] = 2;
+    let b = MockDenseMatrix::from_row_major(n, n, data);
+    let a_recorder = AccessRecorder::new(&a);
+
+    let mismatches: Vec<_> = iter_mismatches(&a_recorder, &b, &ExactElementwiseComparator)
+        .unwrap()
+        .take(1)
+        .collect();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!((mismatches[0].row, mismatches[0].col), (0, 1));
+    assert_eq!((mismatches[0].left, mismatches[0].right), (0, 1));
+
+    // Only the elements up to the first mismatch have been accessed
+    let counts = a_recorder.counts();
+    assert_eq!(counts.fetch_ref + counts.fetch_single, 2);
+}
+
+#[test]
+fn iter_mismatches_reports_failures_up_front() {
+    let comp = AbsoluteElementwiseComparator { tol: 0 };
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![1, 2];
+    let failure = iter_mismatches(&a, &b, &comp)
+        .err()
+        .expect("Expected dimension mismatch");
+    assert_eq!(
+        failure,
+        MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+            dim_left: (1, 3),
+            dim_right: (1, 2),
+        })
+    );
+
+    let sparse = MockSparseMatrix::from_triplets(1, 3, vec![(0, 3, 1)]);
+    let failure = iter_mismatches(&a, &sparse, &comp)
+        .err()
+        .expect("Expected out-of-bounds entry");
+    assert_eq!(
+        failure,
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Right((0, 3)))
+    );
+}
+
+#[test]
+fn iter_mismatches_sparse() {
+    let comp = AbsoluteElementwiseComparator { tol: 1 };
+    let a = mock_matrix![1, 5, 3;
+                         4, 2, 6];
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(1, 2, 9), (0, 0, 1), (0, 1, 2)]);
+
+    let coords: Vec<_> = iter_mismatches(&a, &sparse, &comp)
+        .unwrap()
+        .map(|m| (m.row, m.col, m.left, m.right))
+        .collect();
+    assert_eq!(
+        coords,
+        vec![
+            (0, 1, 5, 2),
+            (0, 2, 3, 0),
+            (1, 0, 4, 0),
+            (1, 1, 2, 0),
+            (1, 2, 6, 9)
+        ]
+    );
+}
+
+#[test]
+fn count_mismatches_counts_mismatched_elements() {
+    let comp = AbsoluteElementwiseComparator { tol: 1 };
+    let a = mock_matrix![1, 5, 3;
+                         4, 2, 6];
+    let b = mock_matrix![1, 2, 3;
+                         4, 0, 6];
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(1, 2, 9), (0, 0, 1), (0, 1, 2)]);
+
+    assert_eq!(count_mismatches(&a, &a, &comp), Ok(0));
+    assert_eq!(count_mismatches(&a, &b, &comp), Ok(2));
+    assert_eq!(count_mismatches(&a, &sparse, &comp), Ok(5));
+    assert_eq!(count_mismatches(&sparse, &a, &comp), Ok(5));
+}
+
+#[test]
+fn count_mismatches_reports_structural_failures() {
+    let comp = AbsoluteElementwiseComparator { tol: 0 };
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![1, 2];
+    assert_eq!(
+        count_mismatches(&a, &b, &comp),
+        Err(MatrixComparisonFailure::MismatchedDimensions(
+            DimensionMismatch {
+                dim_left: (1, 3),
+                dim_right: (1, 2),
+            }
+        ))
+    );
+
+    let sparse = MockSparseMatrix::from_triplets(1, 3, vec![(0, 3, 1)]);
+    assert_eq!(
+        count_mismatches(&sparse, &a, &comp),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Left((0, 3))
+        ))
+    );
+}
+
+proptest! {
+    #[test]
+    fn count_mismatches_matches_compare_matrices(
+        (a, b) in (MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|(rows, cols)| {
+            let a = dense_matrix_strategy_i64(Just(rows), Just(cols));
+            let b = sparse_matrix_strategy_i64(Just(rows), Just(cols));
+            (a, b)
+        })
+    ) {
+        let comp = ExactElementwiseComparator;
+        let expected = collect_mismatches(compare_matrices(&a, &b, &comp)).len();
+        prop_assert_eq!(count_mismatches(&a, &b, &comp), Ok(expected));
+    }
+
+    #[test]
+    fn iter_mismatches_matches_compare_matrices_dense(
+        (a, b) in (MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|(rows, cols)| {
+            let a = dense_matrix_strategy_i64(Just(rows), Just(cols));
+            let b = dense_matrix_strategy_i64(Just(rows), Just(cols));
+            (a, b)
+        })
+    ) {
+        let comp = ExactElementwiseComparator;
+        let expected = collect_mismatches(compare_matrices(&a, &b, &comp));
+        let mismatches: Vec<_> = iter_mismatches(&a, &b, &comp)
+            .unwrap()
+            .map(|m| (m.row, m.col, m.left))
+            .collect();
+        prop_assert_eq!(mismatches, expected);
+    }
+
+    #[test]
+    fn iter_mismatches_matches_compare_matrices_sparse(
+        (a, b) in (MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|(rows, cols)| {
+            let a = dense_matrix_strategy_i64(Just(rows), Just(cols));
+            let b = sparse_matrix_strategy_i64(Just(rows), Just(cols));
+            (a, b)
+        })
+    ) {
+        let comp = ExactElementwiseComparator;
+        let expected = collect_mismatches(compare_matrices(&b, &a, &comp));
+        let mismatches: Vec<_> = iter_mismatches(&b, &a, &comp)
+            .unwrap()
+            .map(|m| (m.row, m.col, m.left))
+            .collect();
+        prop_assert_eq!(mismatches, expected);
+    }
+}
@@ -0,0 +1,192 @@
+//! Invokes every comparator arm of the assertion macros, and checks that the panic message
+//! is the formatted failure.
+
+use matrixcompare::comparators::AbsoluteElementwiseComparator;
+use matrixcompare::{assert_matrix_eq, assert_scalar_eq, assert_slice_eq};
+use matrixcompare_mock::mock_matrix;
+use std::panic::{catch_unwind, UnwindSafe};
+
+fn panic_message(f: impl FnOnce() + UnwindSafe) -> String {
+    let payload = catch_unwind(f).expect_err("Assertion unexpectedly succeeded");
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(_) => panic!("Panic payload is not a formatted string"),
+    }
+}
+
+#[test]
+fn matrix_eq_arms_panic_with_failure_message() {
+    // le fails on the first element, ge on the second
+    let x = mock_matrix![2.0, 1.0];
+    let y = mock_matrix![1.0, 2.0];
+    let custom = AbsoluteElementwiseComparator { tol: 0.1 };
+    let messages = vec![
+        panic_message(|| assert_matrix_eq!(x, y)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = exact)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = abs, tol = 0.1)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = ulp, tol = 2)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = le, tol = 0.1)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = ge, tol = 0.1)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = decimal, places = 6)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = percent, tol = 5.0)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = ratio, tol = 0.01)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = custom)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = float)),
+        panic_message(|| assert_matrix_eq!(x, y, comp = float, eps = 1e-6, ulp = 2)),
+    ];
+    for message in messages {
+        assert!(
+            message.contains("Comparison criterion:"),
+            "Unexpected message: {}",
+            message
+        );
+    }
+}
+
+#[test]
+fn scalar_eq_arms_panic_with_failure_message() {
+    let x = 2.0;
+    let y = 1.0;
+    let custom = AbsoluteElementwiseComparator { tol: 0.1 };
+    let messages = vec![
+        panic_message(|| assert_scalar_eq!(x, y)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = exact)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = abs, tol = 0.1)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = ulp, tol = 2)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = le, tol = 0.1)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = decimal, places = 6)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = percent, tol = 5.0)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = ratio, tol = 0.01)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = custom)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = float)),
+        panic_message(|| assert_scalar_eq!(x, y, comp = float, eps = 1e-6, ulp = 2)),
+        panic_message(|| assert_scalar_eq!(y, x, comp = ge, tol = 0.1)),
+    ];
+    for message in messages {
+        assert!(
+            message.contains("Comparison criterion:"),
+            "Unexpected message: {}",
+            message
+        );
+    }
+}
+
+#[test]
+fn slice_eq_arms_panic_with_failure_message() {
+    // le fails on the first element, ge on the second
+    let x = [2.0, 1.0];
+    let y = [1.0, 2.0];
+    let custom = AbsoluteElementwiseComparator { tol: 0.1 };
+    let messages = vec![
+        panic_message(|| assert_slice_eq!(x, y)),
+        panic_message(|| assert_slice_eq!(x, y, comp = exact)),
+        panic_message(|| assert_slice_eq!(x, y, comp = abs, tol = 0.1)),
+        panic_message(|| assert_slice_eq!(x, y, comp = ulp, tol = 2)),
+        panic_message(|| assert_slice_eq!(x, y, comp = le, tol = 0.1)),
+        panic_message(|| assert_slice_eq!(x, y, comp = ge, tol = 0.1)),
+        panic_message(|| assert_slice_eq!(x, y, comp = decimal, places = 6)),
+        panic_message(|| assert_slice_eq!(x, y, comp = percent, tol = 5.0)),
+        panic_message(|| assert_slice_eq!(x, y, comp = ratio, tol = 0.01)),
+        panic_message(|| assert_slice_eq!(x, y, comp = custom)),
+        panic_message(|| assert_slice_eq!(x, y, comp = float)),
+        panic_message(|| assert_slice_eq!(x, y, comp = float, eps = 1e-6, ulp = 2)),
+    ];
+    for message in messages {
+        assert!(
+            message.contains("Comparison criterion:"),
+            "Unexpected message: {}",
+            message
+        );
+    }
+}
+
+#[test]
+fn matrix_eq_labels_are_used_in_message() {
+    let x = mock_matrix![2.0, 1.0];
+    let y = mock_matrix![1.0, 2.0];
+    let custom = AbsoluteElementwiseComparator { tol: 0.1 };
+    let messages = vec![
+        panic_message(|| assert_matrix_eq!(x, y, labels = ("predicted", "expected"))),
+        panic_message(|| {
+            assert_matrix_eq!(x, y, comp = custom, labels = ("predicted", "expected"))
+        }),
+        panic_message(|| {
+            assert_matrix_eq!(
+                x,
+                y,
+                comp = abs,
+                tol = 0.1,
+                labels = ("predicted", "expected")
+            )
+        }),
+        panic_message(|| {
+            assert_matrix_eq!(
+                x,
+                y,
+                comp = float,
+                eps = 1e-6,
+                ulp = 2,
+                labels = ("predicted", "expected")
+            )
+        }),
+    ];
+    for message in messages {
+        assert!(
+            message.starts_with("Matrices predicted (left) and expected (right)"),
+            "Unexpected message: {}",
+            message
+        );
+    }
+}
+
+#[test]
+fn matrix_eq_context_is_prepended_to_message() {
+    let x = mock_matrix![2.0, 1.0];
+    let y = mock_matrix![1.0, 2.0];
+    let messages = vec![
+        panic_message(|| assert_matrix_eq!(x, y, context = "step 3")),
+        panic_message(|| assert_matrix_eq!(x, y, comp = abs, tol = 0.1, context = "step 3")),
+        panic_message(|| {
+            assert_matrix_eq!(x, y, labels = ("predicted", "expected"), context = "step 3")
+        }),
+        panic_message(|| {
+            assert_matrix_eq!(
+                x,
+                y,
+                comp = float,
+                context = format!("step {}", 3),
+                labels = ("predicted", "expected")
+            )
+        }),
+    ];
+    for message in messages {
+        assert!(
+            message.starts_with("step 3\nMatrices "),
+            "Unexpected message: {}",
+            message
+        );
+    }
+}
+
+#[test]
+fn matrix_eq_without_context_is_unchanged() {
+    let x = mock_matrix![2.0, 1.0];
+    let y = mock_matrix![1.0, 2.0];
+    let message = panic_message(|| assert_matrix_eq!(x, y, comp = abs, tol = 0.1));
+    assert!(message.starts_with("Matrices X (left)"));
+}
+
+#[test]
+fn only_default_arms_point_to_documentation() {
+    let note = "Please see the documentation for ways to compare";
+    let (x, y) = (mock_matrix![1, 2], mock_matrix![1, 3]);
+    assert!(panic_message(|| assert_matrix_eq!(x, y)).contains(note));
+    assert!(!panic_message(|| assert_matrix_eq!(x, y, comp = exact)).contains(note));
+
+    assert!(panic_message(|| assert_scalar_eq!(2, 3)).contains(note));
+    assert!(!panic_message(|| assert_scalar_eq!(2, 3, comp = exact)).contains(note));
+
+    let (x, y) = ([1, 2], [1, 3]);
+    assert!(panic_message(|| assert_slice_eq!(x, y)).contains(note));
+    assert!(!panic_message(|| assert_slice_eq!(x, y, comp = exact)).contains(note));
+}
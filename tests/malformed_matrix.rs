@@ -0,0 +1,102 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_matrices_with_options, Access, ComparisonOptions, DenseAccess, Entry, MalformedMatrix,
+    Matrix, MatrixComparisonFailure,
+};
+use matrixcompare_mock::{mock_matrix, MockSparseMatrix};
+
+/// A dense matrix that reports more columns than it stores.
+struct TooFewColumns {
+    data: Vec<Vec<i64>>,
+    cols: usize,
+}
+
+impl Matrix<i64> for TooFewColumns {
+    fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn access(&self) -> Access<'_, i64> {
+        Access::Dense(self)
+    }
+}
+
+impl DenseAccess<i64> for TooFewColumns {
+    fn fetch_single(&self, row: usize, col: usize) -> i64 {
+        self.data[row][col]
+    }
+}
+
+fn malformed() -> TooFewColumns {
+    TooFewColumns {
+        data: vec![vec![1, 2], vec![3, 4]],
+        cols: 3,
+    }
+}
+
+fn validate() -> ComparisonOptions {
+    ComparisonOptions::new().validate_dimensions(true)
+}
+
+#[test]
+fn malformed_matrix_is_reported() {
+    let a = malformed();
+    let b = mock_matrix![1, 2, 0;
+                         3, 4, 0];
+    let c = ExactElementwiseComparator;
+
+    let expected = MalformedMatrix {
+        entry: Entry::Left((0, 2)),
+        dims: (2, 3),
+    };
+    assert_eq!(
+        compare_matrices_with_options(&a, &b, &c, &validate()),
+        Err(MatrixComparisonFailure::MalformedMatrix(expected.clone()))
+    );
+    assert_eq!(
+        compare_matrices_with_options(&b, &a, &c, &validate()),
+        Err(MatrixComparisonFailure::MalformedMatrix(expected.reverse()))
+    );
+
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(1, 2, 5)]);
+    let err = compare_matrices_with_options(&sparse, &a, &c, &validate()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Matrix Y (right) is malformed: fetching the element at (0, 2) panicked, although the \
+         matrix reports dimensions 2 x 3."
+    );
+}
+
+#[test]
+#[should_panic]
+fn malformed_matrix_panics_without_validation() {
+    let b = mock_matrix![1, 2, 0;
+                         3, 4, 0];
+    let options = ComparisonOptions::new();
+    let _ = compare_matrices_with_options(malformed(), &b, &ExactElementwiseComparator, &options);
+}
+
+#[test]
+fn well_formed_matrices_are_compared_as_usual() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b = mock_matrix![1, 2, 3;
+                         4, 0, 6];
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 1), (1, 2, 6)]);
+    let c = ExactElementwiseComparator;
+
+    for (left, right) in [(&a, &a), (&a, &b)] {
+        assert_eq!(
+            compare_matrices_with_options(left, right, &c, &validate()),
+            compare_matrices_with_options(left, right, &c, &ComparisonOptions::new())
+        );
+    }
+    assert_eq!(
+        compare_matrices_with_options(&a, &sparse, &c, &validate()),
+        compare_matrices_with_options(&a, &sparse, &c, &ComparisonOptions::new())
+    );
+}
@@ -0,0 +1,68 @@
+use matrixcompare::comparators::{F32ReferenceError, UlpError};
+use matrixcompare::compare_f32_against_f64_reference;
+use matrixcompare::ulp::UlpComparisonResult;
+use matrixcompare::MatrixComparisonFailure::MismatchedElements;
+use matrixcompare_mock::{mock_matrix, MockSparseMatrix};
+
+fn next_f32(x: f32) -> f32 {
+    f32::from_bits(x.to_bits() + 1)
+}
+
+#[test]
+fn f32_result_matches_rounded_f64_reference() {
+    let computed = mock_matrix![0.1f32, 1.0 / 3.0;
+                                2.0,    0.0];
+    let reference = mock_matrix![0.1f64, 1.0 / 3.0;
+                                 2.0,    0.0];
+
+    assert!(compare_f32_against_f64_reference(&computed, &reference, 0).is_ok());
+}
+
+#[test]
+fn f32_result_within_ulp_tolerance() {
+    let computed = mock_matrix![next_f32(0.1f32), 2.0];
+    let reference = mock_matrix![0.1f64, 2.0];
+
+    assert!(compare_f32_against_f64_reference(&computed, &reference, 0).is_err());
+    assert!(compare_f32_against_f64_reference(&computed, &reference, 1).is_ok());
+}
+
+#[test]
+fn mismatch_reports_original_reference() {
+    let computed = mock_matrix![1.0f32, next_f32(next_f32(0.1f32))];
+    let reference = mock_matrix![1.0f64, 0.1f64];
+
+    let failure = compare_f32_against_f64_reference(&computed, &reference, 1).unwrap_err();
+    match failure {
+        MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 1);
+            let element = &mismatch.mismatches[0];
+            assert_eq!((element.row, element.col), (0, 1));
+            assert_eq!(element.left, f64::from(next_f32(next_f32(0.1f32))));
+            assert_eq!(element.right, 0.1f64);
+            assert_eq!(
+                element.error,
+                F32ReferenceError {
+                    rounded_reference: 0.1f32,
+                    error: UlpError::new(
+                        UlpComparisonResult::Difference(2),
+                        next_f32(next_f32(0.1f32)) - 0.1f32
+                    ),
+                }
+            );
+        }
+        _ => panic!("Unexpected failure"),
+    }
+}
+
+#[test]
+fn sparse_f32_result_against_dense_reference() {
+    let computed = MockSparseMatrix::from_triplets(2, 2, vec![(0, 0, 0.1f32), (1, 1, 3.0f32)]);
+    let reference = mock_matrix![0.1f64, 0.0;
+                                 0.0,    3.0];
+    assert!(compare_f32_against_f64_reference(&computed, &reference, 0).is_ok());
+
+    let reference = mock_matrix![0.1f64, 1e-3;
+                                 0.0,    3.0];
+    assert!(compare_f32_against_f64_reference(&computed, &reference, 0).is_err());
+}
@@ -0,0 +1,265 @@
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, ExactElementwiseComparator, RelativeElementwiseComparator,
+    UlpElementwiseComparator,
+};
+use matrixcompare::{
+    compare_matrices, compare_matrix_to_transpose_of, Matrix, MatrixComparisonFailure, SparseAccess,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_log_uniform, diagonal_sparse_matrix_strategy,
+    failing_dense_pair_strategy, i64_range, mock_matrix, square_dense_matrix_strategy,
+    symmetric_dense_matrix_strategy, FailingComparison, MockDenseMatrix, MockSparseMatrix,
+    RaggedRowsError, ToDenseError,
+};
+use proptest::prelude::*;
+use std::convert::TryFrom;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+/// Helper to collect the elements of a mock matrix in row-major order.
+fn row_major_data<T: Clone>(matrix: &MockDenseMatrix<T>) -> Vec<T> {
+    let mut data = Vec::new();
+    for i in 0..matrix.rows() {
+        for j in 0..matrix.cols() {
+            data.push(matrix.get(i, j).unwrap().clone());
+        }
+    }
+    data
+}
+
+/// Helper to collect the coordinates of the mismatched elements of a failed comparison.
+fn mismatched_coords<T, E>(
+    result: Result<(), MatrixComparisonFailure<T, E>>,
+) -> Vec<(usize, usize)> {
+    match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch
+            .mismatches
+            .iter()
+            .map(|element| (element.row, element.col))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn mock_matrix_accepts_trailing_semicolon() {
+    let a = mock_matrix![1, 2; 3, 4;];
+    assert_eq!((a.rows(), a.cols()), (2, 2));
+    assert_eq!(row_major_data(&a), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn mock_matrix_single_row() {
+    let a = mock_matrix![1.0, 2.0, 3.0];
+    assert_eq!((a.rows(), a.cols()), (1, 3));
+    assert_eq!(row_major_data(&a), vec![1.0, 2.0, 3.0]);
+
+    let b = mock_matrix![1.0, 2.0, 3.0;];
+    assert_eq!((b.rows(), b.cols()), (1, 3));
+    assert_eq!(row_major_data(&b), vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn mock_matrix_single_column() {
+    let a = mock_matrix![1; 2; 3];
+    assert_eq!((a.rows(), a.cols()), (3, 1));
+    assert_eq!(row_major_data(&a), vec![1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "Row 2 has 1 elements, but expected 2 elements")]
+fn mock_matrix_ragged_rows_panic_with_row_index() {
+    let _ = mock_matrix![1, 2; 3, 4; 5];
+}
+
+#[test]
+#[should_panic(expected = "Row 1 has 3 elements, but expected 2 elements")]
+fn mock_matrix_ragged_rows_with_trailing_semicolon() {
+    let _ = mock_matrix![1, 2; 3, 4, 5;];
+}
+
+#[test]
+#[should_panic(expected = "Dimension product overflows usize.")]
+fn from_row_major_panics_on_dimension_overflow() {
+    let _ = MockDenseMatrix::from_row_major(usize::MAX, 2, vec![0; 2]);
+}
+
+#[test]
+#[should_panic(expected = "Dimension product overflows usize.")]
+fn sparse_to_dense_panics_on_dimension_overflow() {
+    let sparse = MockSparseMatrix::from_triplets(usize::MAX / 2 + 1, 2, vec![(0, 0, 1)]);
+    let _ = sparse.to_dense();
+}
+
+#[test]
+#[should_panic(expected = "Linear index overflows usize.")]
+fn fetch_single_panics_on_index_overflow() {
+    use matrixcompare::DenseAccess;
+    let a = mock_matrix![1, 2; 3, 4];
+    let _ = a.fetch_single(usize::MAX, 0);
+}
+
+#[test]
+fn sparse_to_dense_reports_out_of_bounds_triplet() {
+    let sparse = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 1), (2, 1, 5)]);
+    let err = sparse.to_dense().unwrap_err();
+    assert_eq!(
+        err,
+        ToDenseError::OutOfBounds {
+            coord: (2, 1),
+            shape: (2, 3)
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Triplet at (2, 1) is out of bounds for matrix of shape 2 x 3."
+    );
+}
+
+#[test]
+fn dense_from_shape_and_data() {
+    let a = MockDenseMatrix::from((2, 3, vec![1, 2, 3, 4, 5, 6]));
+    assert_eq!((a.rows(), a.cols()), (2, 3));
+    assert_eq!(row_major_data(&a), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+#[should_panic(expected = "Data must have rows*cols number of elements.")]
+fn dense_from_shape_and_data_panics_on_wrong_length() {
+    let _ = MockDenseMatrix::from((2, 3, vec![1, 2, 3]));
+}
+
+#[test]
+fn dense_try_from_rows() {
+    let a = MockDenseMatrix::try_from(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+    assert_eq!((a.rows(), a.cols()), (3, 2));
+    assert_eq!(row_major_data(&a), vec![1, 2, 3, 4, 5, 6]);
+
+    let empty = MockDenseMatrix::<i32>::try_from(vec![]).unwrap();
+    assert_eq!((empty.rows(), empty.cols()), (0, 0));
+}
+
+#[test]
+fn dense_try_from_ragged_rows_reports_first_offending_row() {
+    let err = MockDenseMatrix::try_from(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+    assert_eq!(
+        err,
+        RaggedRowsError {
+            row: 2,
+            len: 1,
+            expected_len: 2
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Row 2 has 1 elements, but expected 2 elements (the length of row 0)."
+    );
+}
+
+#[test]
+fn dense_collect_from_rows() {
+    let a: MockDenseMatrix<_> = (0..3).map(|i| vec![i, 10 * i]).collect();
+    assert_eq!((a.rows(), a.cols()), (3, 2));
+    assert_eq!(row_major_data(&a), vec![0, 0, 1, 10, 2, 20]);
+}
+
+#[test]
+#[should_panic(expected = "Row 1 has 3 elements, but expected 2 elements (the length of row 0).")]
+fn dense_collect_from_ragged_rows_panics() {
+    let _: MockDenseMatrix<_> = vec![vec![1, 2], vec![3, 4, 5]].into_iter().collect();
+}
+
+#[test]
+fn sparse_from_iter() {
+    let sparse = MockSparseMatrix::from_iter(2, 3, (0..2).map(|i| (i, i + 1, i)));
+    assert_eq!((sparse.rows(), sparse.cols()), (2, 3));
+    assert_eq!(sparse.fetch_triplets(), vec![(0, 1, 0), (1, 2, 1)]);
+}
+
+proptest! {
+    #[test]
+    fn square_dense_matrix_strategy_generates_square_matrices(
+        a in square_dense_matrix_strategy(MATRIX_DIM_RANGE, i64_range())
+    ) {
+        prop_assert_eq!(a.rows(), a.cols());
+    }
+
+    #[test]
+    fn symmetric_dense_matrix_strategy_generates_symmetric_matrices(
+        a in symmetric_dense_matrix_strategy(MATRIX_DIM_RANGE, i64_range())
+    ) {
+        prop_assert_eq!(a.rows(), a.cols());
+        for i in 0..a.rows() {
+            for j in 0..a.cols() {
+                prop_assert_eq!(a.get(i, j), a.get(j, i));
+            }
+        }
+        prop_assert!(compare_matrix_to_transpose_of(&a, &a, &ExactElementwiseComparator).is_ok());
+    }
+
+    #[test]
+    fn diagonal_sparse_matrix_strategy_generates_diagonal_matrices(
+        a in diagonal_sparse_matrix_strategy(MATRIX_DIM_RANGE, i64_range())
+    ) {
+        prop_assert_eq!(a.rows(), a.cols());
+        prop_assert_eq!(a.nnz(), a.rows());
+        prop_assert!(a.fetch_triplets().iter().all(|&(i, j, _)| i == j));
+    }
+
+    #[test]
+    fn failing_pairs_fail_exact_comparison(
+        pair in failing_dense_pair_strategy(FailingComparison::Exact, MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let result = compare_matrices(&pair.left, &pair.right, &ExactElementwiseComparator);
+        prop_assert_eq!(mismatched_coords(result), pair.mismatched);
+    }
+
+    #[test]
+    fn failing_pairs_fail_absolute_comparison(
+        tol in 0.0..1e6,
+        pair in failing_dense_pair_strategy(FailingComparison::Absolute { tol: 1e6 }, MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        // Pairs generated for a larger tolerance also fail smaller tolerances
+        let comp = AbsoluteElementwiseComparator { tol };
+        let result = compare_matrices(&pair.left, &pair.right, &comp);
+        prop_assert_eq!(mismatched_coords(result), pair.mismatched);
+    }
+
+    #[test]
+    fn failing_pairs_fail_ulp_comparison(
+        tol in any::<u64>(),
+        pair in failing_dense_pair_strategy(FailingComparison::Ulp, MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let comp = UlpElementwiseComparator { tol };
+        let result = compare_matrices(&pair.left, &pair.right, &comp);
+        prop_assert_eq!(mismatched_coords(result), pair.mismatched);
+    }
+
+    #[test]
+    fn log_uniform_matrices_span_the_exponent_range(
+        matrix in dense_matrix_strategy_log_uniform(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE, -10.0..10.0)
+    ) {
+        for x in row_major_data(&matrix) {
+            let exponent = x.abs().log10();
+            // Allow for rounding in the computation of the magnitude and its logarithm
+            prop_assert!((-10.0 - 1e-9..10.0 + 1e-9).contains(&exponent), "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn log_uniform_matrices_compare_equal_under_relative_but_not_absolute_tolerance(
+        matrix in dense_matrix_strategy_log_uniform(1usize..5, 1usize..5, 3.0..10.0)
+    ) {
+        let perturbed = MockDenseMatrix::from_row_major(
+            matrix.rows(),
+            matrix.cols(),
+            row_major_data(&matrix).into_iter().map(|x| x * (1.0 + 1e-12)).collect(),
+        );
+
+        let relative = RelativeElementwiseComparator { tol: 1e-10 };
+        let absolute = AbsoluteElementwiseComparator { tol: 1e-10 };
+        prop_assert!(compare_matrices(&matrix, &perturbed, &relative).is_ok());
+        prop_assert!(compare_matrices(&matrix, &perturbed, &absolute).is_err());
+    }
+}
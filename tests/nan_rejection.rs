@@ -0,0 +1,133 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{
+    compare_matrices_with_options, ComparisonOptions, DenseAccess, Entry, MatrixComparisonFailure,
+};
+use matrixcompare_mock::{dense_matrix_strategy, mock_matrix, MockSparseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+fn reject_nan() -> ComparisonOptions {
+    ComparisonOptions::new().reject_nan(true)
+}
+
+#[test]
+fn nan_is_compared_as_usual_by_default() {
+    let a = mock_matrix![1.0, f64::NAN; 3.0, 4.0];
+    let b = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+    let options = ComparisonOptions::new();
+    let err = compare_matrices_with_options(&a, &b, &comp, &options).unwrap_err();
+    assert!(matches!(
+        err,
+        MatrixComparisonFailure::MismatchedElements(_)
+    ));
+}
+
+#[test]
+fn first_nan_is_reported() {
+    let a = mock_matrix![1.0, 2.0; 3.0, f64::NAN];
+    let b = mock_matrix![1.0, f64::NAN; f64::NAN, 4.0];
+    let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+    let err = compare_matrices_with_options(&a, &b, &comp, &reject_nan()).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::NanEncountered(Entry::Right((0, 1)))
+    );
+
+    let err = compare_matrices_with_options(&b, &a, &comp, &reject_nan()).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::NanEncountered(Entry::Left((0, 1)))
+    );
+}
+
+#[test]
+fn left_nan_wins_ties() {
+    let a = mock_matrix![1.0, f64::NAN];
+    let b = mock_matrix![1.0, f64::NAN];
+    let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+    let err = compare_matrices_with_options(&a, &b, &comp, &reject_nan()).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::NanEncountered(Entry::Left((0, 1)))
+    );
+}
+
+#[test]
+fn nan_is_found_in_sparse_matrices() {
+    let a = MockSparseMatrix::from_triplets(3, 3, vec![(2, 0, f64::NAN), (1, 2, f64::NAN)]);
+    let b = mock_matrix![0.0, 0.0, 0.0;
+                         0.0, 0.0, 0.0;
+                         0.0, 0.0, 0.0];
+    let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+    let err = compare_matrices_with_options(&a, &b, &comp, &reject_nan()).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::NanEncountered(Entry::Left((1, 2)))
+    );
+
+    let err = compare_matrices_with_options(&b, &a, &comp, &reject_nan()).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::NanEncountered(Entry::Right((1, 2)))
+    );
+}
+
+#[test]
+fn out_of_bounds_sparse_nan_is_reported_as_out_of_bounds() {
+    let a = MockSparseMatrix::from_triplets(2, 2, vec![(2, 0, f64::NAN)]);
+    let b = MockSparseMatrix::from_triplets(2, 2, vec![]);
+    let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+    let err = compare_matrices_with_options(&a, &b, &comp, &reject_nan()).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Left((2, 0)))
+    );
+}
+
+#[test]
+fn dimension_mismatch_takes_precedence() {
+    let a = mock_matrix![f64::NAN, 2.0];
+    let b = mock_matrix![1.0; 2.0];
+    let comp = AbsoluteElementwiseComparator { tol: 1.0 };
+
+    let err = compare_matrices_with_options(&a, &b, &comp, &reject_nan()).unwrap_err();
+    assert!(matches!(
+        err,
+        MatrixComparisonFailure::MismatchedDimensions(_)
+    ));
+}
+
+proptest! {
+    #[test]
+    fn integer_matrices_never_contain_nan(
+        a in dense_matrix_strategy(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE, -5i64..5)
+    ) {
+        let options = reject_nan();
+        let result = compare_matrices_with_options(&a, &a, &ExactElementwiseComparator, &options);
+        prop_assert!(result.is_ok());
+    }
+
+    #[test]
+    fn nan_is_rejected_if_and_only_if_present(
+        a in dense_matrix_strategy(Just(3), Just(3), prop_oneof![Just(f64::NAN), -1.0..1.0]),
+        b in dense_matrix_strategy(Just(3), Just(3), prop_oneof![Just(f64::NAN), -1.0..1.0])
+    ) {
+        let comp = AbsoluteElementwiseComparator { tol: 2.0 };
+        let mut elements = a.as_slice().unwrap().iter().chain(b.as_slice().unwrap());
+        let has_nan = elements.any(|x| x.is_nan());
+        let result = compare_matrices_with_options(&a, &b, &comp, &reject_nan());
+        prop_assert_eq!(
+            matches!(result, Err(MatrixComparisonFailure::NanEncountered(_))),
+            has_nan
+        );
+        prop_assert_eq!(result.is_ok(), !has_nan);
+    }
+}
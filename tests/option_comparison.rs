@@ -0,0 +1,140 @@
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, ExactElementwiseComparator, ExactError, OptionComparator,
+    OptionError,
+};
+use matrixcompare::optional::Optional;
+use matrixcompare::{
+    assert_matrix_eq, assert_slice_eq, compare_matrices, compare_slices, MatrixComparisonFailure,
+    SliceComparisonFailure,
+};
+use matrixcompare_mock::{mock_matrix, MockSparseMatrix};
+use num_traits::Zero;
+use std::fmt;
+use std::ops::Add;
+
+/// A measurement that may be missing, as might be used to store data with gaps.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Measurement(Option<f64>);
+
+impl Optional for Measurement {
+    type Value = f64;
+
+    fn as_option(&self) -> Option<&f64> {
+        self.0.as_ref()
+    }
+}
+
+impl Add for Measurement {
+    type Output = Measurement;
+
+    fn add(self, rhs: Measurement) -> Measurement {
+        Measurement(self.0.and_then(|x| rhs.0.map(|y| x + y)))
+    }
+}
+
+// Required for the implicit zeros of sparse matrices
+impl Zero for Measurement {
+    fn zero() -> Self {
+        Measurement(Some(0.0))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == Some(0.0)
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(value) => write!(f, "{}", value),
+            None => write!(f, "missing"),
+        }
+    }
+}
+
+const MISSING: Measurement = Measurement(None);
+
+fn m(value: f64) -> Measurement {
+    Measurement(Some(value))
+}
+
+#[test]
+fn option_slices_compare_without_unwrapping() {
+    let comp = OptionComparator(ExactElementwiseComparator);
+    assert!(compare_slices(&[Some(1), None, Some(3)], &[Some(1), None, Some(3)], &comp).is_ok());
+
+    let result = compare_slices(&[Some(1), None, Some(3)], &[None, None, Some(4)], &comp);
+    let mismatch = match result {
+        Err(SliceComparisonFailure::MismatchedElements(mismatch)) => mismatch,
+        _ => panic!("Unexpected result: {:?}", result),
+    };
+    let errors: Vec<_> = mismatch
+        .mismatches
+        .iter()
+        .map(|failure| (failure.index, failure.error))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (0, OptionError::MissingRight),
+            (2, OptionError::Value(ExactError))
+        ]
+    );
+}
+
+#[test]
+fn optional_matrices_compare_equal() {
+    let a = mock_matrix![m(1.0), MISSING;
+                         MISSING, m(4.0)];
+    let b = mock_matrix![m(1.0 + 1e-12), MISSING;
+                         MISSING, m(4.0)];
+
+    let comp = OptionComparator(AbsoluteElementwiseComparator { tol: 1e-9 });
+    assert!(compare_matrices(&a, &b, &comp).is_ok());
+    assert_matrix_eq!(a, b, comp = comp);
+}
+
+#[test]
+fn optional_matrices_report_presence_and_value_mismatches() {
+    let a = mock_matrix![m(1.0), MISSING, m(3.0)];
+    let b = mock_matrix![m(2.0), m(2.0), MISSING];
+
+    let comp = OptionComparator(AbsoluteElementwiseComparator { tol: 0.5 });
+    let result = compare_matrices(&a, &b, &comp);
+    let mismatch = match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch,
+        _ => panic!("Unexpected result: {:?}", result),
+    };
+    let errors: Vec<_> = mismatch
+        .mismatches
+        .iter()
+        .map(|failure| failure.error)
+        .collect();
+    assert!(matches!(errors[0], OptionError::Value(_)));
+    assert_eq!(errors[1], OptionError::MissingLeft);
+    assert_eq!(errors[2], OptionError::MissingRight);
+
+    let message = mismatch.to_string();
+    assert!(message
+        .contains("(0, 1): x = missing, y = 2. Presence mismatch: x is missing, y is present."));
+    assert!(message
+        .contains("(0, 2): x = 3, y = missing. Presence mismatch: x is present, y is missing."));
+    assert!(message.contains("Missing values are only equal to missing values."));
+}
+
+#[test]
+fn sparse_optional_matrix_compares_against_dense() {
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, MISSING), (1, 1, m(2.0))]);
+    let dense = mock_matrix![m(0.0), MISSING;
+                             m(0.0), m(2.0)];
+
+    let comp = OptionComparator(ExactElementwiseComparator);
+    assert!(compare_matrices(&sparse, &dense, &comp).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Presence mismatch")]
+fn assert_slice_eq_reports_presence_mismatch() {
+    let comp = OptionComparator(ExactElementwiseComparator);
+    assert_slice_eq!([m(1.0), MISSING], [m(1.0), m(2.0)], comp = comp);
+}
@@ -1,5 +1,13 @@
-use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
-use matrixcompare::compare_matrices;
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, ExactElementwiseComparator, UlpElementwiseComparator,
+};
+use matrixcompare::{
+    analyze_matrices, compare_head, compare_matrices, compare_matrices_labeled,
+    compare_matrices_relative_norm, compare_matrices_scaled,
+    compare_matrices_up_to_row_permutation, compare_matrices_with_options,
+    compare_matrices_with_zero_threshold, compare_sparsity_patterns, ComparisonOptions,
+    MatrixComparisonFailure, Norm,
+};
 use matrixcompare_mock::{mock_matrix, MockSparseMatrix};
 
 use pretty_assertions::assert_eq;
@@ -15,7 +23,7 @@ fn mismatched_elements() {
     println!("{}", err);
     assert_eq!(
         err_string,
-        r"Matrices X (left) and Y (right) have 3 mismatched element pairs.
+        r"Matrices X (left) and Y (right) have 3 mismatched element pairs out of 6 (50%).
 The mismatched elements are listed below, in the format
 (row, col): x = X[[row, col]], y = Y[[row, col]].
 
@@ -38,7 +46,7 @@ fn mismatched_elements_abs_f64() {
     println!("{}", err);
     assert_eq!(
         err_string,
-        r"Matrices X (left) and Y (right) have 3 mismatched element pairs.
+        r"Matrices X (left) and Y (right) have 3 mismatched element pairs out of 6 (50%).
 The mismatched elements are listed below, in the format
 (row, col): x = X[[row, col]], y = Y[[row, col]].
 
@@ -50,6 +58,143 @@ Comparison criterion: absolute difference, |x - y| <= 0.000000000001."
     );
 }
 
+#[test]
+fn mismatched_elements_sparse() {
+    // The fraction of mismatches is relative to all rows * cols element pairs,
+    // not only the explicitly stored entries
+    let a = MockSparseMatrix::from_triplets(3, 4, vec![(0, 1, 2), (2, 3, 5)]);
+    let b = MockSparseMatrix::from_triplets(3, 4, vec![(0, 1, 3), (1, 0, 1)]);
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 3 mismatched element pairs out of 12 (25%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 1): x = 2, y = 3.
+ (1, 0): x = 0, y = 1.
+ (2, 3): x = 5, y = 0.
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
+#[test]
+fn mismatched_elements_sparse_with_different_nnz() {
+    let a = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 2), (1, 1, 0)]);
+    let b = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 3)]);
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 4 (25%).
+The sparse matrices X and Y explicitly store 2 and 1 entries, respectively.
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 1): x = 2, y = 3.
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
+#[test]
+fn mismatched_elements_with_decimal_places() {
+    let a = mock_matrix![0.1, 0.2; 0.3, 0.4];
+    let b = mock_matrix![0.11, 0.2; 0.3, 0.4 + 1e-4];
+    let c = AbsoluteElementwiseComparator { tol: 1e-6 };
+    let options = ComparisonOptions::default().decimal_places(4);
+
+    let err = compare_matrices_with_options(&a, &b, &c, &options).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 2 mismatched element pairs out of 4 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 0): x = 0.1000, y = 0.1100. Absolute error: 0.0100.
+ (1, 1): x = 0.4000, y = 0.4001. Absolute error: 0.0001.
+
+Comparison criterion: absolute difference, |x - y| <= 0.000001."
+    );
+}
+
+#[test]
+fn mismatched_elements_with_max_reports() {
+    let a = mock_matrix![1, 2, 3; 4, 5, 6];
+    let b = mock_matrix![1, 2, 9; 5, 4, 6];
+    let options = ComparisonOptions::new().max_reports(2);
+
+    let err =
+        compare_matrices_with_options(&a, &b, &ExactElementwiseComparator, &options).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 3 mismatched element pairs out of 6 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 2): x = 3, y = 9.
+ (1, 0): x = 4, y = 5.
+ ... (1 mismatching elements not shown)
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
+#[test]
+fn mismatched_elements_grouped_by_row() {
+    let a = mock_matrix![1, 2, 3, 4;
+                         5, 6, 7, 8;
+                         9, 10, 11, 12];
+    let b = mock_matrix![1, 2, 0, 4;
+                         5, 6, 7, 8;
+                         0, 10, 0, 0];
+    let options = ComparisonOptions::new().group_by_row(true);
+
+    let err =
+        compare_matrices_with_options(&a, &b, &ExactElementwiseComparator, &options).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 4 mismatched element pairs out of 12 (33.33%).
+The columns of the mismatched elements are listed below, grouped by row.
+
+ Row 0: cols [2] mismatched
+ Row 2: cols [0, 2, 3] mismatched
+
+Comparison criterion: exact equality x == y."
+    );
+
+    let options = options.max_reports(1);
+    let err =
+        compare_matrices_with_options(&a, &b, &ExactElementwiseComparator, &options).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r"Matrices X (left) and Y (right) have 4 mismatched element pairs out of 12 (33.33%).
+The columns of the mismatched elements are listed below, grouped by row.
+
+ Row 0: cols [2] mismatched
+ ... (1 rows with mismatching elements not shown)
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
 #[test]
 fn mismatched_dimensions() {
     let a = mock_matrix![1, 2; 4, 5];
@@ -67,6 +212,31 @@ fn mismatched_dimensions() {
     );
 }
 
+#[test]
+fn mismatched_dimensions_empty() {
+    let a = MockSparseMatrix::<i32>::from_triplets(0, 0, vec![]);
+    let b = mock_matrix![1, 2, 9; 5, 4, 6];
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Dimensions of matrices X (left) and Y (right) do not match.
+ dim(X) = 0 x 0 (matrix X is empty)
+ dim(Y) = 2 x 3"
+    );
+
+    let err = compare_matrices(&b, &mock_matrix![], &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r"Dimensions of matrices X (left) and Y (right) do not match.
+ dim(X) = 2 x 3
+ dim(Y) = 0 x 0 (matrix Y is empty)"
+    );
+}
+
 #[test]
 fn duplicate_entry_left() {
     let a = MockSparseMatrix::from_triplets(3, 3, vec![(1, 0, 2), (1, 0, 2)]);
@@ -126,3 +296,470 @@ fn out_of_bounds_right() {
         r"At least one sparse entry is out of bounds. Example: Right(5, 0)."
     );
 }
+
+#[test]
+fn out_of_bounds_both() {
+    let a = MockSparseMatrix::from_triplets(3, 3, vec![(4, 4, 1), (0, 3, 2), (1, 0, 2)]);
+    let b = MockSparseMatrix::from_triplets(3, 3, vec![(5, 0, 2)]);
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"3 sparse entries are out of bounds. Example: Left(0, 3)."
+    );
+}
+
+#[test]
+fn sparsity_pattern_mismatch() {
+    let a = MockSparseMatrix::from_triplets(3, 3, vec![(0, 1, 1), (2, 2, 1)]);
+    let b = MockSparseMatrix::from_triplets(3, 3, vec![(0, 1, 1)]);
+
+    let err = compare_sparsity_patterns(&a, &b).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Sparsity patterns of matrices X (left) and Y (right) do not match.
+Entries stored in X but not in Y: (2, 2)
+Entries stored in Y but not in X: (none)"
+    );
+}
+
+#[test]
+fn mismatched_elements_head() {
+    let a = mock_matrix![1, 2, 3; 4, 5, 6; 7, 8, 9];
+    let b = mock_matrix![1, 2, 9; 4, 5, 6; 0, 0, 0];
+
+    let err = compare_head(&a, &b, 2, &ExactElementwiseComparator).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 6 (16.67%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 2): x = 3, y = 9.
+
+Comparison criterion: exact equality x == y.
+Note: Only the first 2 rows were compared."
+    );
+}
+
+#[test]
+fn mismatched_elements_ulp_f64() {
+    let a = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let b = mock_matrix![1.0, 2.5; -3.0, 4.0];
+
+    let err = compare_matrices(&a, &b, &UlpElementwiseComparator { tol: 4 }).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 2 mismatched element pairs out of 4 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 1): x = 2, y = 2.5. Difference: > 1000000000 ULP. Absolute difference: 0.5.
+ (1, 0): x = 3, y = -3. Numbers have incompatible signs. Absolute difference: 6.
+
+Comparison criterion: ULP difference less than or equal to 4. See documentation for details."
+    );
+}
+
+#[test]
+fn reversed_failure_matches_swapped_comparison() {
+    let a = mock_matrix![1, 2; 3, 4];
+    let b = mock_matrix![1, 5; 3, 4];
+
+    let a_b = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let b_a = compare_matrices(&b, &a, &ExactElementwiseComparator).unwrap_err();
+
+    // x always refers to the left operand, y to the right operand
+    assert!(a_b.to_string().contains("(0, 1): x = 2, y = 5."));
+    assert!(b_a.to_string().contains("(0, 1): x = 5, y = 2."));
+
+    assert_eq!(a_b.clone().reverse().to_string(), b_a.to_string());
+    assert_eq!(b_a.reverse().to_string(), a_b.to_string());
+}
+
+#[test]
+fn reversed_dimension_mismatch_matches_swapped_comparison() {
+    let a = mock_matrix![1, 2; 4, 5];
+    let b = mock_matrix![1, 2, 9; 5, 4, 6];
+
+    let a_b = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let b_a = compare_matrices(&b, &a, &ExactElementwiseComparator).unwrap_err();
+
+    assert!(a_b.to_string().contains("dim(X) = 2 x 2"));
+    assert!(b_a.to_string().contains("dim(X) = 2 x 3"));
+    assert_eq!(a_b.reverse().to_string(), b_a.to_string());
+}
+
+#[test]
+fn mismatched_elements_statistics() {
+    let a = mock_matrix![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let b = mock_matrix![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 35];
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator)
+        .unwrap_err()
+        .with_statistics();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 14 mismatched element pairs out of 14 (100%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 0): x = 0, y = 1.
+ (0, 1): x = 0, y = 2.
+ (0, 2): x = 0, y = 3.
+ (0, 3): x = 0, y = 4.
+ (0, 4): x = 0, y = 5.
+ (0, 5): x = 0, y = 6.
+ (0, 6): x = 0, y = 7.
+ (0, 7): x = 0, y = 8.
+ (0, 8): x = 0, y = 9.
+ (0, 9): x = 0, y = 10.
+ (0, 10): x = 0, y = 11.
+ (0, 11): x = 0, y = 12.
+ ... (2 mismatching elements not shown)
+Statistics of absolute differences |x - y| over all 14 mismatched elements:
+ mean = 9, median = 7.5, max = 35
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
+#[test]
+fn mismatched_elements_statistics_hidden_below_report_limit() {
+    let a = mock_matrix![1, 2, 3; 4, 5, 6];
+    let b = mock_matrix![1, 2, 9; 5, 4, 6];
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let with_statistics = err.clone().with_statistics();
+
+    assert_eq!(err.to_string(), with_statistics.to_string());
+}
+
+#[test]
+fn mismatched_elements_symmetric() {
+    let a = mock_matrix![1, 2; 2, 4];
+    let b = mock_matrix![1, 3; 3, 4];
+
+    let options = ComparisonOptions::default().symmetric(true);
+    let err =
+        compare_matrices_with_options(&a, &b, &ExactElementwiseComparator, &options).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 4 (25%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 1): x = 2, y = 3.
+
+Comparison criterion: exact equality x == y.
+Note: The matrices were assumed to be symmetric. Mirrored mismatches in the lower triangle are not shown."
+    );
+}
+
+#[test]
+fn mismatched_elements_early_exit() {
+    let a = mock_matrix![1, 2, 3; 4, 5, 6];
+    let b = mock_matrix![0, 2, 0; 0, 5, 0];
+
+    let options = ComparisonOptions::default().early_exit_after(2);
+    let err =
+        compare_matrices_with_options(&a, &b, &ExactElementwiseComparator, &options).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have at least 2 mismatched element pairs out of 6 (at least 33.33%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 0): x = 1, y = 0.
+ (0, 2): x = 3, y = 0.
+
+Comparison criterion: exact equality x == y.
+Note: The comparison stopped early after finding 2 mismatched elements."
+    );
+}
+
+#[test]
+fn norm_mismatch() {
+    let a = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let b = mock_matrix![1.0, 2.0; 4.0, 0.0];
+
+    let err = compare_matrices_relative_norm(&a, &b, Norm::Max, 0.5).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have a relative residual of 1.
+||X - Y|| = 4, ||Y|| = 4 (reference).
+
+Comparison criterion: relative max norm, ||X - Y|| <= 0.5 * ||reference||."
+    );
+}
+
+#[test]
+fn nan_encountered() {
+    let a = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let b = mock_matrix![1.0, 2.0; f64::NAN, 4.0];
+
+    let options = ComparisonOptions::new().reject_nan(true);
+    let comp = AbsoluteElementwiseComparator { tol: 1e-6 };
+    let err = compare_matrices_with_options(&a, &b, &comp, &options).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(err_string, r"Matrix Y (right) contains NaN at (1, 0).");
+}
+
+#[test]
+fn row_permutation_mismatch() {
+    let a = mock_matrix![1, 2; 3, 4; 5, 6];
+    let b = mock_matrix![5, 6; 0, 0; 1, 2];
+
+    let err =
+        compare_matrices_up_to_row_permutation(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"No permutation of the rows of matrix Y (right) matches matrix X (left).
+Rows of X without a matching row in Y: 1
+Rows of Y without a matching row in X: 1
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
+#[test]
+fn mismatched_elements_with_zero_threshold() {
+    let a = mock_matrix![1.0, 1e-9];
+    let b = mock_matrix![0.5, 0.0];
+
+    let err = compare_matrices_with_zero_threshold(&a, &b, 1e-6, &ExactElementwiseComparator)
+        .unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 2 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 0): x = 1, y = 0.5.
+
+Comparison criterion: exact equality x == y.
+Elements x with |x| < 0.000001 were treated as zero before comparison."
+    );
+}
+
+#[test]
+fn mismatched_elements_scaled() {
+    let a = mock_matrix![1.0, 2.0];
+    let b = mock_matrix![1000.0, 2500.0];
+
+    let err = compare_matrices_scaled(&a, &b, 1e-3, &AbsoluteElementwiseComparator { tol: 1e-6 })
+        .unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices X (left) and Y (right) have 1 mismatched element pairs out of 2 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = X[[row, col]], y = Y[[row, col]].
+
+ (0, 1): x = 2, y = 2.5. Absolute error: 0.5.
+
+Comparison criterion: absolute difference, |x - y| <= 0.000001.
+Y (right) was scaled by a factor of 0.001 before comparison."
+    );
+}
+
+#[test]
+fn comparison_report() {
+    let a = mock_matrix![1.0, 2.0;
+                         3.0, 4.0];
+    let b = mock_matrix![1.0, 2.5;
+                         3.0, 4.0];
+
+    let report = analyze_matrices(&a, &b).unwrap();
+    let report_string = report.to_string();
+
+    println!("{}", report);
+    assert_eq!(
+        report_string,
+        "Max absolute difference |x - y| over 4 element pairs: 0.5 at (0, 1)."
+    );
+}
+
+#[test]
+fn failure_kinds() {
+    let c = ExactElementwiseComparator;
+    let empty = MockSparseMatrix::from_triplets(3, 3, Vec::<(usize, usize, i32)>::new());
+    let duplicate = MockSparseMatrix::from_triplets(3, 3, vec![(1, 0, 2), (1, 0, 2)]);
+    let out_of_bounds = MockSparseMatrix::from_triplets(3, 3, vec![(5, 0, 2)]);
+
+    let kind_strings: Vec<_> = vec![
+        compare_matrices(mock_matrix![1, 2], mock_matrix![1, 2, 3], &c),
+        compare_matrices(mock_matrix![1, 2], mock_matrix![1, 3], &c),
+        compare_matrices(&empty, &out_of_bounds, &c),
+        compare_matrices(&empty, &duplicate, &c),
+    ]
+    .into_iter()
+    .map(|result| result.unwrap_err().kind().to_string())
+    .collect();
+
+    assert_eq!(
+        kind_strings,
+        vec![
+            "dimension_mismatch",
+            "element_mismatch",
+            "sparse_out_of_bounds",
+            "duplicate_sparse"
+        ]
+    );
+}
+
+#[test]
+fn mismatched_elements_labeled() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 3];
+
+    let err = compare_matrices_labeled(
+        &a,
+        &b,
+        &ExactElementwiseComparator,
+        ("predicted", "expected"),
+    )
+    .unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Matrices predicted (left) and expected (right) have 1 mismatched element pairs out of 2 (50%).
+The mismatched elements are listed below, in the format
+(row, col): x = predicted[[row, col]], y = expected[[row, col]].
+
+ (0, 1): x = 2, y = 3.
+
+Comparison criterion: exact equality x == y."
+    );
+}
+
+#[test]
+fn mismatched_dimensions_labeled() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 2, 3];
+
+    let err = compare_matrices_labeled(
+        &a,
+        &b,
+        &ExactElementwiseComparator,
+        ("predicted", "expected"),
+    )
+    .unwrap_err();
+    let err_string = err.to_string();
+
+    println!("{}", err);
+    assert_eq!(
+        err_string,
+        r"Dimensions of matrices predicted (left) and expected (right) do not match.
+ dim(predicted) = 1 x 2
+ dim(expected) = 1 x 3"
+    );
+}
+
+#[test]
+fn mismatched_elements_side_by_side() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b = mock_matrix![1, 20, 3;
+                         4, 5, -6];
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let side_by_side = match err {
+        MatrixComparisonFailure::MismatchedElements(ref mismatch) => {
+            mismatch.side_by_side(("X", "Y"))
+        }
+        _ => panic!("Unexpected variant"),
+    }
+    .unwrap();
+
+    println!("{}", side_by_side);
+    assert_eq!(
+        side_by_side,
+        r"X (left)      Y (right)
+   .  2*   .     . 20*   .
+   .   .  6*     .   . -6*"
+    );
+}
+
+#[test]
+fn mismatched_elements_side_by_side_with_labels() {
+    let a = mock_matrix![1, 2;
+                         3, 4];
+    let b = mock_matrix![1, 2;
+                         3, 5];
+
+    let err = compare_matrices(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    let labeled = err.with_labels("predicted", "exp");
+    let side_by_side = match labeled.failure {
+        MatrixComparisonFailure::MismatchedElements(ref mismatch) => {
+            let (left, right) = &labeled.labels;
+            mismatch.side_by_side((left, right))
+        }
+        _ => panic!("Unexpected variant"),
+    }
+    .unwrap();
+
+    println!("{}", side_by_side);
+    assert_eq!(
+        side_by_side,
+        r"predicted (left)  exp (right)
+  .  .              .  .
+  . 4*              . 5*"
+    );
+}
+
+#[test]
+fn side_by_side_unavailable_for_large_or_partial_mismatches() {
+    let side_by_side = |err| match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => mismatch.side_by_side(("X", "Y")),
+        _ => panic!("Unexpected variant"),
+    };
+    let c = ExactElementwiseComparator;
+
+    let large_a = MockSparseMatrix::from_triplets(11, 2, vec![(0, 0, 1)]);
+    let large_b = MockSparseMatrix::from_triplets(11, 2, vec![]);
+    let err = compare_matrices(&large_a, &large_b, &c).unwrap_err();
+    assert_eq!(side_by_side(err), None);
+
+    let a = mock_matrix![1, 2, 3];
+    let b = mock_matrix![3, 2, 1];
+    let options = ComparisonOptions::default().early_exit_after(1);
+    let err = compare_matrices_with_options(&a, &b, &c, &options).unwrap_err();
+    assert_eq!(side_by_side(err), None);
+}
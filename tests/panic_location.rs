@@ -0,0 +1,67 @@
+//! Checks that panics raised by the assertion macros are reported at the line of the
+//! assertion in the user's code, rather than inside the library.
+
+use matrixcompare::{
+    assert_matrix_approx_eq_with_report, assert_matrix_eq, assert_matrix_eq_triplets,
+    assert_scalar_eq, assert_scalar_ne, assert_slice_eq,
+};
+use matrixcompare_mock::mock_matrix;
+use std::panic::{self, catch_unwind, UnwindSafe};
+use std::sync::{Arc, Mutex};
+
+/// Runs the closure, which must panic, and returns the file and line of the panic.
+fn panic_location<R>(f: impl FnOnce() -> R + UnwindSafe) -> (String, u32) {
+    let location = Arc::new(Mutex::new(None));
+    let hook_location = location.clone();
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let location = info.location().expect("Panic has no location");
+        *hook_location.lock().unwrap() = Some((location.file().to_string(), location.line()));
+    }));
+    let result = catch_unwind(f);
+    panic::set_hook(previous_hook);
+    assert!(result.is_err(), "Closure unexpectedly did not panic");
+    let location = location.lock().unwrap().take();
+    location.expect("Panic hook was not invoked")
+}
+
+// The panic hook is global, so all checks are made in a single test to avoid interference
+// between tests running in parallel.
+#[test]
+fn panics_are_reported_at_assertion_site() {
+    let x = mock_matrix![1.0, 2.0];
+    let y = mock_matrix![1.0, 3.0];
+    let expected = |line| (file!().to_string(), line);
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_matrix_eq!(x, y));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_matrix_eq!(x, y, comp = abs, tol = 0.1));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_matrix_eq!(x, y, labels = ("a", "b")));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_matrix_approx_eq_with_report!(x, y, comp = float));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_matrix_eq_triplets!(x, [(0, 0, 1.0)]));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_scalar_eq!(1.0, 2.0));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_scalar_ne!(1.0, 1.0));
+    assert_eq!(location, expected(line));
+
+    let line = line!() + 1;
+    let location = panic_location(|| assert_slice_eq!([1.0, 2.0], [1.0, 3.0], comp = exact));
+    assert_eq!(location, expected(line));
+}
@@ -0,0 +1,180 @@
+use matrixcompare::{
+    compare_matrices_relative_norm, DimensionMismatch, Entry, MatrixComparisonFailure, Norm,
+    NormMismatch,
+};
+use matrixcompare_mock::{dense_matrix_strategy, mock_matrix, MockSparseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+const NORMS: [Norm; 4] = [Norm::Frobenius, Norm::Max, Norm::One, Norm::Infinity];
+
+#[test]
+fn small_perturbation_is_accepted() {
+    let reference = mock_matrix![1.0, 2.0; 3.0, 4.0];
+    let perturbed = mock_matrix![1.0, 2.0; 3.0, 4.001];
+
+    for &norm in &NORMS {
+        assert!(compare_matrices_relative_norm(&perturbed, &reference, norm, 1e-3).is_ok());
+        assert!(compare_matrices_relative_norm(&perturbed, &reference, norm, 1e-5).is_err());
+    }
+}
+
+#[test]
+fn failure_reports_relative_residual() {
+    let a = mock_matrix![3.0, 4.0; 0.0, 0.0];
+    let b = mock_matrix![0.0, 0.0; 6.0, 8.0];
+
+    let expected = |residual_norm: f64, reference_norm: f64, description: &str| {
+        MatrixComparisonFailure::NormMismatch(NormMismatch {
+            comparator_description: description.to_string(),
+            residual_norm,
+            reference_norm,
+            relative_residual: residual_norm / reference_norm,
+            reference_is_left: false,
+        })
+    };
+
+    assert_eq!(
+        compare_matrices_relative_norm(&a, &b, Norm::Frobenius, 0.1).unwrap_err(),
+        expected(
+            125f64.sqrt(),
+            10.0,
+            "relative Frobenius norm, ||X - Y|| <= 0.1 * ||reference||."
+        )
+    );
+    assert_eq!(
+        compare_matrices_relative_norm(&a, &b, Norm::Max, 0.1).unwrap_err(),
+        expected(
+            8.0,
+            8.0,
+            "relative max norm, ||X - Y|| <= 0.1 * ||reference||."
+        )
+    );
+    assert_eq!(
+        compare_matrices_relative_norm(&a, &b, Norm::One, 0.1).unwrap_err(),
+        expected(
+            12.0,
+            8.0,
+            "relative 1-norm, ||X - Y|| <= 0.1 * ||reference||."
+        )
+    );
+    assert_eq!(
+        compare_matrices_relative_norm(&a, &b, Norm::Infinity, 0.1).unwrap_err(),
+        expected(
+            14.0,
+            14.0,
+            "relative infinity norm, ||X - Y|| <= 0.1 * ||reference||."
+        )
+    );
+}
+
+#[test]
+fn zero_reference_only_matches_zero() {
+    let zero = mock_matrix![0.0, 0.0; 0.0, 0.0];
+    let nonzero = mock_matrix![0.0, 1e-3; 0.0, 0.0];
+
+    for &norm in &NORMS {
+        assert!(compare_matrices_relative_norm(&zero, &zero, norm, 0.0).is_ok());
+        let failure = compare_matrices_relative_norm(&nonzero, &zero, norm, 1.0).unwrap_err();
+        match failure {
+            MatrixComparisonFailure::NormMismatch(mismatch) => {
+                assert_eq!(mismatch.relative_residual, f64::INFINITY)
+            }
+            failure => panic!("Unexpected failure: {:?}", failure),
+        }
+    }
+}
+
+#[test]
+fn infinite_tolerance_accepts_zero_reference() {
+    let zero = mock_matrix![0.0, 0.0; 0.0, 0.0];
+    let nonzero = mock_matrix![0.0, 1e-3; 0.0, 0.0];
+
+    for &norm in &NORMS {
+        assert!(compare_matrices_relative_norm(&nonzero, &zero, norm, f64::INFINITY).is_ok());
+        assert!(compare_matrices_relative_norm(&zero, &zero, norm, f64::INFINITY).is_ok());
+    }
+}
+
+#[test]
+fn reversed_failure_refers_to_reference() {
+    let a = mock_matrix![3.0, 4.0];
+    let b = mock_matrix![0.0, 5.0];
+
+    let failure = compare_matrices_relative_norm(&a, &b, Norm::Max, 0.1).unwrap_err();
+    assert_eq!(
+        failure.to_string(),
+        "Matrices X (left) and Y (right) have a relative residual of 0.6.
+||X - Y|| = 3, ||Y|| = 5 (reference).
+
+Comparison criterion: relative max norm, ||X - Y|| <= 0.1 * ||reference||."
+    );
+    assert!(failure
+        .reverse()
+        .to_string()
+        .contains("||X - Y|| = 3, ||X|| = 5 (reference)."));
+}
+
+#[test]
+fn nan_elements_are_never_accepted() {
+    let a = mock_matrix![1.0, f64::NAN; 3.0, 4.0];
+    let b = mock_matrix![1.0, 2.0; 3.0, 4.0];
+
+    for &norm in &NORMS {
+        assert!(compare_matrices_relative_norm(&a, &b, norm, f64::INFINITY).is_err());
+        assert!(compare_matrices_relative_norm(&b, &a, norm, f64::INFINITY).is_err());
+    }
+}
+
+#[test]
+fn sparse_matrices_are_compared_by_value() {
+    let dense = mock_matrix![0.0, 2.0; 3.0, 0.0];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(1, 0, 3.0), (0, 1, 2.0)]);
+
+    for &norm in &NORMS {
+        assert!(compare_matrices_relative_norm(&dense, &sparse, norm, 0.0).is_ok());
+        assert!(compare_matrices_relative_norm(&sparse, &dense, norm, 0.0).is_ok());
+    }
+}
+
+#[test]
+fn invalid_sparse_entries_are_reported() {
+    let dense = mock_matrix![0.0, 2.0; 3.0, 0.0];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(2, 0, 3.0)]);
+
+    assert_eq!(
+        compare_matrices_relative_norm(&dense, &sparse, Norm::Frobenius, 1.0),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Right((2, 0))
+        ))
+    );
+}
+
+#[test]
+fn mismatched_dimensions() {
+    let a = mock_matrix![1.0, 2.0];
+    let b = mock_matrix![1.0; 2.0];
+
+    assert_eq!(
+        compare_matrices_relative_norm(&a, &b, Norm::Frobenius, 1.0),
+        Err(MatrixComparisonFailure::MismatchedDimensions(
+            DimensionMismatch {
+                dim_left: (1, 2),
+                dim_right: (2, 1),
+            }
+        ))
+    );
+}
+
+proptest! {
+    #[test]
+    fn matrix_matches_itself(
+        a in dense_matrix_strategy(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE, -1e3..1e3)
+    ) {
+        for &norm in &NORMS {
+            prop_assert!(compare_matrices_relative_norm(&a, &a, norm, 0.0).is_ok());
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use matrixcompare::comparators::{ExactElementwiseComparator, ExactError};
+use matrixcompare::{
+    analyze_matrices, assert_matrix_approx_eq_with_report, compare_matrices, ComparisonReport,
+    DimensionMismatch, MatrixComparisonFailure, MultiComparisonReport,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockDenseMatrix,
+    MockSparseMatrix,
+};
+use proptest::prelude::*;
+use std::panic::catch_unwind;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn analyze_reports_max_abs_diff() {
+    let a = mock_matrix![1, 5, 3;
+                         4, 2, 6u32];
+    let b = mock_matrix![1, 2, 3;
+                         4, 5, 10u32];
+
+    let report = analyze_matrices(&a, &b).unwrap();
+    let expected = ComparisonReport {
+        num_compared: 6,
+        max_abs_diff: 4,
+        max_abs_diff_coord: Some((1, 2)),
+    };
+    assert_eq!(report, expected);
+}
+
+#[test]
+fn analyze_empty_matrices() {
+    let a = MockDenseMatrix::<f64>::from_row_major(0, 3, vec![]);
+
+    let report = analyze_matrices(&a, &a).unwrap();
+    assert_eq!(report.num_compared, 0);
+    assert_eq!(report.max_abs_diff, 0.0);
+    assert_eq!(report.max_abs_diff_coord, None);
+}
+
+#[test]
+fn analyze_ignores_nan() {
+    let a = mock_matrix![1.0, f64::NAN, 3.0];
+    let b = mock_matrix![1.5, 2.0, 3.0];
+
+    let report = analyze_matrices(&a, &b).unwrap();
+    assert_eq!(report.max_abs_diff, 0.5);
+    assert_eq!(report.max_abs_diff_coord, Some((0, 0)));
+}
+
+#[test]
+fn analyze_dense_against_sparse() {
+    let dense = mock_matrix![0, 3;
+                             1, 0];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 3), (1, 1, -2)]);
+
+    let report = analyze_matrices(&dense, &sparse).unwrap();
+    assert_eq!(report.max_abs_diff, 2);
+    assert_eq!(report.max_abs_diff_coord, Some((1, 1)));
+}
+
+#[test]
+fn analyze_mismatched_dimensions() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 2, 3];
+
+    let expected = MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+        dim_left: (1, 2),
+        dim_right: (1, 3),
+    });
+    assert_eq!(analyze_matrices(&a, &b), Err(expected));
+}
+
+#[test]
+fn assert_with_report_returns_report_on_success() {
+    let x = mock_matrix![1.0, 2.0;
+                         3.0, 4.0];
+    let y = mock_matrix![1.0, 2.25;
+                         3.0, 4.0];
+
+    let report = assert_matrix_approx_eq_with_report!(x, y, comp = abs, tol = 0.5);
+    assert_eq!(report.max_abs_diff, 0.25);
+    assert_eq!(report.max_abs_diff_coord, Some((0, 1)));
+
+    let report = assert_matrix_approx_eq_with_report!(x, x);
+    assert_eq!(report.max_abs_diff, 0.0);
+}
+
+#[test]
+fn assert_with_report_accepts_labels_and_context() {
+    let x = mock_matrix![1.0, 2.0];
+    let y = mock_matrix![1.0, 2.25];
+
+    let report = assert_matrix_approx_eq_with_report!(
+        x,
+        y,
+        comp = abs,
+        tol = 0.5,
+        labels = ("predicted", "expected"),
+        context = "step 3"
+    );
+    assert_eq!(report.max_abs_diff, 0.25);
+
+    let payload = catch_unwind(|| {
+        assert_matrix_approx_eq_with_report!(
+            x,
+            y,
+            comp = abs,
+            tol = 0.1,
+            labels = ("predicted", "expected"),
+            context = "step 3"
+        );
+    })
+    .unwrap_err();
+    let message = payload.downcast::<String>().unwrap();
+    assert!(
+        message.starts_with("step 3\nMatrices predicted (left) and expected (right)"),
+        "Unexpected message: {}",
+        message
+    );
+}
+
+#[test]
+#[should_panic]
+fn assert_with_report_panics_on_failure() {
+    let x = mock_matrix![1.0, 2.0];
+    let y = mock_matrix![1.0, 3.0];
+
+    assert_matrix_approx_eq_with_report!(x, y, comp = abs, tol = 0.5);
+}
+
+#[test]
+fn multi_report_collects_failures() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 3];
+    let c = mock_matrix![1, 2, 3];
+
+    let mut report = MultiComparisonReport::new();
+    assert!(report.is_ok());
+    report.assert_all_ok();
+    assert_eq!(report.to_string(), "All 0 matrix comparisons succeeded.");
+
+    report.add(
+        "first",
+        compare_matrices(&a, &a, &ExactElementwiseComparator),
+    );
+    report.add(
+        "second",
+        compare_matrices(&a, &b, &ExactElementwiseComparator),
+    );
+    report.add(
+        String::from("third"),
+        compare_matrices(&a, &c, &ExactElementwiseComparator),
+    );
+
+    assert!(!report.is_ok());
+    assert_eq!(report.num_results(), 3);
+    let names: Vec<_> = report
+        .failures()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, vec!["second", "third"]);
+    assert_eq!(
+        report.failures()[1].1,
+        MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+            dim_left: (1, 2),
+            dim_right: (1, 3),
+        })
+    );
+}
+
+#[test]
+fn multi_report_output() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 3];
+    let c = mock_matrix![1, 2, 3];
+
+    let mut report = MultiComparisonReport::new();
+    report.add(
+        "first",
+        compare_matrices(&a, &a, &ExactElementwiseComparator),
+    );
+    report.add(
+        "second",
+        compare_matrices(&a, &b, &ExactElementwiseComparator),
+    );
+    report.add(
+        "third",
+        compare_matrices(&a, &c, &ExactElementwiseComparator),
+    );
+
+    let expected = format!(
+        "2 of 3 matrix comparisons failed: second, third.
+
+Comparison second failed:
+{}
+
+Comparison third failed:
+{}
+",
+        report.failures()[0].1,
+        report.failures()[1].1
+    );
+    assert_eq!(report.to_string(), expected);
+
+    let mut report = MultiComparisonReport::<i32, ExactError>::new();
+    report.add("first", Ok(()));
+    report.add("second", Ok(()));
+    assert_eq!(report.to_string(), "All 2 matrix comparisons succeeded.");
+}
+
+#[test]
+#[should_panic(expected = "1 of 2 matrix comparisons failed: second.")]
+fn multi_report_assert_all_ok_panics_on_failure() {
+    let a = mock_matrix![1, 2];
+    let b = mock_matrix![1, 3];
+
+    let mut report = MultiComparisonReport::new();
+    report.add(
+        "first",
+        compare_matrices(&a, &a, &ExactElementwiseComparator),
+    );
+    report.add(
+        "second",
+        compare_matrices(&a, &b, &ExactElementwiseComparator),
+    );
+    report.assert_all_ok();
+}
+
+proptest! {
+    #[test]
+    fn zero_max_abs_diff_iff_exactly_equal(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let exact = compare_matrices(&a, &b, &ExactElementwiseComparator);
+        match analyze_matrices(&a, &b) {
+            Ok(report) => {
+                prop_assert!(exact.is_ok() == (report.max_abs_diff == 0));
+            }
+            Err(_) => prop_assert!(exact.is_err()),
+        }
+    }
+}
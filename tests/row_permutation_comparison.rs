@@ -0,0 +1,153 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{
+    compare_matrices_up_to_row_permutation, Entry, MatrixComparisonFailure, RowPermutationMismatch,
+};
+use matrixcompare_core::{Access, Matrix};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockDenseMatrix,
+    MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+/// Returns the matrix with its rows permuted, such that row i of the result is row perm[i] of
+/// the input.
+fn permute_rows(matrix: &MockDenseMatrix<i64>, perm: &[usize]) -> MockDenseMatrix<i64> {
+    let cols = matrix.cols();
+    let access = match matrix.access() {
+        Access::Dense(access) => access,
+        _ => unreachable!(),
+    };
+    let data = perm
+        .iter()
+        .flat_map(|&i| (0..cols).map(move |j| access.fetch_single(i, j)))
+        .collect();
+    MockDenseMatrix::from_row_major(matrix.rows(), cols, data)
+}
+
+#[test]
+fn permuted_rows_compare_equal() {
+    let a = mock_matrix![1.0, 2.0;
+                         3.0, 4.0;
+                         5.0, 6.0];
+    let b = mock_matrix![5.0, 6.0;
+                         1.0, 2.0001;
+                         3.0, 4.0];
+
+    let c = AbsoluteElementwiseComparator { tol: 1e-3 };
+    assert!(compare_matrices_up_to_row_permutation(&a, &b, &c).is_ok());
+}
+
+#[test]
+fn greedy_choice_is_corrected_by_matching() {
+    // The first row of `a` matches both rows of `b`, but the second row of `a`
+    // only matches the first row of `b`.
+    let a = mock_matrix![1.0;
+                         2.0];
+    let b = mock_matrix![1.5;
+                         0.5];
+
+    let c = AbsoluteElementwiseComparator { tol: 0.5 };
+    assert!(compare_matrices_up_to_row_permutation(&a, &b, &c).is_ok());
+}
+
+#[test]
+fn unmatched_rows_are_reported() {
+    let a = mock_matrix![1, 2;
+                         3, 4;
+                         5, 6];
+    let b = mock_matrix![5, 6;
+                         0, 0;
+                         1, 2];
+
+    let c = ExactElementwiseComparator;
+    let err = compare_matrices_up_to_row_permutation(&a, &b, &c).unwrap_err();
+    let expected = RowPermutationMismatch {
+        comparator_description: "exact equality x == y.".to_string(),
+        unmatched_left: vec![1],
+        unmatched_right: vec![1],
+    };
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::RowPermutationMismatch(Box::new(expected))
+    );
+}
+
+#[test]
+fn duplicate_rows_must_be_matched_with_multiplicity() {
+    let a = mock_matrix![1, 2;
+                         1, 2];
+    let b = mock_matrix![1, 2;
+                         3, 4];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_up_to_row_permutation(&a, &b, &c).is_err());
+}
+
+#[test]
+fn sparse_matrices_are_supported() {
+    let a = MockSparseMatrix::from_triplets(3, 2, vec![(0, 0, 1), (2, 1, 2)]);
+    let b = mock_matrix![0, 2;
+                         0, 0;
+                         1, 0];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_up_to_row_permutation(&a, &b, &c).is_ok());
+
+    let out_of_bounds = MockSparseMatrix::from_triplets(3, 2, vec![(0, 2, 1)]);
+    let err = compare_matrices_up_to_row_permutation(&b, &out_of_bounds, &c).unwrap_err();
+    assert_eq!(
+        err,
+        MatrixComparisonFailure::SparseEntryOutOfBounds(Entry::Right((0, 2)))
+    );
+}
+
+#[test]
+fn mismatched_dimensions_are_reported() {
+    let a = mock_matrix![1, 2; 3, 4];
+    let b = mock_matrix![1, 2; 3, 4; 5, 6];
+
+    let c = ExactElementwiseComparator;
+    assert!(matches!(
+        compare_matrices_up_to_row_permutation(&a, &b, &c),
+        Err(MatrixComparisonFailure::MismatchedDimensions(_))
+    ));
+}
+
+/// A strategy producing a dense matrix along with a permutation of its rows.
+fn matrix_and_row_permutation() -> impl Strategy<Value = (MockDenseMatrix<i64>, Vec<usize>)> {
+    dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE).prop_flat_map(|matrix| {
+        let perm: Vec<usize> = (0..matrix.rows()).collect();
+        (Just(matrix), Just(perm).prop_shuffle())
+    })
+}
+
+proptest! {
+    #[test]
+    fn row_permuted_matrix_compares_equal(
+        (matrix, perm) in matrix_and_row_permutation()
+    ) {
+        let permuted = permute_rows(&matrix, &perm);
+        let c = ExactElementwiseComparator;
+        prop_assert!(compare_matrices_up_to_row_permutation(&matrix, &permuted, &c).is_ok());
+    }
+
+    #[test]
+    fn row_permutation_comparison_is_symmetric(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let result1 = compare_matrices_up_to_row_permutation(&a, &b, &c);
+        let result2 = compare_matrices_up_to_row_permutation(&b, &a, &c);
+
+        // The unmatched rows depend on the matching found, so only compare success and failure
+        // kinds in general, but the dimension mismatch must be exactly reversed
+        prop_assert_eq!(result1.is_ok(), result2.is_ok());
+        if let Err(MatrixComparisonFailure::MismatchedDimensions(_)) = result1 {
+            prop_assert_eq!(result1, reverse_result(result2));
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use matrixcompare::assert_scalar_eq;
 use matrixcompare::comparators::{ElementwiseComparator, ExactElementwiseComparator, ExactError};
-use matrixcompare::compare_scalars;
+use matrixcompare::{assert_scalar_eq, assert_scalar_ne};
+use matrixcompare::{compare_matrices, compare_scalars};
+use matrixcompare_mock::mock_matrix;
 
 #[test]
 fn scalar_comparison_reports_correct_mismatch() {
@@ -19,10 +20,44 @@ fn scalar_comparison_reports_correct_mismatch() {
             comparator_description: ElementwiseComparator::<f64>::description(&comp),
         };
 
-        assert_eq!(compare_scalars(&x, &y, comp).unwrap_err(), expected_err);
+        assert_eq!(compare_scalars(&x, &y, &comp).unwrap_err(), expected_err);
     }
 }
 
+/// A comparator that is neither `Copy` nor `Clone`, to make sure that it can be reused.
+struct ToleranceList {
+    tolerances: Vec<f64>,
+}
+
+impl ElementwiseComparator<f64> for ToleranceList {
+    type Error = ExactError;
+
+    fn compare(&self, x: &f64, y: &f64) -> Result<(), ExactError> {
+        if self.tolerances.iter().any(|tol| (x - y).abs() <= *tol) {
+            Ok(())
+        } else {
+            Err(ExactError)
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("any of the tolerances {:?}", self.tolerances)
+    }
+}
+
+#[test]
+fn comparator_is_reused_across_scalar_and_matrix_comparisons() {
+    let comp = ToleranceList {
+        tolerances: vec![0.0, 0.5],
+    };
+
+    assert!(compare_scalars(&1.0, &1.5, &comp).is_ok());
+    assert!(compare_scalars(&1.0, &2.0, &comp).is_err());
+    assert!(compare_matrices(mock_matrix![1.0, 2.0], mock_matrix![1.5, 2.0], &comp).is_ok());
+    assert_scalar_eq!(1.0, 1.25, comp = comp);
+    assert!(compare_scalars(&1.0, &1.0, &comp).is_ok());
+}
+
 #[test]
 pub fn scalar_eq_default_compare_self_for_integer() {
     let x = 2;
@@ -97,6 +132,22 @@ pub fn scalar_eq_ulp_mismatched_elements() {
     assert_scalar_eq!(x, y, comp = ulp, tol = 4);
 }
 
+#[test]
+pub fn scalar_eq_le_and_ge() {
+    let x = 2.0;
+    let y = 3.0;
+    assert_scalar_eq!(x, y, comp = le, tol = 0.0);
+    assert_scalar_eq!(y, x, comp = ge, tol = 0.0);
+}
+
+#[test]
+#[should_panic]
+pub fn scalar_eq_le_violated() {
+    let x = 4.0;
+    let y = 3.0;
+    assert_scalar_eq!(x, y, comp = le, tol = 0.5);
+}
+
 #[test]
 pub fn scalar_eq_float_compare_self() {
     let x = 2.0;
@@ -142,4 +193,63 @@ pub fn scalar_eq_pass_by_ref() {
     assert_scalar_eq!(&x, &x, comp = ulp, tol = 0);
     assert_scalar_eq!(&x, &x, comp = float);
     assert_scalar_eq!(&x, &x, comp = float, eps = 0.0, ulp = 0);
+    assert_scalar_eq!(&x, &x, comp = le, tol = 0.0);
+    assert_scalar_eq!(&x, &x, comp = ge, tol = 0.0);
+    assert_scalar_eq!(&x, &x, comp = decimal, places = 6);
+    assert_scalar_eq!(&x, &x, comp = percent, tol = 0.0);
+}
+
+#[test]
+pub fn scalar_ne_mismatched_elements() {
+    let x = 3.0;
+    let y = 4.0;
+    assert_scalar_ne!(x, y);
+    assert_scalar_ne!(x, y, comp = exact);
+    assert_scalar_ne!(x, y, comp = abs, tol = 0.5);
+    assert_scalar_ne!(x, y, comp = ulp, tol = 4);
+    assert_scalar_ne!(x, y, comp = float);
+    assert_scalar_ne!(x, y, comp = float, eps = 1e-6, ulp = 4);
+    assert_scalar_ne!(y, x, comp = le, tol = 0.5);
+    assert_scalar_ne!(x, y, comp = ge, tol = 0.5);
+}
+
+#[test]
+#[should_panic(expected = "Scalars `x` and `x` compare equal, but were expected not to.")]
+pub fn scalar_ne_default_compare_self() {
+    let x = 2;
+    assert_scalar_ne!(x, x);
+}
+
+#[test]
+#[should_panic]
+pub fn scalar_ne_abs_within_tolerance() {
+    let x = 3.00;
+    let y = 3.05;
+    assert_scalar_ne!(x, y, comp = abs, tol = 0.1);
+}
+
+#[test]
+#[should_panic]
+pub fn scalar_ne_float_compare_self() {
+    let x = 2.0;
+    assert_scalar_ne!(x, x, comp = float);
+}
+
+#[test]
+pub fn scalar_ne_pass_by_ref() {
+    let x = 0.0;
+    let y = 1.0;
+
+    // Exercise all the macro definitions and make sure that we are able to call it
+    // when the arguments are references.
+    assert_scalar_ne!(&x, &y);
+    assert_scalar_ne!(&x, &y, comp = exact);
+    assert_scalar_ne!(&x, &y, comp = abs, tol = 0.5);
+    assert_scalar_ne!(&x, &y, comp = ulp, tol = 0);
+    assert_scalar_ne!(&x, &y, comp = float);
+    assert_scalar_ne!(&x, &y, comp = float, eps = 0.0, ulp = 0);
+    assert_scalar_ne!(&y, &x, comp = le, tol = 0.0);
+    assert_scalar_ne!(&x, &y, comp = ge, tol = 0.0);
+    assert_scalar_ne!(&x, &y, comp = decimal, places = 6);
+    assert_scalar_ne!(&x, &y, comp = percent, tol = 5.0);
 }
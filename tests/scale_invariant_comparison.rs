@@ -0,0 +1,74 @@
+use matrixcompare::comparators::AbsoluteElementwiseComparator;
+use matrixcompare::{compare_matrices_up_to_scale, DimensionMismatch, MatrixComparisonFailure};
+use matrixcompare_mock::{mock_matrix, MockDenseMatrix, MockSparseMatrix};
+use proptest::prelude::*;
+
+#[test]
+fn eigenvector_with_flipped_sign_and_scale() {
+    let v = mock_matrix![1.0; 2.0; -2.0];
+    let w = mock_matrix![-0.5; -1.0; 1.0];
+
+    let c = AbsoluteElementwiseComparator { tol: 1e-12 };
+    assert!(compare_matrices_up_to_scale(&v, &w, &c).is_ok());
+    assert!(compare_matrices_up_to_scale(&w, &v, &c).is_ok());
+}
+
+#[test]
+fn failure_reports_estimated_factor() {
+    // The best fit of [1, 2] * alpha to [2, 0] is alpha = 0.4
+    let a = mock_matrix![2.0, 0.0];
+    let b = mock_matrix![1.0, 2.0];
+
+    let c = AbsoluteElementwiseComparator { tol: 1e-12 };
+    match compare_matrices_up_to_scale(&a, &b, &c).unwrap_err() {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 2);
+            assert!(mismatch.comparator_description.ends_with(
+                "Y (right) was scaled by an estimated best-fit factor of 0.4 before comparison."
+            ));
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn zero_right_matrix() {
+    let zero = MockSparseMatrix::<f64>::from_triplets(2, 2, vec![]);
+    let dense_zero = mock_matrix![0.0, 0.0;
+                                  0.0, 0.0];
+    let nonzero = mock_matrix![0.0, 1.0;
+                               0.0, 0.0];
+
+    let c = AbsoluteElementwiseComparator { tol: 1e-12 };
+    assert!(compare_matrices_up_to_scale(&dense_zero, &zero, &c).is_ok());
+    assert!(compare_matrices_up_to_scale(&nonzero, &zero, &c).is_err());
+}
+
+#[test]
+fn mismatched_dimensions() {
+    let a = mock_matrix![1.0, 2.0];
+    let b = mock_matrix![1.0; 2.0];
+
+    let c = AbsoluteElementwiseComparator { tol: 1e-12 };
+    let expected = MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+        dim_left: (1, 2),
+        dim_right: (2, 1),
+    });
+    assert_eq!(compare_matrices_up_to_scale(&a, &b, &c), Err(expected));
+}
+
+proptest! {
+    #[test]
+    fn scaled_matrix_matches_original(
+        data in proptest::collection::vec(-100i32..100, 6),
+        alpha in prop_oneof![-8i32..-1, 1i32..8]
+    ) {
+        // Integer-valued data and power-of-two scale factors avoid rounding errors
+        let alpha = f64::from(alpha).signum() * 2f64.powi(alpha.abs());
+        let a = MockDenseMatrix::from_row_major(2, 3, data.iter().map(|&x| f64::from(x)).collect());
+        let b = MockDenseMatrix::from_row_major(2, 3, data.iter().map(|&x| alpha * f64::from(x)).collect());
+
+        let c = AbsoluteElementwiseComparator { tol: 1e-9 };
+        prop_assert!(compare_matrices_up_to_scale(&a, &b, &c).is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+use matrixcompare::comparators::{AbsoluteElementwiseComparator, ExactElementwiseComparator};
+use matrixcompare::{compare_matrices, compare_matrices_scaled, MatrixComparisonFailure};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn meters_against_millimeters() {
+    let meters = mock_matrix![1.0, 0.25;
+                              0.0, 2.5];
+    let millimeters = mock_matrix![1000.0, 250.0;
+                                   0.0,    2500.0];
+
+    let c = AbsoluteElementwiseComparator { tol: 1e-12 };
+    assert!(compare_matrices_scaled(&meters, &millimeters, 1e-3, &c).is_ok());
+    assert!(compare_matrices(&meters, &millimeters, &c).is_err());
+}
+
+#[test]
+fn mismatches_contain_scaled_values() {
+    let a = mock_matrix![2, 4];
+    let b = mock_matrix![1, 3];
+
+    let err = compare_matrices_scaled(&a, &b, 2, &ExactElementwiseComparator).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 1);
+            let element = &mismatch.mismatches[0];
+            assert_eq!((element.row, element.col), (0, 1));
+            assert_eq!((element.left, element.right), (4, 6));
+            assert!(mismatch
+                .comparator_description
+                .ends_with("Y (right) was scaled by a factor of 2 before comparison."));
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn sparse_matrices_are_scaled() {
+    let dense = mock_matrix![0, 6;
+                             3, 0];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 1, 2), (1, 0, 1)]);
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_scaled(&dense, &sparse, 3, &c).is_ok());
+}
+
+proptest! {
+    #[test]
+    fn unit_factor_agrees_with_compare_matrices(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let scaled_ok = compare_matrices_scaled(&a, &b, 1, &c).is_ok();
+        prop_assert_eq!(scaled_ok, compare_matrices(&a, &b, &c).is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "self-comparison-warning")]
+
+use matrixcompare::assert_matrix_eq;
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::compare_matrices;
+use matrixcompare_mock::mock_matrix;
+use std::process::Command;
+
+/// Set when the test binary is re-run in a child process, whose error output is inspected.
+const CHILD_ENV_VAR: &str = "MATRIXCOMPARE_SELF_COMPARISON_CHILD";
+
+#[test]
+fn self_comparison_warning_does_not_affect_result() {
+    let x = mock_matrix![1.0, 2.0;
+                         3.0, 4.0];
+    let y = mock_matrix![1.0, 2.0;
+                         3.0, 5.0];
+    assert!(compare_matrices(&x, &x, &ExactElementwiseComparator).is_ok());
+    assert!(compare_matrices(&x, &y, &ExactElementwiseComparator).is_err());
+    assert_matrix_eq!(x, x, comp = abs, tol = 0.0);
+}
+
+#[test]
+fn self_comparison_warning_is_written_to_error_output() {
+    // The line of the self-comparison below, which the warning should point at
+    let line = line!() + 5;
+    if std::env::var_os(CHILD_ENV_VAR).is_some() {
+        let x = mock_matrix![1, 2];
+        let y = mock_matrix![1, 2];
+        assert!(compare_matrices(&x, &y, &ExactElementwiseComparator).is_ok());
+        assert!(compare_matrices(&x, &x, &ExactElementwiseComparator).is_ok());
+        return;
+    }
+
+    // The warning is written directly to the error output, which the test harness does not
+    // capture when run with --nocapture, so we run this test again in a child process
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "self_comparison_warning_is_written_to_error_output",
+            "--exact",
+            "--nocapture",
+        ])
+        .env(CHILD_ENV_VAR, "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let warnings: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.starts_with("warning:"))
+        .collect();
+    if cfg!(debug_assertions) {
+        let location = format!("{}:{}:", file!(), line);
+        assert_eq!(warnings.len(), 1, "error output: {}", stderr);
+        assert!(warnings[0].starts_with("warning: matrix compared with itself at "));
+        assert!(warnings[0].contains(&location), "error output: {}", stderr);
+        assert!(warnings[0]
+            .ends_with(". The comparison always succeeds, so it cannot catch any errors."));
+    } else {
+        assert!(warnings.is_empty(), "error output: {}", stderr);
+    }
+}
@@ -0,0 +1,150 @@
+use matrixcompare::comparators::{
+    AbsoluteElementwiseComparator, AbsoluteError, ExactElementwiseComparator,
+};
+use matrixcompare::{
+    assert_slice_eq, compare_slices, LengthMismatch, SliceComparisonFailure,
+    SliceElementComparisonFailure, SliceElementsMismatch,
+};
+use proptest::prelude::*;
+
+#[test]
+fn slice_comparison_reports_mismatched_indices() {
+    let x = [1.0, 2.0, 3.0, 4.0];
+    let y = [1.0, 2.5, 3.0, 3.0];
+    let comp = AbsoluteElementwiseComparator { tol: 0.1 };
+
+    let expected = SliceComparisonFailure::MismatchedElements(SliceElementsMismatch {
+        comparator_description: "absolute difference, |x - y| <= 0.1.".to_string(),
+        mismatches: vec![
+            SliceElementComparisonFailure {
+                left: 2.0,
+                right: 2.5,
+                error: AbsoluteError(0.5),
+                index: 1,
+            },
+            SliceElementComparisonFailure {
+                left: 4.0,
+                right: 3.0,
+                error: AbsoluteError(1.0),
+                index: 3,
+            },
+        ],
+        len: 4,
+    });
+    assert_eq!(compare_slices(&x, &y, &comp), Err(expected));
+}
+
+#[test]
+fn slice_comparison_reports_mismatched_lengths() {
+    let x = [1, 2, 3];
+    let y = [1, 2];
+
+    assert_eq!(
+        compare_slices(&x, &y, &ExactElementwiseComparator),
+        Err(SliceComparisonFailure::MismatchedLengths(LengthMismatch {
+            len_left: 3,
+            len_right: 2
+        }))
+    );
+}
+
+#[test]
+fn slice_comparison_output() {
+    let x = [1, 2, 3];
+    let y = [1, 5, 3];
+
+    let err = compare_slices(&x, &y, &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r"Slices x (left) and y (right) have 1 mismatched element pairs out of 3.
+The mismatched elements are listed below, in the format
+[index]: x = x[index], y = y[index].
+
+ [1]: x = 2, y = 5.
+
+Comparison criterion: exact equality x == y."
+    );
+
+    let err = compare_slices(&x, &y[..2], &ExactElementwiseComparator).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r"Lengths of slices x (left) and y (right) do not match.
+ len(x) = 3
+ len(y) = 2"
+    );
+}
+
+#[test]
+fn empty_slices_compare_equal() {
+    let empty: [f64; 0] = [];
+    assert!(compare_slices(&empty, &empty, &ExactElementwiseComparator).is_ok());
+}
+
+#[test]
+pub fn slice_eq_accepts_vecs_arrays_and_slices() {
+    let x: Vec<f64> = (1..=3).map(f64::from).collect();
+    let y = [1.0, 2.0, 3.0];
+    assert_slice_eq!(x, y);
+    assert_slice_eq!(&x[1..], y[1..]);
+    assert_slice_eq!(x, y, comp = abs, tol = 0.0);
+}
+
+#[test]
+pub fn slice_eq_abs_within_tolerance() {
+    let x = [1.0, 2.0, 3.0];
+    let y = [1.05, 2.0, 2.95];
+    assert_slice_eq!(x, y, comp = abs, tol = 0.1);
+}
+
+#[test]
+#[should_panic]
+pub fn slice_eq_abs_mismatched_elements() {
+    let x = [1.0, 2.0, 3.0];
+    let y = [1.05, 2.0, 2.95];
+    assert_slice_eq!(x, y, comp = abs, tol = 0.01);
+}
+
+#[test]
+#[should_panic]
+pub fn slice_eq_mismatched_lengths() {
+    let x = [1, 2, 3];
+    let y = [1, 2];
+    assert_slice_eq!(x, y);
+}
+
+#[test]
+pub fn slice_eq_evaluates_tolerance_once() {
+    let x = [1.0, 2.0, 3.0];
+    let y = [1.05, 2.0, 2.95];
+    let mut evaluations = 0;
+    let mut tol = |tol| {
+        evaluations += 1;
+        tol
+    };
+    assert_slice_eq!(x, y, comp = abs, tol = tol(0.1));
+    assert_slice_eq!(x, y, comp = percent, tol = tol(5.0));
+    assert_slice_eq!(x, y, comp = ratio, tol = tol(0.1));
+    assert_eq!(evaluations, 3);
+}
+
+proptest! {
+    #[test]
+    fn slice_comparison_agrees_with_scalar_comparison(
+        pairs in proptest::collection::vec((-10i64..10, -10i64..10), 0..20)
+    ) {
+        let (x, y): (Vec<_>, Vec<_>) = pairs.iter().cloned().unzip();
+        let comp = AbsoluteElementwiseComparator { tol: 2 };
+        let expected_indices: Vec<_> = (0..x.len())
+            .filter(|&i| (x[i] - y[i]).abs() > 2)
+            .collect();
+
+        match compare_slices(&x, &y, &comp) {
+            Ok(()) => prop_assert!(expected_indices.is_empty()),
+            Err(SliceComparisonFailure::MismatchedElements(mismatch)) => {
+                let indices: Vec<_> = mismatch.mismatches.iter().map(|m| m.index).collect();
+                prop_assert_eq!(indices, expected_indices);
+            }
+            Err(failure) => panic!("Unexpected failure: {:?}", failure),
+        }
+    }
+}
@@ -1,18 +1,18 @@
 use matrixcompare::comparators::ExactElementwiseComparator;
-use matrixcompare::{compare_matrices, Entry, MatrixComparisonFailure};
+use matrixcompare::{
+    compare_matrices, compare_matrices_with_options, ComparisonOptions, Entry,
+    MatrixComparisonFailure, OutOfBoundsEntries, PatternMismatch,
+};
 use matrixcompare_mock::{
-    sparse_matrix_strategy_i64, sparse_matrix_strategy_normal_f64, MockSparseMatrix,
+    mock_matrix, sparse_matrix_strategy_i64, sparse_matrix_strategy_normal_f64, MockSparseMatrix,
 };
 use proptest::prelude::*;
 
 mod common;
-use common::reverse_result;
-use common::MATRIX_DIM_RANGE;
+use common::{out_of_bounds_entries, reverse_result, without_nnz, MATRIX_DIM_RANGE};
 
 #[test]
 fn sparse_sparse_out_of_bounds() {
-    use MatrixComparisonFailure::SparseEntryOutOfBounds;
-
     macro_rules! assert_out_of_bounds_detected {
         // oob1 and oob2 contain the out of bounds coordinates for sparse1 and sparse 2
         ($sparse1:expr, $sparse2:expr, $oob1:expr, $oob2:expr) => {
@@ -20,9 +20,11 @@ fn sparse_sparse_out_of_bounds() {
             {
                 let result = compare_matrices(&$sparse1, &$sparse2, &ExactElementwiseComparator);
                 let err = result.unwrap_err();
-                match err {
-                    SparseEntryOutOfBounds(Entry::Left(coord)) => assert!($oob1.contains(&coord)),
-                    SparseEntryOutOfBounds(Entry::Right(coord)) => assert!($oob2.contains(&coord)),
+                match out_of_bounds_entries(err) {
+                    Some(entries) => {
+                        assert_eq!(entries.left, $oob1);
+                        assert_eq!(entries.right, $oob2);
+                    }
                     _ => panic!("Unexpected variant"),
                 }
             }
@@ -31,10 +33,12 @@ fn sparse_sparse_out_of_bounds() {
             {
                 let result = compare_matrices(&$sparse2, &$sparse1, &ExactElementwiseComparator);
                 let err = result.unwrap_err();
-                match err {
+                match out_of_bounds_entries(err) {
                     // Left-right get flipped since we're swapping the comparison order
-                    SparseEntryOutOfBounds(Entry::Right(coord)) => assert!($oob1.contains(&coord)),
-                    SparseEntryOutOfBounds(Entry::Left(coord)) => assert!($oob2.contains(&coord)),
+                    Some(entries) => {
+                        assert_eq!(entries.left, $oob2);
+                        assert_eq!(entries.right, $oob1);
+                    }
                     _ => panic!("Unexpected variant"),
                 }
             }
@@ -45,8 +49,8 @@ fn sparse_sparse_out_of_bounds() {
     {
         let sparse1 = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 2)]);
         let sparse2 = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, -3), (1, 2, 6), (2, 0, 1)]);
-        let oob1 = vec![];
-        let oob2 = vec![(2, 0)];
+        let oob1: [(usize, usize); 0] = [];
+        let oob2 = [(2, 0)];
         assert_out_of_bounds_detected!(sparse1, sparse2, oob1, oob2);
     }
 
@@ -54,8 +58,8 @@ fn sparse_sparse_out_of_bounds() {
     {
         let sparse1 = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 2)]);
         let sparse2 = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, -3), (1, 3, 1), (1, 2, 6)]);
-        let oob1 = vec![];
-        let oob2 = vec![(1, 3)];
+        let oob1: [(usize, usize); 0] = [];
+        let oob2 = [(1, 3)];
         assert_out_of_bounds_detected!(sparse1, sparse2, oob1, oob2);
     }
 
@@ -67,8 +71,8 @@ fn sparse_sparse_out_of_bounds() {
             3,
             vec![(2, 3, 1), (0, 1, -3), (2, 0, 1), (1, 2, 6)],
         );
-        let oob1 = vec![];
-        let oob2 = vec![(2, 0), (2, 3)];
+        let oob1: [(usize, usize); 0] = [];
+        let oob2 = [(2, 0), (2, 3)];
         assert_out_of_bounds_detected!(sparse1, sparse2, oob1, oob2);
     }
 
@@ -80,8 +84,8 @@ fn sparse_sparse_out_of_bounds() {
             3,
             vec![(2, 3, 1), (0, 1, -3), (2, 0, 1), (1, 2, 6)],
         );
-        let oob1 = vec![(4, 6)];
-        let oob2 = vec![(2, 0), (2, 3), (4, 6)];
+        let oob1 = [(4, 6)];
+        let oob2 = [(2, 0), (2, 3)];
         assert_out_of_bounds_detected!(sparse1, sparse2, oob1, oob2);
     }
 }
@@ -154,6 +158,109 @@ fn sparse_sparse_duplicate_entries() {
     }
 }
 
+#[test]
+fn sparse_sparse_reports_smallest_invalid_entry_regardless_of_order() {
+    use MatrixComparisonFailure::{DuplicateSparseEntry, SparseEntriesOutOfBounds};
+
+    let valid = MockSparseMatrix::from_triplets(3, 3, vec![(0, 0, 1)]);
+    let compare_with_triplets = |triplets: Vec<(usize, usize, i32)>| {
+        let sparse = MockSparseMatrix::from_triplets(3, 3, triplets);
+        compare_matrices(&valid, &sparse, &ExactElementwiseComparator).unwrap_err()
+    };
+
+    // The triplets contain out-of-bounds entries at (3, 0) and (0, 4), and a duplicate at (1, 1)
+    let triplets = vec![(3, 0, 1), (1, 1, 2), (0, 4, 1), (1, 1, 2), (2, 2, 1)];
+    let orders = vec![
+        triplets.clone(),
+        triplets.iter().rev().cloned().collect(),
+        vec![
+            triplets[1],
+            triplets[3],
+            triplets[0],
+            triplets[4],
+            triplets[2],
+        ],
+    ];
+    for order in orders {
+        assert_eq!(
+            compare_with_triplets(order),
+            SparseEntriesOutOfBounds(OutOfBoundsEntries {
+                left: vec![],
+                right: vec![(0, 4), (3, 0)]
+            })
+        );
+    }
+
+    let triplets = vec![(2, 0, 1), (2, 0, 1), (0, 2, 1), (0, 2, 1), (2, 4, 1)];
+    let orders = vec![triplets.clone(), triplets.iter().rev().cloned().collect()];
+    for order in orders {
+        assert_eq!(
+            compare_with_triplets(order),
+            DuplicateSparseEntry(Entry::Right((0, 2)))
+        );
+    }
+}
+
+#[test]
+fn sparse_sparse_huge_coordinates_are_out_of_bounds() {
+    use MatrixComparisonFailure::SparseEntryOutOfBounds;
+
+    let valid = MockSparseMatrix::from_triplets(3, 3, vec![(0, 0, 1)]);
+    let huge_coords = vec![(usize::MAX, 0), (0, usize::MAX), (usize::MAX, usize::MAX)];
+    for (i, j) in huge_coords {
+        let sparse = MockSparseMatrix::from_triplets(3, 3, vec![(i, j, 1), (0, 0, 1)]);
+        assert_eq!(
+            compare_matrices(&valid, &sparse, &ExactElementwiseComparator),
+            Err(SparseEntryOutOfBounds(Entry::Right((i, j))))
+        );
+        assert_eq!(
+            compare_matrices(&sparse, &valid, &ExactElementwiseComparator),
+            Err(SparseEntryOutOfBounds(Entry::Left((i, j))))
+        );
+    }
+}
+
+#[test]
+fn sparse_sparse_explicit_zeros_significant() {
+    let comp = ExactElementwiseComparator;
+    let options = ComparisonOptions::new().explicit_zeros_significant(true);
+    let a = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 1), (0, 2, 0), (1, 1, 0)]);
+    let b = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 1), (1, 0, 0), (1, 1, 0)]);
+    let c = MockSparseMatrix::from_triplets(2, 3, vec![(0, 0, 2), (1, 1, 0)]);
+
+    // By default, explicit zeros compare equal to implicit zeros
+    assert!(compare_matrices(&a, &b, &comp).is_ok());
+    assert!(compare_matrices_with_options(&a, &b, &comp, &ComparisonOptions::new()).is_ok());
+
+    let expected = PatternMismatch {
+        left_only: vec![(0, 2)],
+        right_only: vec![(1, 0)],
+    };
+    assert_eq!(
+        compare_matrices_with_options(&a, &b, &comp, &options),
+        Err(MatrixComparisonFailure::PatternMismatch(expected.clone()))
+    );
+    assert_eq!(
+        compare_matrices_with_options(&b, &a, &comp, &options),
+        Err(MatrixComparisonFailure::PatternMismatch(expected.reverse()))
+    );
+    assert!(compare_matrices_with_options(&a, &a, &comp, &options).is_ok());
+
+    // Mismatched elements take precedence over the pattern
+    match compare_matrices_with_options(&a, &c, &comp, &options) {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert_eq!(mismatch.mismatches.len(), 1)
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    // Dense matrices are not affected
+    let dense = mock_matrix![1, 0, 0;
+                             0, 0, 0];
+    assert!(compare_matrices_with_options(&a, &dense, &comp, &options).is_ok());
+    assert!(compare_matrices_with_options(&dense, &b, &comp, &options).is_ok());
+}
+
 /// A strategy producing pairs of dense and sparse matrices with the same dimensions.
 fn same_size_sparse_sparse_matrices(
 ) -> impl Strategy<Value = (MockSparseMatrix<i64>, MockSparseMatrix<i64>)> {
@@ -181,9 +288,9 @@ proptest! {
         sparse2 in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
     ) {
         let c = ExactElementwiseComparator;
-        prop_assert_eq!(compare_matrices(&sparse1, &sparse2, &c),
-                        compare_matrices(&sparse1.to_dense().unwrap(),
-                                          sparse2.to_dense().unwrap(),
+        prop_assert_eq!(without_nnz(compare_matrices(&sparse1, &sparse2, &c)),
+                        compare_matrices(sparse1.to_dense()?,
+                                          sparse2.to_dense()?,
                                           &c));
     }
 
@@ -192,9 +299,9 @@ proptest! {
         (sparse1, sparse2) in same_size_sparse_sparse_matrices()
     ) {
         let c = ExactElementwiseComparator;
-        prop_assert_eq!(compare_matrices(&sparse1, &sparse2, &c),
-                        compare_matrices(&sparse1.to_dense().unwrap(),
-                                          sparse2.to_dense().unwrap(),
+        prop_assert_eq!(without_nnz(compare_matrices(&sparse1, &sparse2, &c)),
+                        compare_matrices(sparse1.to_dense()?,
+                                          sparse2.to_dense()?,
                                           &c));
     }
 
@@ -204,9 +311,9 @@ proptest! {
         sparse2 in sparse_matrix_strategy_normal_f64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
     ) {
         let c = ExactElementwiseComparator;
-        prop_assert_eq!(compare_matrices(&sparse1, &sparse2, &c),
-                        compare_matrices(&sparse1.to_dense().unwrap(),
-                                          sparse2.to_dense().unwrap(),
+        prop_assert_eq!(without_nnz(compare_matrices(&sparse1, &sparse2, &c)),
+                        compare_matrices(sparse1.to_dense()?,
+                                          sparse2.to_dense()?,
                                           &c));
     }
 
@@ -0,0 +1,92 @@
+use matrixcompare::{
+    compare_sparsity_patterns, DimensionMismatch, Entry, MatrixComparisonFailure, PatternMismatch,
+};
+use matrixcompare_mock::{mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+#[test]
+fn sparsity_patterns_ignore_values() {
+    let a = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 1), (1, 2, 0)]);
+    let b = MockSparseMatrix::from_triplets(2, 3, vec![(1, 2, 5), (0, 1, -3)]);
+
+    assert_eq!(compare_sparsity_patterns(&a, &b), Ok(()));
+}
+
+#[test]
+fn sparsity_patterns_report_entries_stored_on_one_side() {
+    let a = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 1), (1, 2, 0), (1, 0, 3)]);
+    let b = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 1), (0, 0, 2)]);
+
+    let expected = MatrixComparisonFailure::PatternMismatch(PatternMismatch {
+        left_only: vec![(1, 0), (1, 2)],
+        right_only: vec![(0, 0)],
+    });
+    assert_eq!(compare_sparsity_patterns(&a, &b), Err(expected));
+}
+
+#[test]
+fn sparsity_patterns_dense_stores_every_entry() {
+    let dense = mock_matrix![1, 0;
+                             0, 0];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 0, 1)]);
+
+    let expected = MatrixComparisonFailure::PatternMismatch(PatternMismatch {
+        left_only: vec![(0, 1), (1, 0), (1, 1)],
+        right_only: vec![],
+    });
+    assert_eq!(compare_sparsity_patterns(&dense, &sparse), Err(expected));
+}
+
+#[test]
+fn sparsity_patterns_mismatched_dimensions() {
+    let a = MockSparseMatrix::<i64>::from_triplets(2, 3, vec![]);
+    let b = MockSparseMatrix::<i64>::from_triplets(3, 2, vec![]);
+
+    let expected = MatrixComparisonFailure::MismatchedDimensions(DimensionMismatch {
+        dim_left: (2, 3),
+        dim_right: (3, 2),
+    });
+    assert_eq!(compare_sparsity_patterns(&a, &b), Err(expected));
+}
+
+#[test]
+fn sparsity_patterns_invalid_entries() {
+    let a = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 1), (0, 1, 2)]);
+    let b = MockSparseMatrix::from_triplets(2, 3, vec![(2, 1, 1)]);
+    let valid = MockSparseMatrix::from_triplets(2, 3, vec![(0, 1, 1)]);
+
+    assert_eq!(
+        compare_sparsity_patterns(&a, &valid),
+        Err(MatrixComparisonFailure::DuplicateSparseEntry(Entry::Left(
+            (0, 1)
+        )))
+    );
+    assert_eq!(
+        compare_sparsity_patterns(&valid, &b),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Right((2, 1))
+        ))
+    );
+}
+
+proptest! {
+    #[test]
+    fn sparsity_pattern_comparison_is_symmetric(
+        sparse1 in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        sparse2 in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let result1 = compare_sparsity_patterns(&sparse1, &sparse2);
+        let result2 = compare_sparsity_patterns(&sparse2, &sparse1);
+        prop_assert_eq!(result1, reverse_result(result2));
+    }
+
+    #[test]
+    fn sparsity_pattern_matches_self(
+        sparse in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        prop_assert_eq!(compare_sparsity_patterns(&sparse, &sparse), Ok(()));
+    }
+}
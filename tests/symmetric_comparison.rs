@@ -0,0 +1,132 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_matrices, compare_matrices_with_options, ComparisonOptions, DimensionMismatch,
+    MatrixComparisonFailure,
+};
+use matrixcompare_core::Matrix;
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+fn symmetric() -> ComparisonOptions {
+    ComparisonOptions::default().symmetric(true)
+}
+
+fn mismatched_coords<T, E>(
+    result: Result<(), MatrixComparisonFailure<T, E>>,
+) -> Vec<(usize, usize)> {
+    match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert!(mismatch.symmetric);
+            mismatch
+                .mismatches
+                .iter()
+                .map(|element| (element.row, element.col))
+                .collect()
+        }
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn symmetric_mismatches_are_deduplicated() {
+    let a = mock_matrix![1, 2, 3;
+                         2, 4, 5;
+                         3, 5, 6];
+    let b = mock_matrix![1, 0, 3;
+                         0, 7, 5;
+                         3, 5, 6];
+
+    let c = ExactElementwiseComparator;
+    let result = compare_matrices_with_options(&a, &b, &c, &symmetric());
+    assert_eq!(mismatched_coords(result), vec![(0, 1), (1, 1)]);
+}
+
+#[test]
+fn unmirrored_lower_triangle_mismatches_are_reported() {
+    let a = mock_matrix![1, 2;
+                         2, 4];
+    let b = mock_matrix![1, 2;
+                         0, 4];
+
+    let c = ExactElementwiseComparator;
+    let result = compare_matrices_with_options(&a, &b, &c, &symmetric());
+    assert_eq!(mismatched_coords(result), vec![(1, 0)]);
+}
+
+#[test]
+fn symmetric_sparse_mismatches_are_deduplicated() {
+    let a = MockSparseMatrix::from_triplets(3, 3, vec![(0, 2, 1), (2, 0, 1), (1, 1, 2)]);
+    let b = mock_matrix![0, 0, 0;
+                         0, 2, 0;
+                         0, 0, 0];
+
+    let c = ExactElementwiseComparator;
+    let result = compare_matrices_with_options(&a, &b, &c, &symmetric());
+    assert_eq!(mismatched_coords(result), vec![(0, 2)]);
+}
+
+#[test]
+fn symmetric_comparison_reports_mismatched_dimensions() {
+    let a = mock_matrix![1, 2; 3, 4];
+    let b = mock_matrix![1, 2, 3; 4, 5, 6];
+
+    let c = ExactElementwiseComparator;
+    let result = compare_matrices_with_options(&a, &b, &c, &symmetric());
+    assert!(matches!(
+        result,
+        Err(MatrixComparisonFailure::MismatchedDimensions(_))
+    ));
+}
+
+#[test]
+fn symmetric_comparison_fails_for_non_square_matrices() {
+    let a = mock_matrix![1, 2, 3; 4, 5, 6];
+    let c = ExactElementwiseComparator;
+    let result = compare_matrices_with_options(&a, &a, &c, &symmetric());
+    assert_eq!(
+        result,
+        Err(MatrixComparisonFailure::NonSquareDimensions(
+            DimensionMismatch {
+                dim_left: (2, 3),
+                dim_right: (2, 3),
+            }
+        ))
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Matrices X (left) and Y (right) must be square.\n dim(X) = 2 x 3\n dim(Y) = 2 x 3"
+    );
+}
+
+proptest! {
+    #[test]
+    fn default_options_agree_with_compare_matrices(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let options = ComparisonOptions::default();
+        prop_assert_eq!(compare_matrices_with_options(&a, &b, &c, &options),
+                        compare_matrices(&a, &b, &c));
+    }
+
+    #[test]
+    fn symmetric_comparison_agrees_with_compare_matrices_on_success(
+        (a, b) in MATRIX_DIM_RANGE.prop_flat_map(|n| {
+            (dense_matrix_strategy_i64(Just(n), Just(n)), sparse_matrix_strategy_i64(Just(n), Just(n)))
+        })
+    ) {
+        prop_assume!(a.rows() == a.cols());
+        let c = ExactElementwiseComparator;
+        let result1 = compare_matrices_with_options(&a, &b, &c, &symmetric());
+        let result2 = compare_matrices_with_options(&b, &a, &c, &symmetric());
+
+        prop_assert_eq!(result1.is_ok(), compare_matrices(&a, &b, &c).is_ok());
+        prop_assert_eq!(result1, reverse_result(result2));
+    }
+}
@@ -0,0 +1,213 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    assert_matrix_eq, compare_matrices, compare_matrix_to_transpose_of, DimensionMismatch, Entry,
+    MatrixComparisonFailure, OutOfBoundsEntries, Transpose,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockCscMatrix,
+    MockCsrMatrix, MockDenseMatrix, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::{without_nnz, MATRIX_DIM_RANGE};
+
+fn transpose(matrix: &MockDenseMatrix<i64>) -> MockDenseMatrix<i64> {
+    use matrixcompare::Matrix;
+    let mut data = Vec::new();
+    for j in 0..matrix.cols() {
+        for i in 0..matrix.rows() {
+            data.push(*matrix.get(i, j).unwrap());
+        }
+    }
+    MockDenseMatrix::from_row_major(matrix.cols(), matrix.rows(), data)
+}
+
+#[test]
+fn matrix_equals_transpose_of_its_transpose() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b = mock_matrix![1, 4;
+                         2, 5;
+                         3, 6];
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrix_to_transpose_of(&a, &b, &c).is_ok());
+    assert!(compare_matrix_to_transpose_of(&b, &a, &c).is_ok());
+
+    let b_sparse = MockSparseMatrix::from_triplets(
+        3,
+        2,
+        vec![
+            (0, 0, 1),
+            (0, 1, 4),
+            (1, 0, 2),
+            (1, 1, 5),
+            (2, 0, 3),
+            (2, 1, 6),
+        ],
+    );
+    assert!(compare_matrix_to_transpose_of(&a, &b_sparse, &c).is_ok());
+
+    let b_csr = MockCsrMatrix::from_triplets(
+        3,
+        2,
+        vec![
+            (0, 0, 1),
+            (0, 1, 4),
+            (1, 0, 2),
+            (1, 1, 5),
+            (2, 0, 3),
+            (2, 1, 6),
+        ],
+    );
+    assert!(compare_matrix_to_transpose_of(&a, &b_csr, &c).is_ok());
+}
+
+#[test]
+fn transpose_adapter_can_be_used_in_assertions() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let triplets = vec![
+        (0, 0, 1),
+        (0, 1, 4),
+        (1, 0, 2),
+        (1, 1, 5),
+        (2, 0, 3),
+        (2, 1, 6),
+    ];
+    let b = mock_matrix![1, 4;
+                         2, 5;
+                         3, 6];
+    let b_sparse = MockSparseMatrix::from_triplets(3, 2, triplets.clone());
+    let b_csr = MockCsrMatrix::from_triplets(3, 2, triplets.clone());
+    let b_csc = MockCscMatrix::from_triplets(3, 2, triplets);
+
+    assert_matrix_eq!(a, Transpose(&b));
+    assert_matrix_eq!(Transpose(&a), b);
+    assert_matrix_eq!(a, Transpose(&b_sparse));
+    assert_matrix_eq!(a, Transpose(&b_csr));
+    assert_matrix_eq!(a, Transpose(&b_csc));
+    assert_matrix_eq!(Transpose(&Transpose(&a)), a);
+}
+
+#[test]
+fn transpose_adapter_reports_transposed_coordinates() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b_csr = MockCsrMatrix::from_triplets(3, 2, vec![(0, 0, 1), (2, 1, 6)]);
+
+    let err = compare_matrices(&a, Transpose(&b_csr), &ExactElementwiseComparator).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            let coords: Vec<_> = mismatch
+                .mismatches
+                .iter()
+                .map(|element| (element.row, element.col, element.right))
+                .collect();
+            assert_eq!(coords, vec![(0, 1, 0), (0, 2, 0), (1, 0, 0), (1, 1, 0)]);
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn mismatches_are_reported_at_left_coordinates() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let b = mock_matrix![1, 4;
+                         2, 5;
+                         9, 6];
+
+    let err = compare_matrix_to_transpose_of(&a, &b, &ExactElementwiseComparator).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 1);
+            let element = &mismatch.mismatches[0];
+            assert_eq!((element.row, element.col), (0, 2));
+            assert_eq!((element.left, element.right), (3, 9));
+            assert_eq!(mismatch.dims, Some((2, 3)));
+            assert!(mismatch
+                .comparator_description
+                .ends_with("Y (right) was transposed before comparison."));
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn dimensions_of_transpose_must_match() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let c = ExactElementwiseComparator;
+
+    let result = compare_matrix_to_transpose_of(&a, &a, &c);
+    assert_eq!(
+        result,
+        Err(MatrixComparisonFailure::MismatchedDimensions(
+            DimensionMismatch {
+                dim_left: (2, 3),
+                dim_right: (3, 2),
+            }
+        ))
+    );
+}
+
+#[test]
+fn invalid_sparse_entries_are_reported_at_right_coordinates() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6];
+    let c = ExactElementwiseComparator;
+
+    let out_of_bounds = MockSparseMatrix::from_triplets(3, 2, vec![(0, 2, 1)]);
+    assert_eq!(
+        compare_matrix_to_transpose_of(&a, &out_of_bounds, &c),
+        Err(MatrixComparisonFailure::SparseEntryOutOfBounds(
+            Entry::Right((0, 2))
+        ))
+    );
+
+    let out_of_bounds = MockSparseMatrix::from_triplets(3, 2, vec![(0, 2, 1), (3, 1, 1)]);
+    assert_eq!(
+        compare_matrix_to_transpose_of(&a, &out_of_bounds, &c),
+        Err(MatrixComparisonFailure::SparseEntriesOutOfBounds(
+            OutOfBoundsEntries {
+                left: vec![],
+                right: vec![(0, 2), (3, 1)]
+            }
+        ))
+    );
+
+    let duplicate = MockSparseMatrix::from_triplets(3, 2, vec![(2, 0, 3), (2, 0, 3)]);
+    assert_eq!(
+        compare_matrix_to_transpose_of(&a, &duplicate, &c),
+        Err(MatrixComparisonFailure::DuplicateSparseEntry(Entry::Right(
+            (2, 0)
+        )))
+    );
+}
+
+proptest! {
+    #[test]
+    fn transposed_comparison_agrees_with_explicit_transpose(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let expected = compare_matrices(&a, transpose(&b), &c).map_err(|failure| failure.kind());
+        let result = compare_matrix_to_transpose_of(&a, &b, &c).map_err(|failure| failure.kind());
+        prop_assert_eq!(result, expected);
+        prop_assert!(compare_matrix_to_transpose_of(&a, transpose(&a), &c).is_ok());
+    }
+
+    #[test]
+    fn sparse_transposed_comparison_agrees_with_dense(
+        a in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let b_dense = b.to_dense().unwrap();
+        let expected = compare_matrix_to_transpose_of(&a, &b_dense, &c);
+        let result = compare_matrix_to_transpose_of(&a, &b, &c);
+        prop_assert_eq!(without_nnz(result), expected);
+    }
+}
@@ -0,0 +1,165 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_matrices, compare_triangular, DimensionMismatch, MatrixComparisonFailure, Triangle,
+};
+use matrixcompare_mock::{i64_range, mock_matrix, square_dense_matrix_strategy, MockSparseMatrix};
+use proptest::prelude::*;
+
+mod common;
+use common::{reverse_result, MATRIX_DIM_RANGE};
+
+fn mismatched_coords<T, E>(
+    result: Result<(), MatrixComparisonFailure<T, E>>,
+) -> Vec<(usize, usize)> {
+    match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => mismatch
+            .mismatches
+            .iter()
+            .map(|element| (element.row, element.col))
+            .collect(),
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn cholesky_factor_ignores_upper_triangle() {
+    let computed = mock_matrix![2, 9, 9;
+                                1, 3, 9;
+                                4, 5, 6];
+    let reference = mock_matrix![2, 0, 0;
+                                 1, 3, 0;
+                                 4, 5, 6];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_triangular(&computed, &reference, Triangle::Lower, true, &c).is_ok());
+    assert!(compare_triangular(&computed, &reference, Triangle::Upper, false, &c).is_err());
+    assert!(compare_matrices(&computed, &reference, &c).is_err());
+}
+
+#[test]
+fn mismatches_have_global_coordinates() {
+    let a = mock_matrix![1, 2, 3;
+                         4, 5, 6;
+                         7, 8, 9];
+    let b = mock_matrix![0, 2, 0;
+                         0, 0, 6;
+                         0, 0, 0];
+
+    let c = ExactElementwiseComparator;
+    let upper = compare_triangular(&a, &b, Triangle::Upper, false, &c);
+    assert_eq!(mismatched_coords(upper), vec![(0, 2)]);
+    let upper_with_diagonal = compare_triangular(&a, &b, Triangle::Upper, true, &c);
+    assert_eq!(
+        mismatched_coords(upper_with_diagonal),
+        vec![(0, 0), (0, 2), (1, 1), (2, 2)]
+    );
+    let lower = compare_triangular(&a, &b, Triangle::Lower, false, &c);
+    assert_eq!(mismatched_coords(lower), vec![(1, 0), (2, 0), (2, 1)]);
+}
+
+#[test]
+fn criterion_notes_compared_part() {
+    let a = mock_matrix![1, 0; 2, 1];
+    let b = mock_matrix![1, 0; 3, 1];
+
+    let result = compare_triangular(&a, &b, Triangle::Lower, false, &ExactElementwiseComparator);
+    match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => assert!(mismatch
+            .comparator_description
+            .ends_with("Only the lower triangle, excluding the diagonal, was compared.")),
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn mismatch_reports_full_dimensions() {
+    let a = mock_matrix![1, 0, 0;
+                         2, 1, 0;
+                         0, 0, 1];
+    let b = mock_matrix![1, 0, 0;
+                         3, 1, 0;
+                         0, 0, 1];
+
+    let result = compare_triangular(&a, &b, Triangle::Lower, true, &ExactElementwiseComparator);
+    match result {
+        Err(MatrixComparisonFailure::MismatchedElements(mismatch)) => {
+            assert_eq!(mismatch.dims, Some((3, 3)));
+            assert_eq!(mismatch.total_element_pairs(), Some(9));
+            assert_eq!(mismatch.reverse().dims, Some((3, 3)));
+        }
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn sparse_matrices() {
+    let dense = mock_matrix![1, 5;
+                             0, 2];
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 0, 1), (1, 1, 2), (1, 0, 7)]);
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_triangular(&dense, &sparse, Triangle::Upper, true, &c).is_err());
+    assert!(compare_triangular(&sparse, &dense, Triangle::Lower, true, &c).is_err());
+    let sparse = MockSparseMatrix::from_triplets(2, 2, vec![(0, 0, 1), (1, 1, 2)]);
+    assert!(compare_triangular(&dense, &sparse, Triangle::Lower, true, &c).is_ok());
+}
+
+#[test]
+fn mismatched_dimensions() {
+    let a = mock_matrix![1, 2; 3, 4];
+    let b = mock_matrix![1, 2, 3; 4, 5, 6; 7, 8, 9];
+
+    let result = compare_triangular(&a, &b, Triangle::Lower, true, &ExactElementwiseComparator);
+    assert_eq!(
+        result,
+        Err(MatrixComparisonFailure::MismatchedDimensions(
+            DimensionMismatch {
+                dim_left: (2, 2),
+                dim_right: (3, 3)
+            }
+        ))
+    );
+}
+
+#[test]
+fn non_square_matrices_fail() {
+    let a = mock_matrix![1, 2, 3];
+    assert_eq!(
+        compare_triangular(&a, &a, Triangle::Upper, true, &ExactElementwiseComparator),
+        Err(MatrixComparisonFailure::NonSquareDimensions(
+            DimensionMismatch {
+                dim_left: (1, 3),
+                dim_right: (1, 3)
+            }
+        ))
+    );
+}
+
+proptest! {
+    #[test]
+    fn triangles_and_diagonal_cover_full_comparison(
+        (a, b) in MATRIX_DIM_RANGE.prop_flat_map(|n| {
+            let strategy = || square_dense_matrix_strategy(Just(n), i64_range());
+            (strategy(), strategy())
+        })
+    ) {
+        let c = ExactElementwiseComparator;
+        let lower = compare_triangular(&a, &b, Triangle::Lower, true, &c).is_ok();
+        let upper = compare_triangular(&a, &b, Triangle::Upper, false, &c).is_ok();
+        prop_assert_eq!(lower && upper, compare_matrices(&a, &b, &c).is_ok());
+    }
+
+    #[test]
+    fn triangular_comparison_is_symmetric(
+        (a, b) in MATRIX_DIM_RANGE.prop_flat_map(|n| {
+            let strategy = || square_dense_matrix_strategy(Just(n), i64_range());
+            (strategy(), strategy())
+        }),
+        include_diagonal in any::<bool>()
+    ) {
+        let c = ExactElementwiseComparator;
+        let forward = compare_triangular(&a, &b, Triangle::Lower, include_diagonal, &c);
+        let backward = compare_triangular(&b, &a, Triangle::Lower, include_diagonal, &c);
+        prop_assert_eq!(forward, reverse_result(backward));
+    }
+}
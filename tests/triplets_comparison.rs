@@ -0,0 +1,64 @@
+use matrixcompare::assert_matrix_eq_triplets;
+use matrixcompare_mock::{mock_matrix, MockSparseMatrix};
+
+#[test]
+fn dense_matrix_equals_triplets() {
+    let a = mock_matrix![1.0, 0.0, 0.0, 0.0;
+                         0.0, 0.0, 0.0, 0.0;
+                         0.0, 0.0, 0.0, 4.0];
+    assert_matrix_eq_triplets!(a, [(0, 0, 1.0), (2, 3, 4.0)]);
+    assert_matrix_eq_triplets!(a, [(2, 3, 4.0), (0, 0, 1.0),]);
+    assert_matrix_eq_triplets!(
+        a,
+        [(0, 0, 1.0), (2, 3, 4.0 + 1e-12)],
+        comp = abs,
+        tol = 1e-9
+    );
+    assert_matrix_eq_triplets!(&a, [(0, 0, 1.0), (1, 1, 0.0), (2, 3, 4.0)], comp = exact);
+}
+
+#[test]
+fn sparse_matrix_equals_triplets() {
+    let a = MockSparseMatrix::from_triplets(3, 2, vec![(0, 1, 2), (2, 0, 0)]);
+    assert_matrix_eq_triplets!(a, [(0, 1, 2)]);
+}
+
+#[test]
+fn zero_matrix_equals_empty_triplets() {
+    let a = mock_matrix![0, 0; 0, 0];
+    assert_matrix_eq_triplets!(a, []);
+}
+
+#[test]
+#[should_panic(expected = "Matrices X (left) and Y (right) have 1 mismatched element pairs")]
+fn unlisted_nonzero_is_mismatch() {
+    let a = mock_matrix![1, 0; 0, 3];
+    assert_matrix_eq_triplets!(a, [(0, 0, 1)]);
+}
+
+#[test]
+#[should_panic(expected = "Matrices result (left) and expected (right)")]
+fn triplets_accept_labels() {
+    let a = mock_matrix![1.0, 0.0; 0.0, 3.0];
+    assert_matrix_eq_triplets!(
+        a,
+        [(0, 0, 1.0)],
+        comp = abs,
+        tol = 1e-9,
+        labels = ("result", "expected")
+    );
+}
+
+#[test]
+#[should_panic(expected = "At least one sparse entry is out of bounds. Example: Right(2, 0).")]
+fn out_of_bounds_triplet_is_reported() {
+    let a = mock_matrix![1, 0; 0, 3];
+    assert_matrix_eq_triplets!(a, [(0, 0, 1), (1, 1, 3), (2, 0, 1)]);
+}
+
+#[test]
+#[should_panic(expected = "At least one duplicate sparse entry detected. Example: Right(1, 1).")]
+fn duplicate_triplet_is_reported() {
+    let a = mock_matrix![1, 0; 0, 3];
+    assert_matrix_eq_triplets!(a, [(0, 0, 1), (1, 1, 3), (1, 1, 3)]);
+}
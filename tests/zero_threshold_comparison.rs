@@ -0,0 +1,101 @@
+use matrixcompare::comparators::ExactElementwiseComparator;
+use matrixcompare::{
+    compare_matrices, compare_matrices_with_zero_threshold, MatrixComparisonFailure,
+};
+use matrixcompare_mock::{
+    dense_matrix_strategy_i64, mock_matrix, sparse_matrix_strategy_i64, MockSparseMatrix,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::MATRIX_DIM_RANGE;
+
+#[test]
+fn denoised_against_sparse_reference() {
+    let denoised = mock_matrix![1.0,   1e-12;
+                                -1e-9, 2.0];
+    let reference = MockSparseMatrix::from_triplets(2, 2, vec![(0, 0, 1.0), (1, 1, 2.0)]);
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_with_zero_threshold(&denoised, &reference, 1e-6, &c).is_ok());
+    assert!(compare_matrices(&denoised, &reference, &c).is_err());
+}
+
+#[test]
+fn threshold_is_applied_to_both_matrices() {
+    let a = mock_matrix![1, -2, 3];
+    let b = mock_matrix![-1, 2, 3];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_with_zero_threshold(&a, &b, 3, &c).is_ok());
+    assert!(compare_matrices_with_zero_threshold(&b, &a, 3, &c).is_ok());
+    assert!(compare_matrices_with_zero_threshold(&a, &b, 2, &c).is_err());
+}
+
+#[test]
+fn threshold_is_exclusive() {
+    let a = mock_matrix![2.0, 0.0];
+    let b = mock_matrix![0.0, 0.0];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_with_zero_threshold(&a, &b, 2.0, &c).is_err());
+    assert!(compare_matrices_with_zero_threshold(&a, &b, 2.5, &c).is_ok());
+}
+
+#[test]
+fn nan_is_never_replaced() {
+    let a = mock_matrix![f64::NAN];
+    let b = mock_matrix![0.0];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_with_zero_threshold(&a, &b, f64::INFINITY, &c).is_err());
+}
+
+#[test]
+fn mismatches_contain_thresholded_values() {
+    let a = mock_matrix![1, 5];
+    let b = mock_matrix![7, 0];
+
+    let err =
+        compare_matrices_with_zero_threshold(&a, &b, 2, &ExactElementwiseComparator).unwrap_err();
+    match err {
+        MatrixComparisonFailure::MismatchedElements(mismatch) => {
+            assert_eq!(mismatch.mismatches.len(), 2);
+            let element = &mismatch.mismatches[0];
+            assert_eq!((element.row, element.col), (0, 0));
+            assert_eq!((element.left, element.right), (0, 7));
+            assert!(mismatch
+                .comparator_description
+                .ends_with("Elements x with |x| < 2 were treated as zero before comparison."));
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn unsigned_elements_are_thresholded() {
+    let a = mock_matrix![1u32, 5];
+    let b = mock_matrix![0u32, 5];
+
+    let c = ExactElementwiseComparator;
+    assert!(compare_matrices_with_zero_threshold(&a, &b, 2, &c).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Zero threshold must be non-negative.")]
+fn negative_threshold_panics() {
+    let a = mock_matrix![1];
+    let _ = compare_matrices_with_zero_threshold(&a, &a, -1, &ExactElementwiseComparator);
+}
+
+proptest! {
+    #[test]
+    fn zero_threshold_agrees_with_compare_matrices(
+        a in dense_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE),
+        b in sparse_matrix_strategy_i64(MATRIX_DIM_RANGE, MATRIX_DIM_RANGE)
+    ) {
+        let c = ExactElementwiseComparator;
+        let thresholded_ok = compare_matrices_with_zero_threshold(&a, &b, 0, &c).is_ok();
+        prop_assert_eq!(thresholded_ok, compare_matrices(&a, &b, &c).is_ok());
+    }
+}